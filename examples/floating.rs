@@ -0,0 +1,31 @@
+use macroquad::prelude::*;
+use macroquad_virtual_joystick::{IdleAppearance, Joystick, JoystickMode};
+
+#[macroquad::main("Floating Joystick")]
+async fn main() {
+    let mut joystick = Joystick::new(150.0, 450.0, 150.0);
+    // a press anywhere in the left half spawns the joystick
+    joystick.set_mode(JoystickMode::Floating {
+        activation: Rect::new(0.0, 0.0, screen_width() / 2.0, screen_height()),
+        return_on_release: true,
+    });
+    joystick.set_idle_appearance(IdleAppearance::Outline {
+        thickness: 2.0,
+        color: LIGHTGRAY,
+    });
+    loop {
+        clear_background(WHITE);
+
+        let joystick_event = joystick.update();
+        draw_text(
+            &format!("{:?}", joystick_event.direction),
+            20.0,
+            40.0,
+            30.0,
+            DARKGRAY,
+        );
+
+        joystick.render();
+        next_frame().await
+    }
+}
@@ -0,0 +1,29 @@
+use macroquad::prelude::*;
+use macroquad_virtual_joystick::{Joystick, JoystickLabel};
+
+#[macroquad::main("Handedness")]
+async fn main() {
+    const SPEED: f32 = 2.5;
+    let mut position = Vec2::new(screen_width() / 2.0, screen_height() / 4.0);
+    let mut joystick = Joystick::new(100.0, 200.0, 100.0);
+    let mut label = JoystickLabel::new("press H to switch hands");
+    label.offset = Vec2::new(40.0, 10.0);
+    joystick.set_label(Some(label));
+    loop {
+        clear_background(WHITE);
+
+        // the joystick is mirrored across the center of the screen,
+        // a drag which is active while switching is cancelled
+        if is_key_pressed(KeyCode::H) {
+            joystick.set_mirrored(!joystick.is_mirrored());
+        }
+
+        let joystick_event = joystick.update();
+        position += joystick_event.axes() * SPEED;
+
+        draw_circle(position.x, position.y, 50.0, YELLOW);
+
+        joystick.render();
+        next_frame().await
+    }
+}
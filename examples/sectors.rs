@@ -0,0 +1,35 @@
+use macroquad::prelude::*;
+use macroquad_virtual_joystick::Joystick;
+
+#[macroquad::main("Direction Sectors")]
+async fn main() {
+    let mut joystick = Joystick::new(200.0, 200.0, 200.0);
+    // vertical drags report less intensity, which shifts the diagonal sectors
+    joystick.set_axis_scale(Vec2::new(1.0, 0.6));
+    let mut show_sectors = true;
+    loop {
+        clear_background(WHITE);
+
+        if is_key_pressed(KeyCode::S) {
+            show_sectors = !show_sectors;
+        }
+
+        let joystick_event = joystick.update();
+        draw_text(
+            &format!(
+                "{:?} (press S to toggle the sectors)",
+                joystick_event.direction
+            ),
+            20.0,
+            40.0,
+            30.0,
+            DARKGRAY,
+        );
+
+        joystick.render();
+        if show_sectors {
+            joystick.render_sectors();
+        }
+        next_frame().await
+    }
+}
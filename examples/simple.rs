@@ -10,7 +10,7 @@ async fn main() {
         clear_background(WHITE);
 
         let joystick_event = joystick.update();
-        position += joystick_event.direction.to_local() * joystick_event.intensity * SPEED;
+        position += joystick_event.axes() * SPEED;
 
         draw_circle(position.x, position.y, 50.0, YELLOW);
 
@@ -0,0 +1,53 @@
+use macroquad::prelude::*;
+use macroquad_virtual_joystick::Joystick;
+
+/// transform which draws a full-sized layout scaled to half size into one half of the screen
+fn half_screen_transform(right: bool) -> Mat3 {
+    let offset_x = if right { screen_width() / 2.0 } else { 0.0 };
+    Mat3::from_scale_angle_translation(
+        Vec2::new(0.5, 0.5),
+        0.0,
+        Vec2::new(offset_x, screen_height() / 4.0),
+    )
+}
+
+#[macroquad::main("Split Screen Joysticks")]
+async fn main() {
+    const SPEED: f32 = 2.5;
+    let mut positions = [
+        Vec2::new(screen_width() / 4.0, screen_height() / 4.0),
+        Vec2::new(screen_width() * 3.0 / 4.0, screen_height() / 4.0),
+    ];
+    // both joysticks share the same layout, only their transforms differ
+    let mut joysticks = [
+        Joystick::new(100.0, 200.0, 100.0),
+        Joystick::new(100.0, 200.0, 100.0),
+    ];
+    loop {
+        clear_background(WHITE);
+
+        for (i, joystick) in joysticks.iter_mut().enumerate() {
+            // update the transforms every frame in case the window was resized
+            joystick.set_transform(half_screen_transform(i == 1));
+
+            let joystick_event = joystick.update();
+            positions[i] += joystick_event.direction.to_local() * joystick_event.intensity * SPEED;
+        }
+
+        draw_line(
+            screen_width() / 2.0,
+            0.0,
+            screen_width() / 2.0,
+            screen_height(),
+            2.0,
+            GRAY,
+        );
+        draw_circle(positions[0].x, positions[0].y, 25.0, YELLOW);
+        draw_circle(positions[1].x, positions[1].y, 25.0, ORANGE);
+
+        for joystick in &joysticks {
+            joystick.render();
+        }
+        next_frame().await
+    }
+}
@@ -0,0 +1,172 @@
+//! a [`Joystick`](crate::Joystick) for aiming, e.g. the right stick of a twin-stick shooter
+
+use crate::{FrameStamp, Joystick, JoystickEvent, JoystickInput, JoystickPhase, MacroquadInput};
+
+/// the event of the [`AimJoystick`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AimEvent {
+    /// the event of the underlying joystick
+    pub event: JoystickEvent,
+
+    /// the angle (in radians) in which is aimed while the joystick is held
+    ///
+    /// This is the angle of the last update in which the knob was outside of the dead zone,
+    /// so the aim doesn't jump back to zero while the knob passes the center.
+    /// `None` if the joystick is not held or wasn't moved out of the dead zone yet.
+    pub aim_angle: Option<f32>,
+
+    /// whether the intensity reached the fire threshold, see [`AimJoystick::set_fire_threshold`]
+    pub firing: bool,
+
+    /// the final aim angle (in radians) if the joystick was released in the update
+    ///
+    /// this is `None` if the joystick was released without aiming
+    pub released_shot: Option<f32>,
+}
+
+/// a [`Joystick`] which reports an aim angle and whether to fire
+///
+/// The joystick aims while it is held and fires continuously while the intensity is at least
+/// the fire threshold. When it's released, the last aim angle is reported once as
+/// [`AimEvent::released_shot`], e.g. for a single aimed shot.
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::Vec2;
+/// use macroquad_virtual_joystick::{AimJoystick, SimulatedInput};
+///
+/// let mut aim = AimJoystick::new(100.0, 200.0, 50.0);
+///
+/// let input = SimulatedInput::new().with_mouse(Vec2::new(100.0, 205.0), true);
+/// let event = aim.update_with_input(&input, 0.0);
+/// assert!((event.aim_angle.unwrap() - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+/// assert!(!event.firing);
+///
+/// let input = SimulatedInput::new().with_mouse(Vec2::new(120.0, 200.0), true);
+/// let event = aim.update_with_input(&input, 0.0);
+/// assert_eq!(event.aim_angle, Some(0.0));
+/// assert!(event.firing);
+///
+/// let input = SimulatedInput::new().with_mouse(Vec2::new(120.0, 200.0), false);
+/// let event = aim.update_with_input(&input, 0.0);
+/// assert_eq!(event.released_shot, Some(0.0));
+/// assert!(!event.firing);
+/// ```
+pub struct AimJoystick {
+    joystick: Joystick,
+    fire_threshold: f32,
+    aim_angle: Option<f32>,
+    output: AimEvent,
+    frame: Option<FrameStamp>,
+}
+
+impl AimJoystick {
+    /// create a new aim joystick with the defaults of [`Joystick::new`]
+    pub fn new(x: f32, y: f32, size: f32) -> Self {
+        Self::from(Joystick::new(x, y, size))
+    }
+
+    /// the underlying joystick
+    pub fn joystick(&self) -> &Joystick {
+        &self.joystick
+    }
+
+    /// the underlying joystick, e.g. to configure it
+    pub fn joystick_mut(&mut self) -> &mut Joystick {
+        &mut self.joystick
+    }
+
+    /// set the intensity from which the joystick fires
+    ///
+    /// the threshold is clamped to `0.0..=1.0`, a threshold of `0.0` fires as soon as the knob
+    /// leaves the dead zone
+    ///
+    /// Defaults to `0.5`.
+    pub fn set_fire_threshold(&mut self, fire_threshold: f32) {
+        self.fire_threshold = fire_threshold.clamp(0., 1.);
+    }
+
+    /// the intensity from which the joystick fires, see [`AimJoystick::set_fire_threshold`]
+    pub fn fire_threshold(&self) -> f32 {
+        self.fire_threshold
+    }
+
+    /// the event of the last update
+    pub fn event(&self) -> AimEvent {
+        self.output
+    }
+
+    /// the current aim angle, see [`AimEvent::aim_angle`]
+    pub fn aim_angle(&self) -> Option<f32> {
+        self.output.aim_angle
+    }
+
+    /// whether the joystick is firing, see [`AimEvent::firing`]
+    pub fn is_firing(&self) -> bool {
+        self.output.firing
+    }
+
+    /// update the joystick once per frame of macroquad, see [`Joystick::update`]
+    pub fn update(&mut self) -> AimEvent {
+        self.update_in_frame(&MacroquadInput, FrameStamp::current())
+    }
+
+    /// update the joystick with the given frame time, see [`Joystick::update_with_dt`]
+    pub fn update_with_dt(&mut self, dt: f32) -> AimEvent {
+        self.update_with_input(&MacroquadInput, dt)
+    }
+
+    /// update the joystick from the given input once per frame
+    ///
+    /// see [`Joystick::update_in_frame`]
+    pub fn update_in_frame(&mut self, input: &impl JoystickInput, frame: FrameStamp) -> AimEvent {
+        if let Some(first) = self.frame {
+            if first.is_same_frame(&frame) {
+                return self.output;
+            }
+        }
+        self.frame = Some(frame);
+        self.update_with_input(input, frame.frame_time)
+    }
+
+    /// update the joystick from the given input, see [`Joystick::update_with_input`]
+    pub fn update_with_input(&mut self, input: &impl JoystickInput, dt: f32) -> AimEvent {
+        let event = self.joystick.update_with_input(input, dt);
+        if event.intensity > 0. {
+            self.aim_angle = Some(event.angle);
+        }
+        let held = matches!(event.phase, JoystickPhase::Start | JoystickPhase::Drag);
+        let released_shot = if event.phase == JoystickPhase::Release {
+            self.aim_angle
+        } else {
+            None
+        };
+        if !held {
+            self.aim_angle = None;
+        }
+        self.output = AimEvent {
+            event,
+            aim_angle: self.aim_angle,
+            firing: held && event.intensity > 0. && event.intensity >= self.fire_threshold,
+            released_shot,
+        };
+        self.output
+    }
+
+    /// render the joystick, see [`Joystick::render`]
+    pub fn render(&self) {
+        self.joystick.render();
+    }
+}
+
+impl From<Joystick> for AimJoystick {
+    fn from(joystick: Joystick) -> Self {
+        Self {
+            joystick,
+            fire_threshold: 0.5,
+            aim_angle: None,
+            output: AimEvent::default(),
+            frame: None,
+        }
+    }
+}
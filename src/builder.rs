@@ -0,0 +1,374 @@
+//! validated construction of the [`Joystick`](crate::Joystick)
+//!
+//! The setters of the joystick accept most values and fall back to a sensible behavior,
+//! the [`JoystickBuilder`] reports conflicting options as a [`JoystickConfigError`] instead.
+
+use std::error::Error;
+use std::fmt;
+
+use macroquad::prelude::{Rect, Vec2};
+
+use crate::{
+    DirectionResolver, Joystick, JoystickAxis, JoystickMode, JoystickRenderState, JoystickRenderer,
+    JoystickStyle, UiScale,
+};
+
+/// a conflict in the configuration of a [`Joystick`]
+///
+/// see [`JoystickBuilder::build`] and [`Joystick::validate`]
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum JoystickConfigError {
+    /// the size is not positive
+    InvalidSize(f32),
+    /// the knob is larger than the background
+    KnobLargerThanBackground {
+        /// the diameter of the knob
+        knob_size: f32,
+        /// the diameter of the background
+        size: f32,
+    },
+    /// the thresholds of the bands are not in ascending order
+    BandsOutOfOrder(Vec<f32>),
+    /// a component of the axis scale is not positive
+    InvalidAxisScale(Vec2),
+    /// the grab forgiveness is not positive, so the joystick can't be grabbed
+    InvalidGrabForgiveness(f32),
+    /// the commit distance is larger than the background, so a drag inside of it never commits
+    CommitDistanceTooLarge {
+        /// the commit distance
+        commit_distance: f32,
+        /// the diameter of the background
+        size: f32,
+    },
+    /// the time scale is negative
+    InvalidTimeScale(f32),
+    /// the dead zone is not smaller than the saturation or one of them is outside of
+    /// `0.0..=1.0`
+    InvalidRadialZones {
+        /// the radius of the dead zone
+        dead_zone: f32,
+        /// the radius of the saturation
+        saturation: f32,
+    },
+    /// the hit rectangle has no area, so it can't be pressed
+    EmptyHitRect(Rect),
+    /// the travel radius is not positive
+    InvalidTravelRadius(f32),
+    /// the diameter of the knob is not positive or not finite
+    InvalidKnobSize(f32),
+}
+
+impl fmt::Display for JoystickConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSize(size) => write!(f, "the size {} is not positive", size),
+            Self::KnobLargerThanBackground { knob_size, size } => write!(
+                f,
+                "the knob ({}) is larger than the background ({})",
+                knob_size, size
+            ),
+            Self::BandsOutOfOrder(bands) => {
+                write!(f, "the bands {:?} are not in ascending order", bands)
+            }
+            Self::InvalidAxisScale(scale) => {
+                write!(f, "the axis scale {} is not positive", scale)
+            }
+            Self::InvalidGrabForgiveness(forgiveness) => {
+                write!(f, "the grab forgiveness {} is not positive", forgiveness)
+            }
+            Self::CommitDistanceTooLarge {
+                commit_distance,
+                size,
+            } => write!(
+                f,
+                "the commit distance {} is larger than the background ({})",
+                commit_distance, size
+            ),
+            Self::InvalidTimeScale(time_scale) => {
+                write!(f, "the time scale {} is negative", time_scale)
+            }
+            Self::InvalidRadialZones {
+                dead_zone,
+                saturation,
+            } => write!(
+                f,
+                "the dead zone {} and the saturation {} are not ordered in 0.0..=1.0",
+                dead_zone, saturation
+            ),
+            Self::EmptyHitRect(rect) => write!(f, "the hit rectangle {:?} has no area", rect),
+            Self::InvalidTravelRadius(radius) => {
+                write!(f, "the travel radius {} is not positive", radius)
+            }
+            Self::InvalidKnobSize(knob_size) => {
+                write!(f, "the knob size {} is not positive", knob_size)
+            }
+        }
+    }
+}
+
+impl Error for JoystickConfigError {}
+
+/// builder of a [`Joystick`] which validates the configuration
+///
+/// Unlike [`Joystick::new`] and the setters, [`JoystickBuilder::build`] doesn't clamp
+/// invalid values but returns a [`JoystickConfigError`].
+///
+/// # Examples
+/// ```
+/// use macroquad_virtual_joystick::{JoystickBuilder, JoystickConfigError};
+///
+/// let joystick = JoystickBuilder::new(100.0, 200.0, 50.0)
+///     .knob_size(20.0)
+///     .bands(&[0.3, 0.7])
+///     .build()
+///     .unwrap();
+/// assert_eq!(joystick.center().x, 100.0);
+///
+/// let error = JoystickBuilder::new(100.0, 200.0, 50.0)
+///     .knob_size(60.0)
+///     .build()
+///     .unwrap_err();
+/// assert!(matches!(error, JoystickConfigError::KnobLargerThanBackground { .. }));
+/// ```
+pub struct JoystickBuilder {
+    center: Vec2,
+    size: f32,
+    knob_size: Option<f32>,
+    bands: Vec<f32>,
+    grab_forgiveness: f32,
+    commit_distance: f32,
+    axis_scale: Vec2,
+    time_scale: f32,
+    dead_zone: f32,
+    saturation: f32,
+    ui_scale: UiScale,
+    mode: JoystickMode,
+    axis: JoystickAxis,
+    style: Option<JoystickStyle>,
+    /// the renderers of the background and the knob
+    renderers: Option<(JoystickRenderer, JoystickRenderer)>,
+    resolver: DirectionResolver,
+    hit_rect: Option<Rect>,
+    travel_radius: Option<f32>,
+}
+
+impl JoystickBuilder {
+    /// create a new builder with the same defaults as [`Joystick::new`]
+    pub fn new(x: f32, y: f32, size: f32) -> Self {
+        Self {
+            center: Vec2::new(x, y),
+            size,
+            knob_size: None,
+            bands: Vec::new(),
+            grab_forgiveness: 1.,
+            commit_distance: 0.,
+            axis_scale: Vec2::ONE,
+            time_scale: 1.,
+            dead_zone: 0.,
+            saturation: 1.,
+            ui_scale: UiScale::ONE,
+            mode: JoystickMode::Fixed,
+            axis: JoystickAxis::Both,
+            style: None,
+            renderers: None,
+            resolver: DirectionResolver::default(),
+            hit_rect: None,
+            travel_radius: None,
+        }
+    }
+
+    /// set the diameter of the knob, defaults to half of the size
+    ///
+    /// this takes precedence over [`JoystickStyle::knob_scale`]
+    pub fn knob_size(mut self, knob_size: f32) -> Self {
+        self.knob_size = Some(knob_size);
+        self
+    }
+
+    /// see [`Joystick::set_bands`]
+    pub fn bands(mut self, thresholds: &[f32]) -> Self {
+        self.bands = thresholds.to_vec();
+        self
+    }
+
+    /// see [`Joystick::set_grab_forgiveness`]
+    pub fn grab_forgiveness(mut self, grab_forgiveness: f32) -> Self {
+        self.grab_forgiveness = grab_forgiveness;
+        self
+    }
+
+    /// see [`Joystick::set_commit_distance`]
+    pub fn commit_distance(mut self, commit_distance: f32) -> Self {
+        self.commit_distance = commit_distance;
+        self
+    }
+
+    /// see [`Joystick::set_axis_scale`]
+    pub fn axis_scale(mut self, axis_scale: Vec2) -> Self {
+        self.axis_scale = axis_scale;
+        self
+    }
+
+    /// see [`Joystick::set_time_scale`]
+    pub fn time_scale(mut self, time_scale: f32) -> Self {
+        self.time_scale = time_scale;
+        self
+    }
+
+    /// see [`Joystick::set_dead_zone`]
+    pub fn dead_zone(mut self, dead_zone: f32) -> Self {
+        self.dead_zone = dead_zone;
+        self
+    }
+
+    /// see [`Joystick::set_saturation`]
+    pub fn saturation(mut self, saturation: f32) -> Self {
+        self.saturation = saturation;
+        self
+    }
+
+    /// see [`Joystick::set_mode`]
+    ///
+    /// the activation area of [`JoystickMode::Floating`] is not scaled by the
+    /// [`JoystickBuilder::ui_scale`]
+    pub fn mode(mut self, mode: JoystickMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// see [`Joystick::set_axis`]
+    pub fn axis(mut self, axis: JoystickAxis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// set the colors and textures, see [`Joystick::set_style`]
+    pub fn style(mut self, style: JoystickStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// draw the background and the knob with custom renderers, see
+    /// [`Joystick::from_custom_renderers`]
+    ///
+    /// a style (see [`JoystickBuilder::style`]) takes precedence over the renderers
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{draw_circle, GREEN, RED};
+    /// use macroquad_virtual_joystick::JoystickBuilder;
+    ///
+    /// let joystick = JoystickBuilder::new(100.0, 200.0, 50.0)
+    ///     .knob_size(20.0)
+    ///     .renderers(
+    ///         |x, y, radius, _| draw_circle(x, y, radius, RED),
+    ///         |x, y, radius, _| draw_circle(x, y, radius, GREEN),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(joystick.knob_size(), 20.0);
+    /// ```
+    pub fn renderers(
+        mut self,
+        render_background: impl FnMut(f32, f32, f32, JoystickRenderState) + 'static,
+        render_knob: impl FnMut(f32, f32, f32, JoystickRenderState) + 'static,
+    ) -> Self {
+        self.renderers = Some((Box::new(render_background), Box::new(render_knob)));
+        self
+    }
+
+    /// see [`Joystick::set_direction_resolver`]
+    pub fn direction_resolver(mut self, resolver: DirectionResolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// see [`Joystick::set_hit_rect`]
+    ///
+    /// the rectangle is scaled by the [`JoystickBuilder::ui_scale`] like the position
+    pub fn hit_rect(mut self, hit_rect: Rect) -> Self {
+        self.hit_rect = Some(hit_rect);
+        self
+    }
+
+    /// see [`Joystick::set_travel_radius`], defaults to half of the size
+    ///
+    /// the radius is scaled by the [`JoystickBuilder::ui_scale`] like the size
+    pub fn travel_radius(mut self, travel_radius: f32) -> Self {
+        self.travel_radius = Some(travel_radius);
+        self
+    }
+
+    /// set the scale of the position, the size, the knob size and the travel radius, see
+    /// [`UiScale`]
+    ///
+    /// the other options are relative to the size or not lengths, so they are not scaled
+    pub fn ui_scale(mut self, ui_scale: UiScale) -> Self {
+        self.ui_scale = ui_scale;
+        self
+    }
+
+    /// build the joystick
+    ///
+    /// returns an error if the options conflict, see [`Joystick::validate`]
+    pub fn build(self) -> Result<Joystick, JoystickConfigError> {
+        let center = self.center * self.ui_scale.0;
+        let size = self.ui_scale.scale(self.size);
+        // the constructor would clamp the size
+        if size.is_nan() || size <= 0. {
+            return Err(JoystickConfigError::InvalidSize(size));
+        }
+        let mut joystick = match self.renderers {
+            Some((render_background, render_knob)) => Joystick::from_custom_renderers(
+                center.x,
+                center.y,
+                size,
+                size / 2.,
+                render_background,
+                render_knob,
+            ),
+            None => Joystick::new(center.x, center.y, size),
+        };
+        joystick.set_style(self.style);
+        if let Some(knob_size) = self.knob_size {
+            joystick.knob.radius = self.ui_scale.scale(knob_size) / 2.;
+        }
+        joystick.bands = self.bands;
+        joystick.grab_forgiveness = self.grab_forgiveness;
+        joystick.commit_distance = self.commit_distance;
+        joystick.axis_scale = self.axis_scale;
+        joystick.time_scale = self.time_scale;
+        joystick.dead_zone = self.dead_zone;
+        joystick.saturation = self.saturation;
+        joystick.mode = self.mode;
+        joystick.axis = self.axis;
+        joystick.resolver = self.resolver;
+        if let Some(travel_radius) = self.travel_radius {
+            joystick.travel_radius = self.ui_scale.scale(travel_radius);
+        }
+        let scale = self.ui_scale.0;
+        joystick.hit_rect = self.hit_rect.map(|rect| {
+            Rect::new(
+                rect.x * scale,
+                rect.y * scale,
+                rect.w * scale,
+                rect.h * scale,
+            )
+        });
+        joystick.validate()?;
+        Ok(joystick)
+    }
+}
+
+impl fmt::Debug for JoystickBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoystickBuilder")
+            .field("center", &self.center)
+            .field("size", &self.size)
+            .field("knob_size", &self.knob_size)
+            .field("style", &self.style)
+            .field("custom_renderers", &self.renderers.is_some())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,411 @@
+//! a virtual button which can be used next to the [`Joystick`](crate::Joystick)
+
+use macroquad::prelude::{
+    draw_circle, draw_rectangle, get_frame_time, Rect, Touch, TouchPhase, Vec2,
+};
+
+use crate::{
+    order_touch_phases, InputSource, JoystickInput, MacroquadInput, BACKGROUND_COLOR, KNOB_COLOR,
+};
+
+/// hit area of a [`TouchButton`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ButtonShape {
+    /// a circle
+    Circle {
+        /// the center of the circle
+        center: Vec2,
+        /// the radius of the circle
+        radius: f32,
+    },
+    /// an axis aligned rectangle
+    Rect(Rect),
+}
+
+impl ButtonShape {
+    /// whether the position is inside of the shape
+    pub fn contains(&self, position: Vec2) -> bool {
+        match *self {
+            Self::Circle { center, radius } => (position - center).length() < radius,
+            Self::Rect(rect) => rect.contains(position),
+        }
+    }
+
+    /// the bounding rectangle of the shape
+    pub fn bounds(&self) -> Rect {
+        match *self {
+            Self::Circle { center, radius } => Rect::new(
+                center.x - radius,
+                center.y - radius,
+                radius * 2.,
+                radius * 2.,
+            ),
+            Self::Rect(rect) => rect,
+        }
+    }
+
+    /// the center of the shape
+    pub(crate) fn center(&self) -> Vec2 {
+        match *self {
+            Self::Circle { center, .. } => center,
+            Self::Rect(rect) => rect.center(),
+        }
+    }
+
+    /// the shape moved by `offset`
+    pub(crate) fn translated(self, offset: Vec2) -> Self {
+        match self {
+            Self::Circle { center, radius } => Self::Circle {
+                center: center + offset,
+                radius,
+            },
+            Self::Rect(rect) => Self::Rect(rect.offset(offset)),
+        }
+    }
+
+    /// the shape scaled by `factor` around its center
+    pub(crate) fn scaled(self, factor: f32) -> Self {
+        match self {
+            Self::Circle { center, radius } => Self::Circle {
+                center,
+                radius: radius * factor,
+            },
+            Self::Rect(rect) => {
+                let size = rect.size() * factor;
+                let origin = rect.center() - size / 2.;
+                Self::Rect(Rect::new(origin.x, origin.y, size.x, size.y))
+            }
+        }
+    }
+
+    /// the radius of the largest circle around the center which fits into the shape
+    pub(crate) fn inner_radius(&self) -> f32 {
+        match *self {
+            Self::Circle { radius, .. } => radius,
+            Self::Rect(rect) => rect.w.min(rect.h) / 2.,
+        }
+    }
+}
+
+/// renderer of the [`TouchButton`], see [`TouchButton::set_render`]
+pub type ButtonRenderer = Box<dyn Fn(&ButtonShape, bool)>;
+
+/// a callback of the [`TouchButton`], see [`TouchButton::on_press`]
+///
+/// the callback gets the duration (in seconds) of the press, see [`TouchButton::hold_time`]
+pub type ButtonCallback = Box<dyn FnMut(f32)>;
+
+/// a virtual button which is pressed by a touch or the mouse
+///
+/// A touch or a click which starts inside of the button presses it until it is released,
+/// even if it leaves the button in between. Like the [`Joystick`](crate::Joystick), the
+/// mouse is only used while there are no touches and only one touch can press the button.
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::{TouchPhase, Vec2};
+/// use macroquad_virtual_joystick::{SimulatedInput, TouchButton};
+///
+/// let mut button = TouchButton::circle(300.0, 200.0, 30.0);
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(310.0, 200.0));
+/// button.update_with_input(&input, 1.0 / 60.0);
+/// assert!(button.is_pressed());
+/// assert!(button.is_just_pressed());
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Stationary, Vec2::new(310.0, 200.0));
+/// button.update_with_input(&input, 1.0 / 60.0);
+/// assert!(button.is_held());
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Ended, Vec2::new(310.0, 200.0));
+/// button.update_with_input(&input, 1.0 / 60.0);
+/// assert!(!button.is_pressed());
+/// assert!(button.is_just_released());
+/// ```
+pub struct TouchButton {
+    shape: ButtonShape,
+    render_fn: ButtonRenderer,
+    pressed: bool,
+    just_pressed: bool,
+    just_released: bool,
+    source: InputSource,
+    touch_id: u64,
+    released_touch: Option<u64>,
+    mouse_was_down: bool,
+    hold_time: f32,
+    enabled: bool,
+    visible: bool,
+    on_press: Option<ButtonCallback>,
+    on_release: Option<ButtonCallback>,
+}
+
+impl TouchButton {
+    /// create a new button with the shape
+    pub fn new(shape: ButtonShape) -> Self {
+        Self {
+            shape,
+            render_fn: Box::new(render_default),
+            pressed: false,
+            just_pressed: false,
+            just_released: false,
+            source: InputSource::None,
+            touch_id: 0,
+            released_touch: None,
+            mouse_was_down: false,
+            hold_time: 0.,
+            enabled: true,
+            visible: true,
+            on_press: None,
+            on_release: None,
+        }
+    }
+
+    /// create a new circular button
+    ///
+    /// # Arguments
+    /// * `x`, `y`: center of the button
+    /// * `radius`: radius of the button
+    pub fn circle(x: f32, y: f32, radius: f32) -> Self {
+        Self::new(ButtonShape::Circle {
+            center: Vec2::new(x, y),
+            radius,
+        })
+    }
+
+    /// create a new rectangular button
+    pub fn rect(rect: Rect) -> Self {
+        Self::new(ButtonShape::Rect(rect))
+    }
+
+    /// set a custom drawing function
+    ///
+    /// the function gets the shape of the button and whether it is pressed
+    pub fn set_render(&mut self, render: ButtonRenderer) {
+        self.render_fn = render;
+    }
+
+    /// the hit area of the button
+    pub fn shape(&self) -> ButtonShape {
+        self.shape
+    }
+
+    /// set the hit area of the button
+    pub fn set_shape(&mut self, shape: ButtonShape) {
+        self.shape = shape;
+    }
+
+    /// enable or disable the input of the button, like [`Joystick::set_enabled`](crate::Joystick::set_enabled)
+    ///
+    /// Disabling the button releases it without reporting the release. A touch or a click
+    /// which is still held when it's enabled again doesn't press it.
+    ///
+    /// Defaults to `true`.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.cancel();
+        }
+        self.enabled = enabled;
+    }
+
+    /// whether the input of the button is enabled, see [`TouchButton::set_enabled`]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// show or hide the button
+    ///
+    /// a hidden button still accepts input
+    ///
+    /// Defaults to `true`.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// whether the button is rendered, see [`TouchButton::set_visible`]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// call the callback at the end of the update in which the button was pressed
+    ///
+    /// The callback replaces the previous one. A tap which is pressed and released in the
+    /// same update calls the callbacks of the press and the release.
+    pub fn on_press(&mut self, callback: ButtonCallback) {
+        self.on_press = Some(callback);
+    }
+
+    /// call the callback at the end of the update in which the button was released
+    ///
+    /// the callback gets the duration of the press, e.g. to charge a jump
+    pub fn on_release(&mut self, callback: ButtonCallback) {
+        self.on_release = Some(callback);
+    }
+
+    /// whether the button is pressed
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// whether the button was pressed in the last update
+    pub fn is_just_pressed(&self) -> bool {
+        self.just_pressed
+    }
+
+    /// whether the button was released in the last update
+    pub fn is_just_released(&self) -> bool {
+        self.just_released
+    }
+
+    /// whether the button is pressed since an earlier update
+    pub fn is_held(&self) -> bool {
+        self.pressed && !self.just_pressed
+    }
+
+    /// the duration (in seconds) of the current press
+    ///
+    /// After the release, this is the duration of the last press until the next press starts.
+    pub fn hold_time(&self) -> f32 {
+        self.hold_time
+    }
+
+    /// update the button with the input of macroquad
+    ///
+    /// this should be called once per frame
+    pub fn update(&mut self) {
+        self.update_with_input(&MacroquadInput, get_frame_time());
+    }
+
+    /// update the button with the input of macroquad and the given frame time
+    ///
+    /// see [`Joystick::update_with_dt`](crate::Joystick::update_with_dt)
+    pub fn update_with_dt(&mut self, dt: f32) {
+        self.update_with_input(&MacroquadInput, dt);
+    }
+
+    /// update the button from the input
+    pub fn update_with_input(&mut self, input: &impl JoystickInput, dt: f32) {
+        self.just_pressed = false;
+        self.just_released = false;
+        self.released_touch = None;
+
+        let mut touches = input.touches();
+        let mouse_down = input.is_mouse_down();
+        if !self.enabled {
+            self.mouse_was_down = mouse_down;
+            return;
+        }
+        if touches.is_empty() {
+            self.update_mouse(input.mouse_position(), mouse_down);
+        } else {
+            order_touch_phases(&mut touches);
+            self.update_touch(&touches);
+        }
+        self.mouse_was_down = mouse_down;
+        if self.pressed {
+            self.hold_time += dt;
+        }
+        if self.just_pressed {
+            if let Some(callback) = &mut self.on_press {
+                callback(0.);
+            }
+        }
+        if self.just_released {
+            if let Some(callback) = &mut self.on_release {
+                callback(self.hold_time);
+            }
+        }
+    }
+
+    /// render the button
+    pub fn render(&self) {
+        if !self.visible {
+            return;
+        }
+        (self.render_fn)(&self.shape, self.pressed);
+    }
+
+    fn update_touch(&mut self, touches: &[Touch]) {
+        for touch in touches {
+            match touch.phase {
+                TouchPhase::Started if !self.pressed && self.shape.contains(touch.position) => {
+                    self.touch_id = touch.id;
+                    self.press(InputSource::Touch);
+                }
+                TouchPhase::Ended | TouchPhase::Cancelled if self.owns(touch) => {
+                    self.release();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn update_mouse(&mut self, position: Vec2, mouse_down: bool) {
+        if self.pressed {
+            if !mouse_down {
+                self.release();
+            }
+        } else if mouse_down && !self.mouse_was_down && self.shape.contains(position) {
+            self.press(InputSource::Mouse);
+        }
+    }
+
+    /// whether the touch presses the button
+    fn owns(&self, touch: &Touch) -> bool {
+        self.pressed && self.source == InputSource::Touch && touch.id == self.touch_id
+    }
+
+    /// the id of the touch which presses the button
+    pub(crate) fn owned_touch(&self) -> Option<u64> {
+        if self.pressed && self.source == InputSource::Touch {
+            Some(self.touch_id)
+        } else {
+            None
+        }
+    }
+
+    /// whether the touch presses the button or released it in the last update
+    pub(crate) fn uses_touch(&self, id: u64) -> bool {
+        self.owned_touch() == Some(id) || self.released_touch == Some(id)
+    }
+
+    /// whether the mouse presses the button
+    pub(crate) fn owns_mouse(&self) -> bool {
+        self.pressed && self.source == InputSource::Mouse
+    }
+
+    fn press(&mut self, source: InputSource) {
+        self.pressed = true;
+        self.just_pressed = true;
+        self.source = source;
+        self.hold_time = 0.;
+    }
+
+    fn release(&mut self) {
+        self.released_touch = self.owned_touch();
+        self.pressed = false;
+        self.just_released = true;
+        self.source = InputSource::None;
+    }
+
+    /// release the button without reporting the release
+    pub(crate) fn cancel(&mut self) {
+        self.pressed = false;
+        self.just_pressed = false;
+        self.just_released = false;
+        self.released_touch = None;
+        self.source = InputSource::None;
+    }
+}
+
+/// draw the shape in the colors of the joystick
+fn render_default(shape: &ButtonShape, pressed: bool) {
+    let color = if pressed {
+        KNOB_COLOR
+    } else {
+        BACKGROUND_COLOR
+    };
+    match *shape {
+        ButtonShape::Circle { center, radius } => draw_circle(center.x, center.y, radius, color),
+        ButtonShape::Rect(rect) => draw_rectangle(rect.x, rect.y, rect.w, rect.h, color),
+    }
+}
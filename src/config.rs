@@ -0,0 +1,289 @@
+//! serializable layout of the [`Joystick`](crate::Joystick)
+//!
+//! With the `serde` feature, a [`JoystickConfig`] can be loaded from a settings file
+//! (e.g. RON or JSON) and a layout which was customized by the player can be saved again.
+
+#[cfg(feature = "serde")]
+use macroquad::prelude::Rect;
+use macroquad::prelude::{load_texture, Color, FileError};
+
+use crate::{
+    Joystick, JoystickAxis, JoystickBuilder, JoystickConfigError, JoystickMode, JoystickStyle,
+};
+
+/// serde definition of [`Rect`], see [`JoystickMode::Floating`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "Rect")]
+pub(crate) struct RectDef {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+/// serde definition of [`Color`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "Color")]
+pub(crate) struct ColorDef {
+    r: f32,
+    g: f32,
+    b: f32,
+    a: f32,
+}
+
+/// the layout and look of a [`Joystick`]
+///
+/// see [`Joystick::from_config`] and [`Joystick::to_config`]
+///
+/// With the `serde` feature, the config can be serialized. All fields except the position and
+/// the size are optional in the serialized form.
+///
+/// # Examples
+/// ```
+/// use macroquad_virtual_joystick::{Joystick, JoystickAxis, JoystickConfig};
+///
+/// let mut config = JoystickConfig::new(100.0, 200.0, 50.0);
+/// config.axis = JoystickAxis::Horizontal;
+/// config.dead_zone = 0.1;
+///
+/// let joystick = Joystick::from_config(&config).unwrap();
+/// assert_eq!(joystick.to_config(), config);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JoystickConfig {
+    /// the x coordinate of the center
+    pub x: f32,
+    /// the y coordinate of the center
+    pub y: f32,
+    /// the diameter of the joystick
+    pub size: f32,
+    /// the diameter of the knob, defaults to half of the size
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub knob_size: Option<f32>,
+    /// see [`Joystick::set_mode`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mode: JoystickMode,
+    /// see [`Joystick::set_dead_zone`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dead_zone: f32,
+    /// see [`Joystick::set_saturation`]
+    #[cfg_attr(feature = "serde", serde(default = "default_saturation"))]
+    pub saturation: f32,
+    /// see [`Joystick::set_axis`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub axis: JoystickAxis,
+    /// the look of the joystick, `None` for the default look
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub style: Option<JoystickStyleConfig>,
+}
+
+#[cfg(feature = "serde")]
+fn default_saturation() -> f32 {
+    1.
+}
+
+/// the serializable part of a [`JoystickStyle`]
+///
+/// the textures are referenced by their path, see [`JoystickConfig::load_style`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JoystickStyleConfig {
+    /// see [`JoystickStyle::background_color`]
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub background_color: Color,
+    /// see [`JoystickStyle::knob_color`]
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub knob_color: Color,
+    /// the path of the texture of the background
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub background_texture: Option<String>,
+    /// the path of the texture of the knob
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub knob_texture: Option<String>,
+    /// see [`JoystickStyle::idle_alpha`]
+    pub idle_alpha: f32,
+    /// see [`JoystickStyle::active_alpha`]
+    pub active_alpha: f32,
+    /// see [`JoystickStyle::border_color`]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "ColorDef", default = "default_border_color")
+    )]
+    pub border_color: Color,
+    /// see [`JoystickStyle::border_width`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub border_width: f32,
+}
+
+#[cfg(feature = "serde")]
+fn default_border_color() -> Color {
+    JoystickStyle::default().border_color
+}
+
+impl Default for JoystickStyleConfig {
+    /// the colors of [`JoystickStyle::default`] without textures
+    fn default() -> Self {
+        let style = JoystickStyle::default();
+        Self {
+            background_color: style.background_color,
+            knob_color: style.knob_color,
+            background_texture: None,
+            knob_texture: None,
+            idle_alpha: style.idle_alpha,
+            active_alpha: style.active_alpha,
+            border_color: style.border_color,
+            border_width: style.border_width,
+        }
+    }
+}
+
+impl JoystickConfig {
+    /// create a new config with the defaults of [`Joystick::new`]
+    pub fn new(x: f32, y: f32, size: f32) -> Self {
+        Self {
+            x,
+            y,
+            size,
+            knob_size: None,
+            mode: JoystickMode::default(),
+            dead_zone: 0.,
+            saturation: 1.,
+            axis: JoystickAxis::default(),
+            style: None,
+        }
+    }
+
+    /// the style of the config without its textures
+    ///
+    /// this is used by [`Joystick::from_config`], load the textures with
+    /// [`JoystickConfig::load_style`]
+    pub fn style(&self) -> Option<JoystickStyle> {
+        let style = self.style.as_ref()?;
+        let knob_size = self.knob_size.unwrap_or(self.size / 2.);
+        Some(JoystickStyle {
+            background_color: style.background_color,
+            knob_color: style.knob_color,
+            knob_scale: knob_size / self.size,
+            idle_alpha: style.idle_alpha,
+            active_alpha: style.active_alpha,
+            border_color: style.border_color,
+            border_width: style.border_width,
+            ..JoystickStyle::default()
+        })
+    }
+
+    /// the style of the config with its textures
+    ///
+    /// # Errors
+    /// if a texture can't be loaded
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use macroquad_virtual_joystick::{Joystick, JoystickConfig};
+    ///
+    /// # async fn run(config: JoystickConfig) {
+    /// let mut joystick = Joystick::from_config(&config).unwrap();
+    /// joystick.set_style(config.load_style().await.unwrap());
+    /// # }
+    /// ```
+    pub async fn load_style(&self) -> Result<Option<JoystickStyle>, FileError> {
+        let config = match &self.style {
+            Some(config) => config,
+            None => return Ok(None),
+        };
+        let mut style = self.style().unwrap_or_default();
+        if let Some(path) = &config.background_texture {
+            style.background_texture = Some(load_texture(path).await?);
+        }
+        if let Some(path) = &config.knob_texture {
+            style.knob_texture = Some(load_texture(path).await?);
+        }
+        Ok(Some(style))
+    }
+}
+
+impl Joystick {
+    /// create a new [`Joystick`] from a config
+    ///
+    /// The textures of the style are not loaded, see [`JoystickConfig::load_style`].
+    ///
+    /// # Errors
+    /// if the options conflict, see [`JoystickBuilder::build`]
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::{Joystick, JoystickConfig};
+    ///
+    /// let mut config = JoystickConfig::new(100.0, 200.0, 50.0);
+    /// config.knob_size = Some(20.0);
+    /// let joystick = Joystick::from_config(&config).unwrap();
+    /// assert_eq!(joystick.size(), 50.0);
+    ///
+    /// config.knob_size = Some(60.0);
+    /// assert!(Joystick::from_config(&config).is_err());
+    /// ```
+    pub fn from_config(config: &JoystickConfig) -> Result<Self, JoystickConfigError> {
+        let mut builder = JoystickBuilder::new(config.x, config.y, config.size)
+            .dead_zone(config.dead_zone)
+            .saturation(config.saturation)
+            .mode(config.mode)
+            .axis(config.axis);
+        if let Some(knob_size) = config.knob_size {
+            builder = builder.knob_size(knob_size);
+        }
+        if let Some(style) = config.style() {
+            builder = builder.style(style);
+        }
+        let mut joystick = builder.build()?;
+        if let Some(style) = &config.style {
+            joystick
+                .set_texture_paths(style.background_texture.clone(), style.knob_texture.clone());
+        }
+        Ok(joystick)
+    }
+
+    /// set the paths of the textures which [`Joystick::to_config`] saves in the style
+    ///
+    /// [`Joystick::from_config`] keeps the paths of the config, so a layout round-trips.
+    ///
+    /// Defaults to `None` for both textures.
+    pub fn set_texture_paths(&mut self, background: Option<String>, knob: Option<String>) {
+        self.texture_paths = (background, knob);
+    }
+
+    /// the current layout and look of the joystick, e.g. to save a customized layout
+    ///
+    /// The position is the home of the joystick (see [`Joystick::set_position`]).
+    /// The paths of the textures are the ones of [`Joystick::set_texture_paths`].
+    pub fn to_config(&self) -> JoystickConfig {
+        let size = self.size;
+        let knob_size = self.knob.radius * 2.;
+        JoystickConfig {
+            x: self.static_center.x,
+            y: self.static_center.y,
+            size,
+            knob_size: if knob_size == size / 2. {
+                None
+            } else {
+                Some(knob_size)
+            },
+            mode: self.mode,
+            dead_zone: self.dead_zone,
+            saturation: self.saturation,
+            axis: self.axis,
+            style: self.style.map(|style| JoystickStyleConfig {
+                background_color: style.background_color,
+                knob_color: style.knob_color,
+                background_texture: self.texture_paths.0.clone(),
+                knob_texture: self.texture_paths.1.clone(),
+                idle_alpha: style.idle_alpha,
+                active_alpha: style.active_alpha,
+                border_color: style.border_color,
+                border_width: style.border_width,
+            }),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! quantization of the angle of the [`Joystick`](crate::Joystick) into directions
+
+use std::f32::consts::PI;
+
+use crate::JoystickDirection;
+
+/// the eight directions in the order of increasing angle, starting at the positive x-axis
+const DIRECTIONS: [JoystickDirection; 8] = [
+    JoystickDirection::Right,
+    JoystickDirection::DownRight,
+    JoystickDirection::Down,
+    JoystickDirection::DownLeft,
+    JoystickDirection::Left,
+    JoystickDirection::UpLeft,
+    JoystickDirection::Up,
+    JoystickDirection::UpRight,
+];
+
+/// quantization of an angle into a number of equally sized sectors
+///
+/// Sector `0` is centered on the offset and the sectors are numbered with increasing angle,
+/// i.e. clockwise on the screen like [`JoystickEvent::angle`](crate::JoystickEvent::angle).
+/// An angle on the boundary between two sectors belongs to the sector with the lower angle.
+///
+/// The [`JoystickDirection`] of an angle is the closest of the eight directions to the center
+/// of its sector. With more than eight sectors the directions are coarser than the sectors,
+/// so the direction is the closest one to the angle itself and the sector has to be used
+/// for the finer resolution (see [`JoystickEvent::sector`](crate::JoystickEvent::sector)).
+///
+/// Defaults to [`DirectionResolver::eight_way`], which agrees with
+/// [`JoystickDirection::from_radians`].
+///
+/// # Examples
+/// ```
+/// use std::f32::consts::FRAC_PI_4;
+/// use macroquad_virtual_joystick::{DirectionResolver, JoystickDirection};
+///
+/// let resolver = DirectionResolver::four_way();
+/// // slightly below the diagonal between right and down
+/// let angle = FRAC_PI_4 * 0.9;
+/// assert_eq!(resolver.direction(angle), JoystickDirection::Right);
+/// assert_eq!(resolver.snapped_angle(angle), 0.0);
+///
+/// // turn the sectors so the diagonals are centered
+/// let resolver = DirectionResolver::four_way().with_offset(FRAC_PI_4);
+/// assert_eq!(resolver.direction(angle), JoystickDirection::DownRight);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectionResolver {
+    sectors: u16,
+    offset: f32,
+}
+
+impl DirectionResolver {
+    /// create a resolver with `sectors` sectors and no offset
+    ///
+    /// # Panics
+    /// if `sectors` is zero
+    pub fn new(sectors: u16) -> Self {
+        assert!(sectors > 0, "the resolver needs at least one sector");
+        Self {
+            sectors,
+            offset: 0.,
+        }
+    }
+
+    /// the four directions right, down, left and up
+    pub fn four_way() -> Self {
+        Self::new(4)
+    }
+
+    /// all eight directions
+    pub fn eight_way() -> Self {
+        Self::new(8)
+    }
+
+    /// sixteen sectors, e.g. for aiming
+    pub fn sixteen_way() -> Self {
+        Self::new(16)
+    }
+
+    /// rotate the sectors by `offset` radians
+    ///
+    /// a positive offset rotates clockwise on the screen
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// the number of sectors
+    pub fn sectors(&self) -> u16 {
+        self.sectors
+    }
+
+    /// the rotation of the sectors in radians
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    /// the angle which is covered by one sector in radians
+    pub fn sector_angle(&self) -> f32 {
+        2. * PI / self.sectors as f32
+    }
+
+    /// the sector of the angle (in radians)
+    pub fn sector(&self, angle: f32) -> u16 {
+        let position = (angle - self.offset) / self.sector_angle();
+        // a boundary belongs to the lower sector, like in `JoystickDirection::from_degrees`
+        let sector = (position - 0.5).ceil();
+        if !sector.is_finite() {
+            return 0;
+        }
+        (sector as i64).rem_euclid(self.sectors as i64) as u16
+    }
+
+    /// the angle at the center of the sector in `-π..=π`
+    pub fn sector_center(&self, sector: u16) -> f32 {
+        let angle = self.offset + sector as f32 * self.sector_angle();
+        let angle = (angle + PI).rem_euclid(2. * PI) - PI;
+        if angle <= -PI {
+            angle + 2. * PI
+        } else {
+            angle
+        }
+    }
+
+    /// the angle at the center of the sector of the angle
+    pub fn snapped_angle(&self, angle: f32) -> f32 {
+        self.sector_center(self.sector(angle))
+    }
+
+    /// the direction of the angle in degrees, like [`JoystickDirection::from_degrees`]
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::{DirectionResolver, JoystickDirection};
+    ///
+    /// let resolver = DirectionResolver::four_way();
+    /// assert_eq!(resolver.direction_from_degrees(40.0), JoystickDirection::Right);
+    /// assert_eq!(resolver.direction_from_degrees(50.0), JoystickDirection::Down);
+    /// assert_eq!(JoystickDirection::from_degrees(40.0), JoystickDirection::DownRight);
+    /// ```
+    pub fn direction_from_degrees(&self, degrees: f64) -> JoystickDirection {
+        self.direction(degrees.to_radians() as f32)
+    }
+
+    /// the direction of the angle, see [`DirectionResolver`]
+    pub fn direction(&self, angle: f32) -> JoystickDirection {
+        if !angle.is_finite() {
+            return JoystickDirection::Idle;
+        }
+        let angle = if self.sectors > 8 {
+            angle
+        } else {
+            self.snapped_angle(angle)
+        };
+        let index = (angle / (PI / 4.)).round() as i64;
+        DIRECTIONS[index.rem_euclid(8) as usize]
+    }
+}
+
+impl Default for DirectionResolver {
+    fn default() -> Self {
+        Self::eight_way()
+    }
+}
@@ -0,0 +1,336 @@
+//! a directional pad which reports digital directions
+
+use macroquad::prelude::{
+    draw_circle, draw_texture_ex, Color, DrawTextureParams, Mat3, Texture2D, Touch, TouchPhase,
+    Vec2, WHITE,
+};
+
+use crate::{
+    order_touch_phases, sanitize_size, DirectionResolver, InputSource, JoystickDirection,
+    JoystickElement, JoystickInput, JoystickRenderState, MacroquadInput, BACKGROUND_COLOR,
+    KNOB_COLOR,
+};
+
+/// the tint of the texture of a direction which is not pressed
+const RELEASED_TINT: Color = Color::new(1., 1., 1., 0.5);
+
+/// the directions of a [`DPad`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DPadMode {
+    /// only up, down, left and right
+    FourWay,
+    /// the four directions and the diagonals
+    EightWay,
+}
+
+/// a directional pad which reports a [`JoystickDirection`] without intensity
+///
+/// A touch or the mouse which is pressed inside of the pad selects the zone under it until it
+/// is released. The zones are sectors around the center, so a drag can slide from one zone to
+/// the next. Inside of the dead zone around the center, the pad reports
+/// [`JoystickDirection::Idle`].
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::{TouchPhase, Vec2};
+/// use macroquad_virtual_joystick::{DPad, DPadMode, JoystickDirection, SimulatedInput};
+///
+/// let mut dpad = DPad::new(100.0, 200.0, 80.0, DPadMode::FourWay);
+///
+/// // slightly right of up is still up
+/// let position = Vec2::new(110.0, 170.0);
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, position);
+/// assert_eq!(dpad.update_with_input(&input), JoystickDirection::Up);
+/// assert!(dpad.is_pressed(JoystickDirection::Up));
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Ended, position);
+/// assert_eq!(dpad.update_with_input(&input), JoystickDirection::Idle);
+/// ```
+pub struct DPad {
+    center: Vec2,
+    radius: f32,
+    mode: DPadMode,
+    dead_zone: f32,
+    background: JoystickElement,
+    textures: [Option<Texture2D>; 8],
+    direction: JoystickDirection,
+    /// the direction before the last update
+    previous_direction: JoystickDirection,
+    pressed: bool,
+    source: InputSource,
+    touch_id: u64,
+}
+
+impl DPad {
+    /// create a new directional pad
+    ///
+    /// # Arguments
+    /// * `x`, `y`: center of the pad
+    /// * `size`: diameter of the pad, sizes below one pixel are clamped to one pixel
+    /// * `mode`: whether the diagonals are reported
+    pub fn new(x: f32, y: f32, size: f32, mode: DPadMode) -> Self {
+        let radius = sanitize_size(size) / 2.;
+        let background_fn = Box::new(|center_x: f32, center_y: f32, radius: f32| {
+            draw_circle(center_x, center_y, radius, BACKGROUND_COLOR);
+        });
+        Self {
+            center: Vec2::new(x, y),
+            radius,
+            mode,
+            dead_zone: 0.2,
+            background: JoystickElement::new(x, y, radius, background_fn).cacheable(),
+            textures: [None; 8],
+            direction: JoystickDirection::Idle,
+            previous_direction: JoystickDirection::Idle,
+            pressed: false,
+            source: InputSource::None,
+            touch_id: 0,
+        }
+    }
+
+    /// set a custom drawing function for the background
+    ///
+    /// the function gets the center and the radius of the pad, like the elements of the
+    /// [`Joystick`](crate::Joystick)
+    pub fn set_background(&mut self, render_background: Box<dyn Fn(f32, f32, f32)>) {
+        self.background =
+            JoystickElement::new(self.center.x, self.center.y, self.radius, render_background);
+    }
+
+    /// set the texture which is drawn in the zone of the direction
+    ///
+    /// The texture is drawn in full color while the direction is pressed and translucent
+    /// otherwise. Directions without a texture are drawn as circles.
+    /// The texture of [`JoystickDirection::Idle`] is ignored.
+    pub fn set_direction_texture(
+        &mut self,
+        direction: JoystickDirection,
+        texture: Option<Texture2D>,
+    ) {
+        if let Some(index) = zone_index(direction) {
+            self.textures[index] = texture;
+        }
+    }
+
+    /// set the radius of the dead zone relative to the radius of the pad
+    ///
+    /// Defaults to `0.2`.
+    pub fn set_dead_zone(&mut self, dead_zone: f32) {
+        self.dead_zone = dead_zone;
+    }
+
+    /// set whether the diagonals are reported
+    pub fn set_mode(&mut self, mode: DPadMode) {
+        self.mode = mode;
+    }
+
+    /// the center of the pad
+    pub fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    /// the direction of the last update
+    pub fn direction(&self) -> JoystickDirection {
+        self.direction
+    }
+
+    /// whether the direction is pressed
+    ///
+    /// A diagonal also presses both of its directions, e.g. [`JoystickDirection::UpRight`]
+    /// presses [`JoystickDirection::Up`] and [`JoystickDirection::Right`].
+    /// [`JoystickDirection::Idle`] is pressed while no direction is pressed.
+    pub fn is_pressed(&self, direction: JoystickDirection) -> bool {
+        presses(self.direction, direction)
+    }
+
+    /// whether the direction was pressed in the last update, see [`DPad::is_pressed`]
+    ///
+    /// e.g. sliding from [`JoystickDirection::Up`] to [`JoystickDirection::UpRight`] only
+    /// presses [`JoystickDirection::Right`] and [`JoystickDirection::UpRight`]
+    pub fn is_just_pressed(&self, direction: JoystickDirection) -> bool {
+        presses(self.direction, direction) && !presses(self.previous_direction, direction)
+    }
+
+    /// whether the direction was released in the last update, see [`DPad::is_pressed`]
+    pub fn is_just_released(&self, direction: JoystickDirection) -> bool {
+        !presses(self.direction, direction) && presses(self.previous_direction, direction)
+    }
+
+    /// update the pad with the input of macroquad
+    ///
+    /// this should be called once per frame
+    pub fn update(&mut self) -> JoystickDirection {
+        self.update_with_input(&MacroquadInput)
+    }
+
+    /// update the pad from the input
+    pub fn update_with_input(&mut self, input: &impl JoystickInput) -> JoystickDirection {
+        self.previous_direction = self.direction;
+        let mut touches = input.touches();
+        if touches.is_empty() {
+            self.update_mouse(input.mouse_position(), input.is_mouse_down());
+        } else {
+            order_touch_phases(&mut touches);
+            self.update_touch(&touches);
+        }
+        self.direction
+    }
+
+    /// render the pad
+    pub fn render(&self) {
+        let state = JoystickRenderState {
+            active: self.pressed,
+            intensity: if self.direction == JoystickDirection::Idle {
+                0.
+            } else {
+                1.
+            },
+            angle: direction_angle(self.direction),
+            direction: self.direction,
+            opacity: 1.,
+        };
+        self.background.render(&Mat3::IDENTITY, state);
+        let directions: &[JoystickDirection] = match self.mode {
+            DPadMode::FourWay => &[
+                JoystickDirection::Up,
+                JoystickDirection::Left,
+                JoystickDirection::Down,
+                JoystickDirection::Right,
+            ],
+            DPadMode::EightWay => &[
+                JoystickDirection::Up,
+                JoystickDirection::UpLeft,
+                JoystickDirection::Left,
+                JoystickDirection::DownLeft,
+                JoystickDirection::Down,
+                JoystickDirection::DownRight,
+                JoystickDirection::Right,
+                JoystickDirection::UpRight,
+            ],
+        };
+        let zone_radius = match self.mode {
+            DPadMode::FourWay => self.radius * 0.25,
+            DPadMode::EightWay => self.radius * 0.18,
+        };
+        for &direction in directions {
+            let position = self.center + direction.to_local().normalize() * self.radius * 0.6;
+            let pressed = self.direction == direction;
+            let texture = zone_index(direction).and_then(|index| self.textures[index]);
+            match texture {
+                Some(texture) => draw_texture_ex(
+                    texture,
+                    position.x - zone_radius,
+                    position.y - zone_radius,
+                    if pressed { WHITE } else { RELEASED_TINT },
+                    DrawTextureParams {
+                        dest_size: Some(Vec2::splat(zone_radius * 2.)),
+                        ..Default::default()
+                    },
+                ),
+                None => {
+                    let color = if pressed {
+                        KNOB_COLOR
+                    } else {
+                        BACKGROUND_COLOR
+                    };
+                    draw_circle(position.x, position.y, zone_radius, color);
+                }
+            }
+        }
+    }
+
+    fn update_touch(&mut self, touches: &[Touch]) {
+        for touch in touches {
+            match touch.phase {
+                TouchPhase::Started if !self.pressed && self.contains(touch.position) => {
+                    self.touch_id = touch.id;
+                    self.press(InputSource::Touch, touch.position);
+                }
+                TouchPhase::Moved if self.owns(touch) => {
+                    self.direction = self.zone(touch.position);
+                }
+                TouchPhase::Ended | TouchPhase::Cancelled if self.owns(touch) => {
+                    self.release();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn update_mouse(&mut self, position: Vec2, mouse_down: bool) {
+        if self.pressed {
+            if mouse_down {
+                self.direction = self.zone(position);
+            } else {
+                self.release();
+            }
+        } else if mouse_down && self.contains(position) {
+            self.press(InputSource::Mouse, position);
+        }
+    }
+
+    /// whether the touch presses the pad
+    fn owns(&self, touch: &Touch) -> bool {
+        self.pressed && self.source == InputSource::Touch && touch.id == self.touch_id
+    }
+
+    fn contains(&self, position: Vec2) -> bool {
+        (position - self.center).length() < self.radius
+    }
+
+    fn press(&mut self, source: InputSource, position: Vec2) {
+        self.pressed = true;
+        self.source = source;
+        self.direction = self.zone(position);
+    }
+
+    fn release(&mut self) {
+        self.pressed = false;
+        self.source = InputSource::None;
+        self.direction = JoystickDirection::Idle;
+    }
+
+    /// the direction of the zone at the position
+    fn zone(&self, position: Vec2) -> JoystickDirection {
+        let delta = position - self.center;
+        if !delta.is_finite() || delta.length() <= self.radius * self.dead_zone {
+            return JoystickDirection::Idle;
+        }
+        let resolver = match self.mode {
+            DPadMode::FourWay => DirectionResolver::four_way(),
+            DPadMode::EightWay => DirectionResolver::eight_way(),
+        };
+        resolver.direction(delta.y.atan2(delta.x))
+    }
+}
+
+/// whether `current` presses `direction`, see [`DPad::is_pressed`]
+fn presses(current: JoystickDirection, direction: JoystickDirection) -> bool {
+    if direction == JoystickDirection::Idle || current == JoystickDirection::Idle {
+        return direction == current;
+    }
+    let current = current.to_local();
+    let asked = direction.to_local();
+    (asked.x == 0. || asked.x == current.x) && (asked.y == 0. || asked.y == current.y)
+}
+
+/// the angle of the direction like [`JoystickEvent::angle`](crate::JoystickEvent::angle)
+fn direction_angle(direction: JoystickDirection) -> f32 {
+    let local = direction.to_local();
+    local.y.atan2(local.x)
+}
+
+/// the index of the direction in the textures
+fn zone_index(direction: JoystickDirection) -> Option<usize> {
+    match direction {
+        JoystickDirection::Up => Some(0),
+        JoystickDirection::UpLeft => Some(1),
+        JoystickDirection::Left => Some(2),
+        JoystickDirection::DownLeft => Some(3),
+        JoystickDirection::Down => Some(4),
+        JoystickDirection::DownRight => Some(5),
+        JoystickDirection::Right => Some(6),
+        JoystickDirection::UpRight => Some(7),
+        JoystickDirection::Idle => None,
+    }
+}
@@ -0,0 +1,65 @@
+//! hooks for haptic or audio feedback of the [`Joystick`](crate::Joystick)
+
+use crate::{JoystickDirection, JoystickEvent};
+
+/// receiver of the notable moments of a drag, e.g. to vibrate or to play a sound
+///
+/// The methods are called by the update of the joystick after the event was computed, see
+/// [`Joystick::set_feedback`](crate::Joystick::set_feedback). All methods do nothing by
+/// default. In one update they are called in the order of the declaration.
+///
+/// # Examples
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use macroquad::prelude::Vec2;
+/// use macroquad_virtual_joystick::{Joystick, JoystickEvent, JoystickFeedback, SimulatedInput};
+///
+/// struct Rumble(Rc<RefCell<Vec<&'static str>>>);
+///
+/// impl JoystickFeedback for Rumble {
+///     fn on_start(&mut self, _event: &JoystickEvent) {
+///         self.0.borrow_mut().push("start");
+///     }
+///
+///     fn on_edge_reach(&mut self, _event: &JoystickEvent) {
+///         self.0.borrow_mut().push("edge");
+///     }
+/// }
+///
+/// let log = Rc::new(RefCell::new(Vec::new()));
+/// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+/// joystick.set_feedback(Some(Box::new(Rumble(Rc::clone(&log)))));
+/// for x in [110.0, 130.0, 140.0] {
+///     let input = SimulatedInput::new().with_mouse(Vec2::new(x, 200.0), true);
+///     joystick.update_with_input(&input, 0.0);
+/// }
+/// assert_eq!(*log.borrow(), ["start", "edge"]);
+/// ```
+pub trait JoystickFeedback {
+    /// the joystick was grabbed, see [`JoystickPhase::Start`](crate::JoystickPhase::Start)
+    fn on_start(&mut self, _event: &JoystickEvent) {}
+
+    /// the joystick was dragged after the press, see
+    /// [`JoystickPhase::Drag`](crate::JoystickPhase::Drag)
+    ///
+    /// this is called in each update of the drag
+    fn on_drag(&mut self, _event: &JoystickEvent) {}
+
+    /// the direction changed from `from` to the direction of the event
+    ///
+    /// this includes the changes from and to [`JoystickDirection::Idle`]
+    fn on_direction_change(&mut self, _from: JoystickDirection, _event: &JoystickEvent) {}
+
+    /// the intensity reached `1.0`
+    ///
+    /// this is called again after the intensity dropped below `1.0`
+    fn on_edge_reach(&mut self, _event: &JoystickEvent) {}
+
+    /// the joystick was released, see [`JoystickPhase::Release`](crate::JoystickPhase::Release)
+    ///
+    /// a tap which is grabbed and released in the same update calls [`JoystickFeedback::on_start`]
+    /// first
+    fn on_release(&mut self, _event: &JoystickEvent) {}
+}
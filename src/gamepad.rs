@@ -0,0 +1,226 @@
+//! gamepad-style access to the [`Joystick`](crate::Joystick)
+//!
+//! [`StickLike`] abstracts over sticks with two axes and a pressed state, so the same code
+//! can handle the virtual joystick (with [`GamepadStickAdapter`]) and a physical gamepad.
+//! A physical stick can also drive the joystick with [`GamepadInput`].
+
+use macroquad::prelude::{KeyCode, Touch, Vec2};
+
+use crate::{Joystick, JoystickInput};
+
+/// a stick with two axes and a pressed state, like the left stick of a gamepad
+///
+/// Both axes are in the range `-1.0..=1.0`. x is positive to the right and y is positive
+/// downwards, like [`JoystickEvent::axes`](crate::JoystickEvent::axes).
+/// Implementations for sources with y positive upwards have to flip it.
+///
+/// # Examples
+/// ```
+/// use macroquad_virtual_joystick::StickLike;
+///
+/// /// a physical stick with y positive upwards
+/// struct PhysicalStick {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// impl StickLike for PhysicalStick {
+///     fn left_stick_x(&self) -> f32 {
+///         self.x
+///     }
+///
+///     fn left_stick_y(&self) -> f32 {
+///         -self.y
+///     }
+///
+///     fn is_pressed(&self) -> bool {
+///         self.x != 0.0 || self.y != 0.0
+///     }
+/// }
+///
+/// let stick = PhysicalStick { x: 0.0, y: 1.0 };
+/// assert_eq!(stick.left_stick_y(), -1.0);
+/// assert!(stick.is_pressed());
+/// ```
+pub trait StickLike {
+    /// the horizontal axis, positive to the right
+    fn left_stick_x(&self) -> f32;
+
+    /// the vertical axis, positive downwards
+    fn left_stick_y(&self) -> f32;
+
+    /// whether the stick is held
+    fn is_pressed(&self) -> bool;
+
+    /// both axes as a vector
+    fn left_stick(&self) -> Vec2 {
+        Vec2::new(self.left_stick_x(), self.left_stick_y())
+    }
+}
+
+/// the [`Joystick`] as a [`StickLike`]
+///
+/// The axes are the [`JoystickEvent::axes`](crate::JoystickEvent::axes) of the last update
+/// (see [`Joystick::event`]) and the stick is pressed while the joystick is dragged.
+/// Each axis can be inverted, e.g. for an abstraction with y positive upwards.
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::Vec2;
+/// use macroquad_virtual_joystick::{GamepadStickAdapter, Joystick, SimulatedInput, StickLike};
+///
+/// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+/// let input = SimulatedInput::new().with_mouse(Vec2::new(100.0, 190.0), true);
+/// joystick.update_with_input(&input, 0.0);
+///
+/// let stick = GamepadStickAdapter::new(&joystick).invert_y(true);
+/// assert!((stick.left_stick_y() - 0.4).abs() < 1e-6);
+/// assert!(stick.is_pressed());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct GamepadStickAdapter<'a> {
+    joystick: &'a Joystick,
+    invert_x: bool,
+    invert_y: bool,
+}
+
+impl<'a> GamepadStickAdapter<'a> {
+    /// create a new adapter without inverted axes
+    pub fn new(joystick: &'a Joystick) -> Self {
+        Self {
+            joystick,
+            invert_x: false,
+            invert_y: false,
+        }
+    }
+
+    /// set whether the horizontal axis is inverted
+    pub fn invert_x(mut self, invert_x: bool) -> Self {
+        self.invert_x = invert_x;
+        self
+    }
+
+    /// set whether the vertical axis is inverted
+    pub fn invert_y(mut self, invert_y: bool) -> Self {
+        self.invert_y = invert_y;
+        self
+    }
+}
+
+/// negate the value if `invert` is set
+fn invert(value: f32, invert: bool) -> f32 {
+    if invert {
+        -value
+    } else {
+        value
+    }
+}
+
+impl StickLike for GamepadStickAdapter<'_> {
+    fn left_stick_x(&self) -> f32 {
+        invert(self.joystick.event().axes().x, self.invert_x)
+    }
+
+    fn left_stick_y(&self) -> f32 {
+        invert(self.joystick.event().axes().y, self.invert_y)
+    }
+
+    fn is_pressed(&self) -> bool {
+        self.joystick.is_dragging()
+    }
+}
+
+impl<T: StickLike + ?Sized> StickLike for &T {
+    fn left_stick_x(&self) -> f32 {
+        (**self).left_stick_x()
+    }
+
+    fn left_stick_y(&self) -> f32 {
+        (**self).left_stick_y()
+    }
+
+    fn is_pressed(&self) -> bool {
+        (**self).is_pressed()
+    }
+}
+
+/// an input which adds the stick of a physical gamepad to another input
+///
+/// The [`Joystick`] is driven by the stick while no pointer drags it and no bound key is
+/// down, so the widget on the screen mirrors the gamepad and the game only reads the
+/// [`JoystickEvent`](crate::JoystickEvent). The events are reported with
+/// [`InputSource::Gamepad`](crate::InputSource::Gamepad). The dead zone, the saturation and
+/// the response curves of the joystick apply to the stick too.
+///
+/// The stick can be any [`StickLike`], e.g. a wrapper around the gamepad of `gilrs`.
+///
+/// # Examples
+/// ```
+/// use macroquad_virtual_joystick::{
+///     GamepadInput, InputSource, Joystick, JoystickDirection, SimulatedInput, StickLike,
+/// };
+///
+/// struct PhysicalStick {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// impl StickLike for PhysicalStick {
+///     fn left_stick_x(&self) -> f32 {
+///         self.x
+///     }
+///
+///     fn left_stick_y(&self) -> f32 {
+///         self.y
+///     }
+///
+///     fn is_pressed(&self) -> bool {
+///         self.x != 0.0 || self.y != 0.0
+///     }
+/// }
+///
+/// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+/// let stick = PhysicalStick { x: 0.0, y: -0.5 };
+/// let input = GamepadInput::new(SimulatedInput::new(), &stick);
+/// let event = joystick.update_with_input(&input, 1.0 / 60.0);
+/// assert_eq!(event.direction, JoystickDirection::Up);
+/// assert_eq!(event.intensity, 0.5);
+/// assert_eq!(event.source, InputSource::Gamepad);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct GamepadInput<I, S> {
+    input: I,
+    stick: S,
+}
+
+impl<I: JoystickInput, S: StickLike> GamepadInput<I, S> {
+    /// combine the input with the stick
+    ///
+    /// use [`MacroquadInput`](crate::MacroquadInput) for the touches, the mouse and the keys
+    /// of macroquad
+    pub fn new(input: I, stick: S) -> Self {
+        Self { input, stick }
+    }
+}
+
+impl<I: JoystickInput, S: StickLike> JoystickInput for GamepadInput<I, S> {
+    fn touches(&self) -> Vec<Touch> {
+        self.input.touches()
+    }
+
+    fn mouse_position(&self) -> Vec2 {
+        self.input.mouse_position()
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        self.input.is_mouse_down()
+    }
+
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        self.input.is_key_down(key)
+    }
+
+    fn gamepad_stick(&self) -> Vec2 {
+        self.stick.left_stick()
+    }
+}
@@ -0,0 +1,220 @@
+//! scripted input for tests of the [`Joystick`](crate::Joystick)
+//!
+//! only available with the `test-utils` feature
+
+use macroquad::prelude::{KeyCode, Touch, TouchPhase, Vec2};
+
+use crate::{Joystick, JoystickEvent, SimulatedInput};
+
+/// the touch id used by [`JoystickHarness::press`], [`JoystickHarness::drag_to`] and
+/// [`JoystickHarness::release`]
+const DEFAULT_TOUCH_ID: u64 = 0;
+
+/// test harness which drives a [`Joystick`] with scripted input
+///
+/// The harness keeps track of the touches like macroquad does: touches which are held
+/// are reported as [`TouchPhase::Stationary`] and ended touches are removed after one frame.
+/// Changes to the touches and the mouse are staged and applied in the next frame,
+/// which is run by [`JoystickHarness::advance`].
+/// The convenience methods like [`JoystickHarness::press`] stage a change and run a frame
+/// with the frame time of the harness.
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::Vec2;
+/// use macroquad_virtual_joystick::{Joystick, JoystickDirection, JoystickHarness};
+///
+/// let mut harness = JoystickHarness::new(Joystick::new(100.0, 200.0, 50.0));
+///
+/// let event = harness.press(Vec2::new(100.0, 195.0));
+/// assert_eq!(event.direction, JoystickDirection::Up);
+/// let events = harness.drag_path(Vec2::new(100.0, 195.0), Vec2::new(110.0, 200.0), 4);
+/// assert_eq!(events.last().unwrap().direction, JoystickDirection::Right);
+/// let event = harness.release();
+/// assert_eq!(event.direction, JoystickDirection::Idle);
+/// ```
+pub struct JoystickHarness {
+    joystick: Joystick,
+    frame_time: f32,
+    touches: Vec<Touch>,
+    mouse_position: Vec2,
+    mouse_down: bool,
+    keys: Vec<KeyCode>,
+    gamepad_stick: Vec2,
+}
+
+impl JoystickHarness {
+    /// create a new harness for the joystick with a frame time of 1/60 seconds
+    pub fn new(joystick: Joystick) -> Self {
+        Self {
+            joystick,
+            frame_time: 1. / 60.,
+            touches: Vec::new(),
+            mouse_position: Vec2::ZERO,
+            mouse_down: false,
+            keys: Vec::new(),
+            gamepad_stick: Vec2::ZERO,
+        }
+    }
+
+    /// the driven joystick
+    pub fn joystick(&self) -> &Joystick {
+        &self.joystick
+    }
+
+    /// the driven joystick
+    pub fn joystick_mut(&mut self) -> &mut Joystick {
+        &mut self.joystick
+    }
+
+    /// set the frame time which is used by the convenience methods
+    pub fn set_frame_time(&mut self, frame_time: f32) {
+        self.frame_time = frame_time;
+    }
+
+    /// press the default touch at `position` and run a frame
+    pub fn press(&mut self, position: Vec2) -> JoystickEvent {
+        self.touch_start(DEFAULT_TOUCH_ID, position);
+        self.advance(self.frame_time)
+    }
+
+    /// move the default touch to `position` and run a frame
+    pub fn drag_to(&mut self, position: Vec2) -> JoystickEvent {
+        self.touch_move(DEFAULT_TOUCH_ID, position);
+        self.advance(self.frame_time)
+    }
+
+    /// drag the default touch from `from` to `to` in `steps` frames
+    ///
+    /// the touch is pressed at `from` first if it isn't pressed yet.
+    /// returns the events of all frames
+    pub fn drag_path(&mut self, from: Vec2, to: Vec2, steps: usize) -> Vec<JoystickEvent> {
+        let mut events = Vec::with_capacity(steps + 1);
+        if !self
+            .touches
+            .iter()
+            .any(|touch| touch.id == DEFAULT_TOUCH_ID)
+        {
+            events.push(self.press(from));
+        }
+        for step in 1..=steps {
+            let position = from.lerp(to, step as f32 / steps as f32);
+            events.push(self.drag_to(position));
+        }
+        events
+    }
+
+    /// release the default touch and run a frame
+    pub fn release(&mut self) -> JoystickEvent {
+        self.touch_end(DEFAULT_TOUCH_ID);
+        self.advance(self.frame_time)
+    }
+
+    /// cancel the default touch and run a frame
+    pub fn cancel(&mut self) -> JoystickEvent {
+        self.touch_cancel(DEFAULT_TOUCH_ID);
+        self.advance(self.frame_time)
+    }
+
+    /// stage the start of a touch
+    pub fn touch_start(&mut self, id: u64, position: Vec2) -> &mut Self {
+        self.touches.retain(|touch| touch.id != id);
+        self.touches.push(Touch {
+            id,
+            phase: TouchPhase::Started,
+            position,
+        });
+        self
+    }
+
+    /// stage the movement of a touch
+    ///
+    /// # Panics
+    /// if the touch isn't pressed
+    pub fn touch_move(&mut self, id: u64, position: Vec2) -> &mut Self {
+        let touch = self.touch_mut(id);
+        touch.position = position;
+        if touch.phase != TouchPhase::Started {
+            touch.phase = TouchPhase::Moved;
+        }
+        self
+    }
+
+    /// stage the end of a touch
+    ///
+    /// # Panics
+    /// if the touch isn't pressed
+    pub fn touch_end(&mut self, id: u64) -> &mut Self {
+        self.touch_mut(id).phase = TouchPhase::Ended;
+        self
+    }
+
+    /// stage the cancellation of a touch
+    ///
+    /// # Panics
+    /// if the touch isn't pressed
+    pub fn touch_cancel(&mut self, id: u64) -> &mut Self {
+        self.touch_mut(id).phase = TouchPhase::Cancelled;
+        self
+    }
+
+    /// stage the position of the mouse and the state of the left mouse button
+    pub fn mouse(&mut self, position: Vec2, down: bool) -> &mut Self {
+        self.mouse_position = position;
+        self.mouse_down = down;
+        self
+    }
+
+    /// stage whether the key is down
+    pub fn key(&mut self, key: KeyCode, down: bool) -> &mut Self {
+        self.keys.retain(|&other| other != key);
+        if down {
+            self.keys.push(key);
+        }
+        self
+    }
+
+    /// stage the axes of the gamepad stick
+    ///
+    /// see [`JoystickInput::gamepad_stick`](crate::JoystickInput::gamepad_stick)
+    pub fn gamepad_stick(&mut self, stick: Vec2) -> &mut Self {
+        self.gamepad_stick = stick;
+        self
+    }
+
+    /// redraw the render cache of the joystick like [`Joystick::render`] would
+    ///
+    /// This only updates [`Joystick::is_render_cache_dirty`], because drawing needs a window.
+    pub fn redraw_render_cache(&mut self) -> &mut Self {
+        self.joystick.take_render_cache_dirty();
+        self
+    }
+
+    /// run a frame with all staged changes
+    pub fn advance(&mut self, dt: f32) -> JoystickEvent {
+        let input = SimulatedInput {
+            touches: self.touches.clone(),
+            mouse_position: self.mouse_position,
+            mouse_down: self.mouse_down,
+            keys: self.keys.clone(),
+            gamepad_stick: self.gamepad_stick,
+        };
+        let event = self.joystick.update_with_input(&input, dt);
+
+        // like macroquad: remove ended touches and keep the others as stationary
+        self.touches.retain(|touch| {
+            touch.phase != TouchPhase::Ended && touch.phase != TouchPhase::Cancelled
+        });
+        for touch in &mut self.touches {
+            touch.phase = TouchPhase::Stationary;
+        }
+        event
+    }
+
+    fn touch_mut(&mut self, id: u64) -> &mut Touch {
+        self.touches
+            .iter_mut()
+            .find(|touch| touch.id == id)
+            .unwrap_or_else(|| panic!("the touch {} is not pressed", id))
+    }
+}
@@ -0,0 +1,310 @@
+//! input sources of the [`Joystick`](crate::Joystick)
+//!
+//! By default the joystick reads the input from macroquad with [`MacroquadInput`].
+//! For tests or replays the input can be simulated with [`SimulatedInput`].
+
+use macroquad::prelude::{
+    get_frame_time, get_time, is_key_down, is_mouse_button_down, mouse_position, touches, KeyCode,
+    MouseButton, Touch, TouchPhase, Vec2,
+};
+
+/// the source of the input which controls the [`Joystick`](crate::Joystick)
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::{TouchPhase, Vec2};
+/// use macroquad_virtual_joystick::{InputSource, Joystick, SimulatedInput};
+///
+/// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+/// let position = Vec2::new(110.0, 200.0);
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, position);
+/// assert_eq!(joystick.update_with_input(&input, 0.0).source, InputSource::Touch);
+/// // the source is kept in the update of the release
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Ended, position);
+/// assert_eq!(joystick.update_with_input(&input, 0.0).source, InputSource::Touch);
+/// assert_eq!(joystick.update_with_input(&SimulatedInput::new(), 0.0).source, InputSource::None);
+///
+/// // the next drag uses the mouse
+/// let input = SimulatedInput::new().with_mouse(position, true);
+/// assert_eq!(joystick.update_with_input(&input, 0.0).source, InputSource::Mouse);
+/// let input = SimulatedInput::new().with_mouse(position, false);
+/// assert_eq!(joystick.update_with_input(&input, 0.0).source, InputSource::Mouse);
+/// assert_eq!(joystick.update_with_input(&input, 0.0).source, InputSource::None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputSource {
+    /// the joystick is not controlled
+    #[default]
+    None,
+    /// the joystick is dragged by a touch
+    Touch,
+    /// the joystick is dragged by the mouse
+    Mouse,
+    /// the joystick is controlled by keys of the keyboard
+    Keyboard,
+    /// the joystick is controlled by the analog stick of a gamepad
+    Gamepad,
+    /// the joystick is controlled by the application instead of a user input
+    Virtual,
+}
+
+/// a source of pointer and keyboard input for the [`Joystick`](crate::Joystick)
+///
+/// The trait is implemented for references and boxes, so the source can be chosen at runtime,
+/// e.g. to replay recorded input instead of the input of macroquad.
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::{TouchPhase, Vec2};
+/// use macroquad_virtual_joystick::{
+///     Joystick, JoystickDirection, JoystickInput, MacroquadInput, SimulatedInput,
+/// };
+///
+/// let replay = true;
+/// let input: Box<dyn JoystickInput> = if replay {
+///     Box::new(SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(100.0, 190.0)))
+/// } else {
+///     Box::new(MacroquadInput)
+/// };
+///
+/// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+/// let event = joystick.update_with_input(&input, 1.0 / 60.0);
+/// assert_eq!(event.direction, JoystickDirection::Up);
+/// ```
+pub trait JoystickInput {
+    /// all touches of the current frame
+    fn touches(&self) -> Vec<Touch>;
+
+    /// the current position of the mouse in screen coordinates
+    fn mouse_position(&self) -> Vec2;
+
+    /// whether the left mouse button is currently down
+    fn is_mouse_down(&self) -> bool;
+
+    /// whether the key is currently down
+    ///
+    /// this is only used for bound keys, see [`Joystick::bind_keys`](crate::Joystick::bind_keys).
+    /// Defaults to no key being down.
+    fn is_key_down(&self, _key: KeyCode) -> bool {
+        false
+    }
+
+    /// the axes of the analog stick of a gamepad which controls the joystick
+    ///
+    /// Both axes are in the range `-1.0..=1.0` and y is positive downwards, like
+    /// [`StickLike`](crate::StickLike). The stick is used while no pointer drags the joystick
+    /// and no bound key is down, see [`GamepadInput`](crate::GamepadInput).
+    /// Defaults to [`Vec2::ZERO`].
+    fn gamepad_stick(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+}
+
+impl<T: JoystickInput + ?Sized> JoystickInput for &T {
+    fn touches(&self) -> Vec<Touch> {
+        (**self).touches()
+    }
+
+    fn mouse_position(&self) -> Vec2 {
+        (**self).mouse_position()
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        (**self).is_mouse_down()
+    }
+
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        (**self).is_key_down(key)
+    }
+
+    fn gamepad_stick(&self) -> Vec2 {
+        (**self).gamepad_stick()
+    }
+}
+
+impl<T: JoystickInput + ?Sized> JoystickInput for Box<T> {
+    fn touches(&self) -> Vec<Touch> {
+        (**self).touches()
+    }
+
+    fn mouse_position(&self) -> Vec2 {
+        (**self).mouse_position()
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        (**self).is_mouse_down()
+    }
+
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        (**self).is_key_down(key)
+    }
+
+    fn gamepad_stick(&self) -> Vec2 {
+        (**self).gamepad_stick()
+    }
+}
+
+/// the input of macroquad
+///
+/// this is used by [`Joystick::update`](crate::Joystick::update)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MacroquadInput;
+
+impl JoystickInput for MacroquadInput {
+    fn touches(&self) -> Vec<Touch> {
+        touches()
+    }
+
+    fn mouse_position(&self) -> Vec2 {
+        let (x, y) = mouse_position();
+        Vec2::new(x, y)
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        is_mouse_button_down(MouseButton::Left)
+    }
+
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        is_key_down(key)
+    }
+}
+
+/// the frame of the game loop in which the [`Joystick`](crate::Joystick) is updated
+///
+/// see [`Joystick::update_in_frame`](crate::Joystick::update_in_frame)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameStamp {
+    /// the duration of the last frame in seconds
+    pub frame_time: f32,
+    /// the time of the update in seconds
+    pub time: f64,
+}
+
+impl FrameStamp {
+    /// create a new stamp
+    pub fn new(frame_time: f32, time: f64) -> Self {
+        Self { frame_time, time }
+    }
+
+    /// the stamp of the current frame of macroquad
+    pub fn current() -> Self {
+        Self::new(get_frame_time(), get_time())
+    }
+
+    /// whether `later` belongs to the same frame as this stamp
+    ///
+    /// macroquad doesn't count its frames, so they are told apart by their frame time:
+    /// `later` is in the same frame if it has exactly the same frame time and less than
+    /// one frame time passed since this stamp.
+    ///
+    /// This is a heuristic: if two frames have exactly the same frame time and the update is
+    /// called later in the first frame than in the second one, less than one frame time passes
+    /// between the stamps, so they are taken for one frame and the second update is skipped. Use
+    /// [`Joystick::update_with_dt`](crate::Joystick::update_with_dt) if the game loop already
+    /// updates each control once per frame.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::FrameStamp;
+    ///
+    /// let frame = FrameStamp::new(0.016, 1.0);
+    /// assert!(frame.is_same_frame(&FrameStamp::new(0.016, 1.005)));
+    /// assert!(!frame.is_same_frame(&FrameStamp::new(0.017, 1.005)));
+    /// assert!(!frame.is_same_frame(&FrameStamp::new(0.016, 1.02)));
+    /// ```
+    pub fn is_same_frame(&self, later: &FrameStamp) -> bool {
+        self.frame_time.to_bits() == later.frame_time.to_bits()
+            && later.time - self.time < self.frame_time as f64
+    }
+}
+
+/// simulated input for one frame
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::{TouchPhase, Vec2};
+/// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+///
+/// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(110.0, 200.0));
+/// let event = joystick.update_with_input(&input, 1.0 / 60.0);
+/// assert_eq!(event.direction, JoystickDirection::Right);
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Ended, Vec2::new(110.0, 200.0));
+/// let event = joystick.update_with_input(&input, 1.0 / 60.0);
+/// assert_eq!(event.direction, JoystickDirection::Idle);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SimulatedInput {
+    /// the touches of the frame
+    pub touches: Vec<Touch>,
+    /// the position of the mouse
+    pub mouse_position: Vec2,
+    /// whether the left mouse button is down
+    pub mouse_down: bool,
+    /// the keys which are down
+    pub keys: Vec<KeyCode>,
+    /// the axes of the analog stick of a gamepad, see [`JoystickInput::gamepad_stick`]
+    pub gamepad_stick: Vec2,
+}
+
+impl SimulatedInput {
+    /// create a new input without touches and with the mouse button up
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a touch to the input
+    pub fn with_touch(mut self, id: u64, phase: TouchPhase, position: Vec2) -> Self {
+        self.touches.push(Touch {
+            id,
+            phase,
+            position,
+        });
+        self
+    }
+
+    /// set the mouse position and the state of the left mouse button
+    pub fn with_mouse(mut self, position: Vec2, down: bool) -> Self {
+        self.mouse_position = position;
+        self.mouse_down = down;
+        self
+    }
+
+    /// add a key which is down
+    pub fn with_key(mut self, key: KeyCode) -> Self {
+        self.keys.push(key);
+        self
+    }
+
+    /// set the axes of the analog stick of a gamepad
+    pub fn with_gamepad_stick(mut self, stick: Vec2) -> Self {
+        self.gamepad_stick = stick;
+        self
+    }
+}
+
+impl JoystickInput for SimulatedInput {
+    fn touches(&self) -> Vec<Touch> {
+        self.touches.clone()
+    }
+
+    fn mouse_position(&self) -> Vec2 {
+        self.mouse_position
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        self.mouse_down
+    }
+
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        self.keys.contains(&key)
+    }
+
+    fn gamepad_stick(&self) -> Vec2 {
+        self.gamepad_stick
+    }
+}
@@ -16,7 +16,7 @@
 //!         clear_background(WHITE);
 //!
 //!         let joystick_event = joystick.update();
-//!         position += joystick_event.direction.to_local() * joystick_event.intensity * SPEED;
+//!         position += joystick_event.axes() * SPEED;
 //!
 //!         draw_circle(position.x, position.y, 50., YELLOW);
 //!
@@ -27,13 +27,117 @@
 //! ```
 #![warn(missing_docs)]
 
+use std::cell::{Cell, RefCell};
+use std::f32::consts::PI;
+use std::fmt;
+
 use macroquad::prelude::{
-    color_u8, draw_circle, is_mouse_button_down, mouse_position, touches, Color, MouseButton,
-    TouchPhase, Vec2,
+    clear_background, color_u8, draw_circle, draw_circle_lines, draw_line, draw_text_ex,
+    draw_texture_ex, draw_triangle, measure_text, pop_camera_state, push_camera_state,
+    render_target, screen_height, screen_width, set_camera, set_default_camera, Camera2D, Color,
+    DrawTextureParams, Font, KeyCode, Mat3, Rect, RenderTarget, TextParams, Texture2D, Touch,
+    TouchPhase, Vec2, BLANK, WHITE,
 };
 
+mod aim;
+mod builder;
+mod button;
+mod config;
+mod direction;
+mod dpad;
+mod feedback;
+mod gamepad;
+#[cfg(feature = "test-utils")]
+mod harness;
+mod input;
+mod manager;
+mod recorder;
+mod scale;
+mod slider;
+mod wheel;
+
+pub use aim::{AimEvent, AimJoystick};
+pub use builder::{JoystickBuilder, JoystickConfigError};
+pub use button::{ButtonCallback, ButtonRenderer, ButtonShape, TouchButton};
+pub use config::{JoystickConfig, JoystickStyleConfig};
+pub use direction::DirectionResolver;
+pub use dpad::{DPad, DPadMode};
+pub use feedback::JoystickFeedback;
+pub use gamepad::{GamepadInput, GamepadStickAdapter, StickLike};
+#[cfg(feature = "test-utils")]
+pub use harness::JoystickHarness;
+pub use input::{FrameStamp, InputSource, JoystickInput, MacroquadInput, SimulatedInput};
+pub use manager::{ControlKind, JoystickManager, LayoutCallback, LayoutChange};
+pub use recorder::{JoystickRecorder, JoystickReplay, RecordedFrame};
+pub use scale::UiScale;
+pub use slider::{SliderOrientation, SliderRenderer, VirtualSlider};
+pub use wheel::{VirtualWheel, WheelRenderer};
+
 static BACKGROUND_COLOR: Color = color_u8!(96, 128, 144, 128);
 static KNOB_COLOR: Color = color_u8!(96, 128, 144, 168);
+/// the minimum size of the joystick and the knob
+const MIN_SIZE: f32 = 1.;
+
+/// clamp sizes which are smaller than [`MIN_SIZE`] (including NaN) to [`MIN_SIZE`]
+fn sanitize_size(size: f32) -> f32 {
+    if size >= MIN_SIZE {
+        size
+    } else {
+        MIN_SIZE
+    }
+}
+
+static LOCK_COLOR: Color = color_u8!(240, 176, 64, 224);
+static SECTOR_LINE_COLOR: Color = color_u8!(255, 255, 255, 192);
+static ACTIVE_SECTOR_COLOR: Color = color_u8!(240, 176, 64, 96);
+/// seconds to fade the hover outline in or out
+const HOVER_FADE_TIME: f32 = 0.1;
+/// the distance (in pixels) and velocity (in pixels per second) below which the
+/// knob spring comes to rest
+const SPRING_REST_DISTANCE: f32 = 0.01;
+/// the maximum number of queued events and repeats, older ones are dropped if they aren't taken
+const MAX_QUEUED: usize = 64;
+/// the maximum time step (in seconds) of the spring integration to keep stiff springs stable
+const SPRING_MAX_STEP: f32 = 1. / 120.;
+/// the maximum time (in seconds) which a spring integrates in one update, so a long frame
+/// (e.g. after the app was suspended) doesn't hang the update
+const SPRING_MAX_TIME: f32 = 1.;
+/// the minimum component of the axis scale, see [`Joystick::set_axis_scale`]
+const MIN_AXIS_SCALE: f32 = 0.01;
+
+/// queue the item and drop the oldest one if the queue is full, see [`MAX_QUEUED`]
+fn push_capped<T>(queue: &mut Vec<T>, item: T) {
+    if queue.len() >= MAX_QUEUED {
+        queue.remove(0);
+    }
+    queue.push(item);
+}
+
+/// sort the phases of each touch into the order start, movement, end
+///
+/// the touches are grouped by their first occurrence, so the order of different touches is kept
+fn order_touch_phases(touches: &mut [Touch]) {
+    if touches.len() < 2 {
+        return;
+    }
+    let rank = |phase: TouchPhase| match phase {
+        TouchPhase::Started => 0,
+        TouchPhase::Stationary | TouchPhase::Moved => 1,
+        TouchPhase::Ended | TouchPhase::Cancelled => 2,
+    };
+    let ids: Vec<u64> = touches.iter().map(|touch| touch.id).collect();
+    let first = |id: u64| ids.iter().position(|&other| other == id);
+    touches.sort_by_key(|touch| (first(touch.id), rank(touch.phase)));
+}
+
+/// tracer of the [`Joystick`], see [`Joystick::set_tracer`]
+pub type JoystickTracer = Box<dyn FnMut(&str, &JoystickEvent)>;
+
+/// renderer of an element of the [`Joystick`], see [`Joystick::from_custom_renderers`]
+pub type JoystickRenderer = Box<dyn FnMut(f32, f32, f32, JoystickRenderState)>;
+
+/// a callback of the events of the [`Joystick`], see [`Joystick::on_press`]
+pub type EventCallback = Box<dyn FnMut(&JoystickEvent)>;
 
 /// The joystick component
 ///
@@ -55,7 +159,119 @@ pub struct Joystick {
     knob: JoystickElement,
     dragging: bool,
     touch_id: u64,
-    event: JoystickEvent,
+    source: InputSource,
+    /// the event of the input
+    raw_event: JoystickEvent,
+    /// the event of the input after all post-processing, which is returned by `update`
+    output: JoystickEvent,
+    transform: Mat3,
+    inverse_transform: Mat3,
+    render_cache: bool,
+    render_cache_dirty: Cell<bool>,
+    track_rotation: bool,
+    rotation_min_intensity: f32,
+    total_rotation: f32,
+    last_rotation_angle: Option<f32>,
+    repeat: Option<Repeat>,
+    repeat_direction: JoystickDirection,
+    repeat_timer: f32,
+    repeats: Vec<JoystickDirection>,
+    outer_ring: Option<OuterRing>,
+    label: Option<JoystickLabel>,
+    turbo: Option<Turbo>,
+    turbo_charge: f32,
+    hover_color: Option<Color>,
+    hovered: bool,
+    hover_fade: f32,
+    id: Option<String>,
+    tracer: Option<JoystickTracer>,
+    grab_forgiveness: f32,
+    direction_lock: bool,
+    direction_lock_threshold: f32,
+    /// the threshold before [`Joystick::set_sticky`] set it to `0.0`
+    sticky_threshold: Option<f32>,
+    lock_color: Color,
+    locked: bool,
+    bands: Vec<f32>,
+    band_hysteresis: f32,
+    direction_hysteresis: f32,
+    band: u8,
+    attachment: Option<Box<dyn Fn() -> Vec2>>,
+    static_center: Vec2,
+    rim_persistence: Option<RimPersistence>,
+    rim_event: Option<JoystickEvent>,
+    rim_dip_frames: u32,
+    rim_dip_time: f32,
+    touch_handoff: bool,
+    idle_appearance: IdleAppearance,
+    axis_scale: Vec2,
+    scale_knob: bool,
+    idle_time: f32,
+    idle_timeout: Option<IdleTimeout>,
+    callbacks: EventCallbacks,
+    knob_spring: Option<Spring>,
+    spring_offset: Vec2,
+    spring_velocity: Vec2,
+    return_duration: f32,
+    /// the rendered offset of the knob when it was released
+    return_from: Vec2,
+    return_easing: Easing,
+    return_elapsed: f32,
+    smoothing: Option<f32>,
+    /// the position of the pointer which the smoothed drag position follows
+    smoothing_target: Vec2,
+    mirrored: bool,
+    mirror_axis: Option<f32>,
+    mirror_output: bool,
+    commit_distance: f32,
+    press_position: Vec2,
+    committed: bool,
+    /// the frame of the first update in the current frame
+    frame: Option<FrameStamp>,
+    axis_response: Option<(ResponseCurve, ResponseCurve)>,
+    response_curve: ResponseCurve,
+    predictive_start: bool,
+    stats: Option<Box<JoystickStats>>,
+    time_scale: f32,
+    hold_time: f32,
+    swipe_interpolation: Option<SwipeInterpolation>,
+    hit_radius: f32,
+    hit_rect: Option<Rect>,
+    follow: bool,
+    follow_bounds: Option<Rect>,
+    feedback: Option<Box<dyn JoystickFeedback>>,
+    /// whether the intensity of the output reached the edge
+    at_edge: bool,
+    enabled: bool,
+    visible: bool,
+    auto_fade: Option<AutoFade>,
+    snap_knob: bool,
+    travel_radius: f32,
+    touch_synthesizes_mouse: bool,
+    mode: JoystickMode,
+    style: Option<JoystickStyle>,
+    /// the paths of the background and the knob texture, see [`Joystick::set_texture_paths`]
+    texture_paths: (Option<String>, Option<String>),
+    dead_zone: f32,
+    saturation: f32,
+    axis: JoystickAxis,
+    /// whether the joystick was grabbed in the current update
+    grabbed: bool,
+    camera_aware: bool,
+    keys: Option<KeyBindings>,
+    resolver: DirectionResolver,
+    /// whether the bound keys or the gamepad stick control the joystick
+    external_active: bool,
+    flick: Option<Flick>,
+    /// the hold time and the knob offset relative to the travel radius of the recent updates
+    flick_samples: Vec<(f32, Vec2)>,
+    /// whether the mouse mirrors a touch until its button is released
+    mouse_suppressed: bool,
+    /// whether the mouse button was down in the last update
+    mouse_was_down: bool,
+    /// the last position of the drag
+    drag_position: Vec2,
+    events: Vec<JoystickEvent>,
 }
 
 impl Joystick {
@@ -65,6 +281,8 @@ impl Joystick {
     /// * `x`, `y`: center of the joystick
     /// * `size`: diameter of the joystick
     ///
+    /// sizes which are below one pixel (including NaN) are clamped to one pixel
+    ///
     /// # Examples
     /// ```
     /// use macroquad_virtual_joystick::Joystick;
@@ -74,24 +292,174 @@ impl Joystick {
     /// let joystick = Joystick::new(center_x, center_y, size);
     /// ```
     pub fn new(x: f32, y: f32, size: f32) -> Self {
-        let background_fn = Box::new(|center_x: f32, center_y: f32, radius: f32| {
-            draw_circle(center_x, center_y, radius, BACKGROUND_COLOR);
-        });
-        let background = JoystickElement::new(x, y, size / 2., background_fn);
-        let knob_fn = Box::new(|center_x: f32, center_y: f32, radius: f32| {
-            draw_circle(center_x, center_y, radius, KNOB_COLOR);
+        let size = sanitize_size(size);
+        let circle = |color: Color| -> JoystickRenderer {
+            Box::new(move |x, y, radius, state: JoystickRenderState| {
+                let color = Color {
+                    a: color.a * state.opacity,
+                    ..color
+                };
+                draw_circle(x, y, radius, color);
+            })
+        };
+        let background =
+            JoystickElement::with_state(x, y, size / 2., circle(BACKGROUND_COLOR)).cacheable();
+        let knob = JoystickElement::with_state(x, y, size / 4., circle(KNOB_COLOR)).cacheable();
+
+        Self::from_elements(Vec2::new(x, y), size, background, knob)
+    }
+
+    /// create a new joystick which is anchored to a corner or an edge of the screen
+    ///
+    /// The center is recomputed from [`screen_width`] and [`screen_height`] in each update
+    /// (see [`Joystick::attach_to`]), so the joystick stays in the corner when the window is
+    /// resized or the orientation changes. [`Joystick::detach`] keeps it at its last position.
+    ///
+    /// # Arguments
+    /// * `anchor`: the corner or the edge of the screen
+    /// * `offset`: the distance of the center from the anchor, see [`Anchor::position`]
+    /// * `size`: diameter of the joystick
+    pub fn anchored(anchor: Anchor, offset: Vec2, size: f32) -> Self {
+        let mut joystick = Self::new(0., 0., size);
+        joystick.set_anchor(anchor, offset);
+        joystick
+    }
+
+    /// create a new joystick which is hidden until it's spawned by a press in `activation`
+    ///
+    /// This is a [`JoystickMode::Floating`] joystick which returns to the center of the
+    /// activation area after the release, with [`IdleAppearance::Hidden`].
+    ///
+    /// # Arguments
+    /// * `activation`: the area in which a press spawns the joystick
+    /// * `size`: diameter of the joystick
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{Rect, Vec2};
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::floating(Rect::new(0.0, 200.0, 400.0, 400.0), 50.0);
+    /// let press = SimulatedInput::new().with_mouse(Vec2::new(50.0, 500.0), true);
+    /// joystick.update_with_input(&press, 0.0);
+    /// assert_eq!(joystick.center(), Vec2::new(50.0, 500.0));
+    ///
+    /// let release = SimulatedInput::new().with_mouse(Vec2::new(50.0, 500.0), false);
+    /// joystick.update_with_input(&release, 0.0);
+    /// assert_eq!(joystick.center(), Vec2::new(200.0, 400.0));
+    /// ```
+    pub fn floating(activation: Rect, size: f32) -> Self {
+        let center = activation.center();
+        let mut joystick = Self::new(center.x, center.y, size);
+        joystick.set_mode(JoystickMode::Floating {
+            activation,
+            return_on_release: true,
         });
-        let knob = JoystickElement::new(x, y, size / 4., knob_fn);
+        joystick.set_idle_appearance(IdleAppearance::Hidden);
+        joystick
+    }
 
-        Self {
-            center: Vec2::new(x, y),
-            size,
-            background,
-            knob,
-            dragging: false,
-            touch_id: 0,
-            event: JoystickEvent::default(),
+    /// create a new joystick which is placed relative to the size of the screen
+    ///
+    /// Like [`Joystick::anchored`], the center is recomputed in each update, so the joystick
+    /// keeps its relative position when the window is resized.
+    ///
+    /// # Arguments
+    /// * `fraction`: the center as a fraction of the screen, e.g. `(0.15, 0.8)` for the
+    ///   lower left part of the screen
+    /// * `size`: diameter of the joystick, e.g. scaled with [`UiScale`]
+    pub fn relative(fraction: Vec2, size: f32) -> Self {
+        let mut joystick = Self::new(0., 0., size);
+        joystick.attach_to(Box::new(move || {
+            fraction * Vec2::new(screen_width(), screen_height())
+        }));
+        joystick
+    }
+
+    /// create a new joystick which is drawn with the style
+    ///
+    /// # Arguments
+    /// * `x`, `y`: center of the joystick
+    /// * `size`: diameter of the joystick
+    /// * `style`: colors and textures of the joystick, see [`Joystick::set_style`]
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::RED;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickStyle};
+    ///
+    /// let joystick = Joystick::with_style(
+    ///     100.0,
+    ///     200.0,
+    ///     50.0,
+    ///     JoystickStyle {
+    ///         knob_color: RED,
+    ///         knob_scale: 0.4,
+    ///         idle_alpha: 0.5,
+    ///         border_color: RED,
+    ///         border_width: 2.0,
+    ///         ..JoystickStyle::default()
+    ///     },
+    /// );
+    /// ```
+    pub fn with_style(x: f32, y: f32, size: f32, style: JoystickStyle) -> Self {
+        let mut joystick = Self::new(x, y, size);
+        joystick.set_style(Some(style));
+        joystick
+    }
+
+    /// create a new joystick which is drawn with the textures
+    ///
+    /// see [`JoystickStyle::textured`]
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use macroquad::prelude::load_texture;
+    /// use macroquad_virtual_joystick::Joystick;
+    ///
+    /// # async fn run() {
+    /// let background = load_texture("background.png").await.unwrap();
+    /// let knob = load_texture("knob.png").await.unwrap();
+    /// let joystick = Joystick::with_textures(100.0, 200.0, 50.0, background, knob);
+    /// # }
+    /// ```
+    pub fn with_textures(
+        x: f32,
+        y: f32,
+        size: f32,
+        background: Texture2D,
+        knob: Texture2D,
+    ) -> Self {
+        Self::with_style(x, y, size, JoystickStyle::textured(background, knob))
+    }
+
+    /// set the style of the joystick
+    ///
+    /// A style replaces the elements of the joystick (see [`Joystick::from_custom_elements`]):
+    /// the background and the knob are drawn as circles in their colors or as their textures,
+    /// which are centered and scaled to the diameter. The knob is resized according to
+    /// [`JoystickStyle::knob_scale`] and the hover color is replaced by
+    /// [`JoystickStyle::hover_color`]. Styled joysticks are not cached
+    /// (see [`Joystick::set_render_cache`]).
+    ///
+    /// Defaults to `None`, which draws the elements. Setting it to `None` restores the knob
+    /// size and the hover color of [`Joystick::new`].
+    pub fn set_style(&mut self, style: Option<JoystickStyle>) {
+        if let Some(style) = style {
+            self.knob.radius =
+                (self.size * style.knob_scale / 2.).clamp(MIN_SIZE / 2., self.size / 2.);
+            self.hover_color = style.hover_color;
+        } else {
+            self.knob.radius = self.size / 4.;
+            self.hover_color = None;
         }
+        self.style = style;
+        self.render_cache_dirty.set(true);
+    }
+
+    /// the style of the joystick, see [`Joystick::set_style`]
+    pub fn style(&self) -> Option<JoystickStyle> {
+        self.style
     }
 
     /// create a new [`Joystick`] with custom elements for background and knob
@@ -101,12 +469,16 @@ impl Joystick {
     /// * `size`: diameter of the joystick, should have the same size as the background element
     /// * `knob_size`: diameter of the knob, should have the same size as the background element
     /// * `render_background`, `render_knob`: custom drawing functions with the following
-    ///  arguments:
+    ///   arguments:
     ///   * `x` the x coordinate of the center of the component
     ///   * `y` the y coordinate of the center of the component
     ///   * `radius` the radius used for mouse/ touch collision
     ///     for good UX this should also be the size of the drawing
     ///
+    /// sizes which are below one pixel (including NaN) are clamped to one pixel.
+    /// a knob which is larger than the background is clamped to the size of the background
+    /// (this panics in debug builds, use [`JoystickBuilder`] to handle it as an error)
+    ///
     /// # Examples
     /// ```
     /// use macroquad::prelude::*;
@@ -157,10 +529,71 @@ impl Joystick {
         render_background: Box<fn(f32, f32, f32)>,
         render_knob: Box<fn(f32, f32, f32)>,
     ) -> Self {
+        Self::from_custom_renderers(
+            x,
+            y,
+            size,
+            knob_size,
+            move |x, y, radius, _| render_background(x, y, radius),
+            move |x, y, radius, _| render_knob(x, y, radius),
+        )
+    }
+
+    /// create a new [`Joystick`] with closures which draw the background and the knob
+    ///
+    /// Like [`Joystick::from_custom_elements`], but the drawing functions can capture state
+    /// (e.g. textures or an animation) and get the [`JoystickRenderState`] of the joystick as
+    /// the fourth argument, so they can tint or animate the elements depending on the input.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::*;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickRenderState};
+    ///
+    /// let mut pulse = 0.0;
+    /// let joystick = Joystick::from_custom_renderers(
+    ///     100.0,
+    ///     200.0,
+    ///     50.0,
+    ///     25.0,
+    ///     |x, y, radius, state: JoystickRenderState| {
+    ///         let color = if state.active { ORANGE } else { GRAY };
+    ///         draw_circle(x, y, radius, color);
+    ///     },
+    ///     move |x, y, radius, state: JoystickRenderState| {
+    ///         pulse += state.intensity;
+    ///         draw_circle(x, y, radius * (1.0 + 0.1 * pulse.sin()), WHITE);
+    ///     },
+    /// );
+    /// ```
+    pub fn from_custom_renderers(
+        x: f32,
+        y: f32,
+        size: f32,
+        knob_size: f32,
+        render_background: impl FnMut(f32, f32, f32, JoystickRenderState) + 'static,
+        render_knob: impl FnMut(f32, f32, f32, JoystickRenderState) + 'static,
+    ) -> Self {
+        let size = sanitize_size(size);
+        debug_assert!(
+            knob_size.is_nan() || knob_size <= size,
+            "the knob must not be larger than the background"
+        );
+        let knob_size = sanitize_size(knob_size).min(size);
         let center = Vec2::new(x, y);
-        let background = JoystickElement::new(x, y, size / 2., render_background);
-        let knob = JoystickElement::new(x, y, knob_size / 2., render_knob);
+        let background = JoystickElement::with_state(x, y, size / 2., Box::new(render_background));
+        let knob = JoystickElement::with_state(x, y, knob_size / 2., Box::new(render_knob));
+
+        Self::from_elements(center, size, background, knob)
+    }
 
+    /// create a new joystick from its elements
+    fn from_elements(
+        center: Vec2,
+        size: f32,
+        background: JoystickElement,
+        knob: JoystickElement,
+    ) -> Self {
         Self {
             center,
             size,
@@ -168,201 +601,4532 @@ impl Joystick {
             knob,
             dragging: false,
             touch_id: 0,
-            event: JoystickEvent::default(),
+            source: InputSource::None,
+            raw_event: JoystickEvent::default(),
+            output: JoystickEvent::default(),
+            transform: Mat3::IDENTITY,
+            inverse_transform: Mat3::IDENTITY,
+            render_cache: false,
+            render_cache_dirty: Cell::new(true),
+            track_rotation: false,
+            rotation_min_intensity: 0.5,
+            total_rotation: 0.,
+            last_rotation_angle: None,
+            repeat: None,
+            repeat_direction: JoystickDirection::Idle,
+            repeat_timer: 0.,
+            repeats: Vec::new(),
+            outer_ring: None,
+            label: None,
+            turbo: None,
+            turbo_charge: 0.,
+            hover_color: None,
+            hovered: false,
+            hover_fade: 0.,
+            id: None,
+            tracer: None,
+            grab_forgiveness: 1.,
+            direction_lock: false,
+            direction_lock_threshold: 0.9,
+            sticky_threshold: None,
+            lock_color: LOCK_COLOR,
+            locked: false,
+            bands: Vec::new(),
+            band_hysteresis: 0.,
+            direction_hysteresis: 0.,
+            band: 0,
+            attachment: None,
+            static_center: center,
+            rim_persistence: None,
+            rim_event: None,
+            rim_dip_frames: 0,
+            rim_dip_time: 0.,
+            touch_handoff: false,
+            idle_appearance: IdleAppearance::Full,
+            axis_scale: Vec2::ONE,
+            scale_knob: false,
+            idle_time: 0.,
+            idle_timeout: None,
+            callbacks: EventCallbacks::default(),
+            knob_spring: None,
+            spring_offset: Vec2::ZERO,
+            spring_velocity: Vec2::ZERO,
+            return_duration: 0.,
+            return_from: Vec2::ZERO,
+            return_easing: Easing::default(),
+            return_elapsed: 0.,
+            smoothing: None,
+            smoothing_target: center,
+            mirrored: false,
+            mirror_axis: None,
+            mirror_output: false,
+            commit_distance: 0.,
+            press_position: center,
+            committed: false,
+            frame: None,
+            axis_response: None,
+            response_curve: ResponseCurve::Linear,
+            predictive_start: false,
+            stats: None,
+            time_scale: 1.,
+            hold_time: 0.,
+            swipe_interpolation: None,
+            hit_radius: size / 2.,
+            hit_rect: None,
+            follow: false,
+            follow_bounds: None,
+            feedback: None,
+            at_edge: false,
+            enabled: true,
+            visible: true,
+            auto_fade: None,
+            snap_knob: false,
+            travel_radius: size / 2.,
+            touch_synthesizes_mouse: false,
+            mode: JoystickMode::Fixed,
+            style: None,
+            texture_paths: (None, None),
+            dead_zone: 0.,
+            saturation: 1.,
+            axis: JoystickAxis::Both,
+            grabbed: false,
+            camera_aware: false,
+            keys: None,
+            resolver: DirectionResolver::default(),
+            external_active: false,
+            flick: None,
+            flick_samples: Vec::new(),
+            mouse_suppressed: false,
+            mouse_was_down: false,
+            drag_position: center,
+            events: Vec::new(),
         }
     }
 
-    /// render the joystick
+    /// check the configuration for conflicting options
     ///
-    /// renders the background and knob
+    /// The setters accept conflicting options and fall back to a documented behavior,
+    /// e.g. a commit distance larger than the background means that a drag only commits
+    /// outside of it. This reports these conflicts, e.g. after changing options at runtime.
     ///
-    /// call [`macroquad::prelude::set_default_camera()`] before!
-    pub fn render(&self) {
-        self.background.render();
-        self.knob.render();
-    }
-
-    /// update the joystick from touch
-    fn update_touch(&mut self) {
-        for touch in touches() {
-            match touch.phase {
-                TouchPhase::Started => {
-                    // a touch starts in the joystick
-                    if (touch.position - self.center).length() < (self.size / 2.) {
-                        self.dragging = true;
-                        self.touch_id = touch.id;
-                        self.moving(touch.position);
-                    }
-                }
-                TouchPhase::Moved => {
-                    if self.dragging && touch.id == self.touch_id {
-                        self.moving(touch.position);
-                    }
-                }
-                TouchPhase::Ended | TouchPhase::Cancelled => {
-                    if self.dragging && touch.id == self.touch_id {
-                        self.reset();
-                    }
-                }
-                _ => {}
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::{Joystick, JoystickConfigError};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// assert_eq!(joystick.validate(), Ok(()));
+    /// joystick.set_commit_distance(60.0);
+    /// assert_eq!(
+    ///     joystick.validate(),
+    ///     Err(JoystickConfigError::CommitDistanceTooLarge {
+    ///         commit_distance: 60.0,
+    ///         size: 50.0,
+    ///     })
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), JoystickConfigError> {
+        if self.size.is_nan() || self.size <= 0. {
+            return Err(JoystickConfigError::InvalidSize(self.size));
+        }
+        let knob_size = self.knob.radius * 2.;
+        if !knob_size.is_finite() || knob_size <= 0. {
+            return Err(JoystickConfigError::InvalidKnobSize(knob_size));
+        }
+        if knob_size > self.size {
+            return Err(JoystickConfigError::KnobLargerThanBackground {
+                knob_size,
+                size: self.size,
+            });
+        }
+        if !self.bands.windows(2).all(|pair| pair[0] <= pair[1]) {
+            return Err(JoystickConfigError::BandsOutOfOrder(self.bands.clone()));
+        }
+        if !(self.axis_scale.x > 0. && self.axis_scale.y > 0.) {
+            return Err(JoystickConfigError::InvalidAxisScale(self.axis_scale));
+        }
+        if self.grab_forgiveness.is_nan() || self.grab_forgiveness <= 0. {
+            return Err(JoystickConfigError::InvalidGrabForgiveness(
+                self.grab_forgiveness,
+            ));
+        }
+        if self.commit_distance > self.size {
+            return Err(JoystickConfigError::CommitDistanceTooLarge {
+                commit_distance: self.commit_distance,
+                size: self.size,
+            });
+        }
+        if self.time_scale.is_nan() || self.time_scale < 0. {
+            return Err(JoystickConfigError::InvalidTimeScale(self.time_scale));
+        }
+        if !(0. <= self.dead_zone && self.dead_zone < self.saturation && self.saturation <= 1.) {
+            return Err(JoystickConfigError::InvalidRadialZones {
+                dead_zone: self.dead_zone,
+                saturation: self.saturation,
+            });
+        }
+        if let Some(hit_rect) = self.hit_rect {
+            if !(hit_rect.w > 0. && hit_rect.h > 0.) {
+                return Err(JoystickConfigError::EmptyHitRect(hit_rect));
             }
         }
+        if self.travel_radius.is_nan() || self.travel_radius <= 0. {
+            return Err(JoystickConfigError::InvalidTravelRadius(self.travel_radius));
+        }
+        Ok(())
     }
 
-    /// update the joystick from mouse drag
-    fn update_mouse(&mut self) {
-        let (mouse_x, mouse_y) = mouse_position();
-        let mouse = Vec2::new(mouse_x, mouse_y);
-        let mouse_down = is_mouse_button_down(MouseButton::Left);
-        if self.dragging {
-            if mouse_down {
-                self.moving(mouse)
-            } else {
-                self.reset();
-            }
-        } else if mouse_down && (self.center - mouse).length() < (self.size / 2.) {
-            self.dragging = true;
-            self.moving(mouse)
+    /// set the transform which is applied to the joystick
+    ///
+    /// The transform is applied when rendering the elements and its inverse is applied
+    /// to the pointer positions before hit testing, so the joystick can be placed in a
+    /// scaled, offset or rotated part of the screen (e.g. one half of a split-screen).
+    /// The joystick itself keeps working in its own untransformed coordinates.
+    ///
+    /// Defaults to [`Mat3::IDENTITY`]. A transform which is not invertible is ignored and
+    /// the previous transform is kept (this panics in debug builds).
+    ///
+    /// # Arguments
+    /// * `transform`: 2D affine transform from joystick coordinates to screen coordinates,
+    ///   should be invertible
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{Mat3, Vec2};
+    /// use macroquad_virtual_joystick::Joystick;
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// // draw the joystick at half size in the right half of a 800px wide screen
+    /// joystick.set_transform(Mat3::from_scale_angle_translation(
+    ///     Vec2::new(0.5, 0.5),
+    ///     0.0,
+    ///     Vec2::new(400.0, 0.0),
+    /// ));
+    /// ```
+    pub fn set_transform(&mut self, transform: Mat3) {
+        let determinant = transform.determinant();
+        debug_assert!(
+            determinant != 0. && determinant.is_finite(),
+            "the transform of the joystick must be invertible"
+        );
+        if determinant == 0. || !determinant.is_finite() {
+            return;
+        }
+        if transform.determinant() != self.transform.determinant() {
+            // the elements are drawn with a different radius
+            self.render_cache_dirty.set(true);
         }
+        self.transform = transform;
+        self.inverse_transform = transform.inverse();
     }
 
-    /// reset the joystick
-    fn reset(&mut self) {
-        self.dragging = false;
-        self.knob.x = self.center.x;
-        self.knob.y = self.center.y;
-        self.event = JoystickEvent::default();
+    /// get the transform which is applied to the joystick
+    ///
+    /// see [`Joystick::set_transform`]
+    pub fn transform(&self) -> Mat3 {
+        self.transform
     }
 
-    /// update the joystick
+    /// map a screen position into the coordinates of the joystick
+    fn to_local(&self, position: Vec2) -> Vec2 {
+        self.inverse_transform
+            .transform_point2(position - self.mirror_offset())
+    }
+
+    /// the transform from joystick coordinates to screen coordinates including the mirroring
+    fn screen_transform(&self) -> Mat3 {
+        Mat3::from_translation(self.mirror_offset()) * self.transform
+    }
+
+    /// mirror the joystick for left-handed layouts
     ///
-    /// this updates the joystick and returns the current [`JoystickEvent`]
+    /// The position of the joystick on the screen is reflected across the mirror axis
+    /// (see [`Joystick::set_mirror_axis`]) after the transform, and the horizontal offset of the
+    /// label is flipped. Rendering and hit testing use the mirrored position.
+    /// The joystick itself is not flipped: dragging to the right still reports a direction to
+    /// the right unless [`Joystick::set_mirror_output`] is enabled.
+    ///
+    /// An attached joystick (see [`Joystick::attach_to`]) follows its attachment and is not
+    /// mirrored.
+    ///
+    /// Changing the mirroring while the joystick is dragged cancels the drag, because the
+    /// joystick moves away from the pointer. A latched direction is kept.
+    ///
+    /// Defaults to `false`.
     ///
     /// # Examples
-    /// see [`Joystick`]
-    pub fn update(&mut self) -> JoystickEvent {
-        if touches().is_empty() {
-            self.update_mouse();
-        } else {
-            self.update_touch();
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_mirror_axis(Some(400.0));
+    /// joystick.set_mirrored(true);
+    ///
+    /// // the joystick is now at the right edge of a 800px wide screen
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(710.0, 200.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::Right);
+    /// ```
+    pub fn set_mirrored(&mut self, mirrored: bool) {
+        if mirrored != self.mirrored && self.dragging {
+            self.reset();
         }
-        self.event
+        self.mirrored = mirrored;
     }
 
-    /// move the knob according to the drag position and update the [`self.event`]
-    fn moving(&mut self, position: Vec2) {
-        let radius = self.size / 2.;
-        let delta = position - self.center;
-        let angle = delta.y.atan2(delta.x);
-        let angle_degrees = angle.to_degrees();
-
-        // maximum distance for the knob is the radius of the background
-        let dist = f32::min(delta.length(), radius);
-
-        self.knob.x = self.center.x + dist * angle.cos();
-        self.knob.y = self.center.y + dist * angle.sin();
+    /// whether the joystick is mirrored
+    ///
+    /// see [`Joystick::set_mirrored`]
+    pub fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
 
-        let intensity = dist / radius;
-        let direction = if intensity == 0. {
-            JoystickDirection::Idle
-        } else {
-            JoystickDirection::from_degrees(angle_degrees as f64)
-        };
-        self.event = JoystickEvent::new(direction, intensity, angle);
+    /// set the x coordinate on the screen across which the joystick is mirrored
+    ///
+    /// see [`Joystick::set_mirrored`]
+    ///
+    /// Defaults to `None`, which is the center of the screen.
+    pub fn set_mirror_axis(&mut self, mirror_axis: Option<f32>) {
+        self.mirror_axis = mirror_axis;
     }
-}
 
-/// element of the [`Joystick`]
-///
-/// can be used for the background or the knob
-struct JoystickElement {
-    x: f32,
-    y: f32,
-    radius: f32,
-    drawable: Box<dyn Fn(f32, f32, f32)>,
-}
+    /// mirror the horizontal axis of the events while the joystick is mirrored
+    ///
+    /// see [`Joystick::set_mirrored`]
+    ///
+    /// If enabled, a drag to the right of a mirrored joystick is reported as a drag to the left
+    /// and the tracked rotation is reversed.
+    ///
+    /// Defaults to `false`.
+    pub fn set_mirror_output(&mut self, mirror_output: bool) {
+        self.mirror_output = mirror_output;
+    }
 
-impl JoystickElement {
-    fn new(x: f32, y: f32, radius: f32, drawable: Box<dyn Fn(f32, f32, f32)>) -> Self {
-        Self {
-            x,
-            y,
-            radius,
-            drawable,
+    /// the translation on the screen which mirrors the joystick
+    fn mirror_offset(&self) -> Vec2 {
+        if !self.mirrored || self.attachment.is_some() {
+            return Vec2::ZERO;
         }
+        let axis = self.mirror_axis.unwrap_or_else(|| screen_width() / 2.);
+        let center = self.transform.transform_point2(self.center);
+        Vec2::new(2. * (axis - center.x), 0.)
     }
 
-    /// render the element
-    pub fn render(&self) {
-        (self.drawable)(self.x, self.y, self.radius);
+    /// mirror the horizontal axis of the event if enabled
+    fn apply_mirror_output(&self, event: &mut JoystickEvent) {
+        if !self.mirrored || !self.mirror_output || event.is_idle() {
+            return;
+        }
+        let mut angle = PI - event.angle;
+        if angle > PI {
+            angle -= 2. * PI;
+        }
+        event.angle = angle;
+        event.direction = self.resolver.direction(angle);
+        event.sector = self.resolver.sector(angle);
+        event.total_rotation = -event.total_rotation;
     }
-}
-
-#[allow(missing_docs)]
-/// different directions of the [`Joystick`]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum JoystickDirection {
-    Up,
-    UpLeft,
-    Left,
-    DownLeft,
-    Down,
-    DownRight,
-    Right,
-    UpRight,
-    Idle,
-}
 
-impl JoystickDirection {
-    /// calculate a JoystickDirection from degrees
+    /// enable or disable the render cache
     ///
-    /// 0 degrees are on the positive X-Axis and then it rotates clockwise
+    /// If enabled, the default background and knob are drawn once into small textures
+    /// which are then drawn each frame instead of tessellating the circles again.
+    /// The textures are redrawn when the drawn size of the joystick changes.
+    /// Custom elements (see [`Joystick::from_custom_elements`]) are never cached.
+    ///
+    /// Defaults to `false`.
     ///
     /// # Examples
     /// ```
-    /// use macroquad_virtual_joystick::JoystickDirection;
-    ///
-    /// let degrees = 153.5;
-    /// let direction = JoystickDirection::from_degrees(degrees);
+    /// use macroquad_virtual_joystick::Joystick;
     ///
-    /// assert_eq!(direction, JoystickDirection::DownLeft);
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_render_cache(true);
+    /// assert!(joystick.render_cache());
+    /// // nothing has been drawn yet
+    /// assert!(joystick.is_render_cache_dirty());
     /// ```
-    pub fn from_degrees(degrees: f64) -> Self {
-        if degrees > -22.5 && degrees <= 22.5 {
-            Self::Right
-        } else if degrees > 22.5 && degrees <= 67.5 {
-            Self::DownRight
-        } else if degrees > 67.5 && degrees <= 112.5 {
-            Self::Down
-        } else if degrees > 112.5 && degrees <= 157.5 {
-            Self::DownLeft
-        } else if (degrees > 157.5 && degrees <= 180.) || (degrees > -180. && degrees <= -157.5)  {
-            Self::Left
-        } else if degrees > -157.5 && degrees <= -112.5 {
-            Self::UpLeft
-        } else if degrees > -112.5 && degrees <= -67.5 {
-            Self::Up
-        } else if degrees > -67.5 && degrees <= -22.5 {
-            Self::UpRight
-        } else {
-            Self::Idle
+    pub fn set_render_cache(&mut self, render_cache: bool) {
+        if render_cache != self.render_cache {
+            self.render_cache = render_cache;
+            self.render_cache_dirty.set(true);
         }
     }
 
-    /// convert the direction to a Vec2 with x and y
+    /// whether the render cache is enabled
     ///
-    /// x and y are both one of these: [-1.0, 0.0, 1.0]
+    /// see [`Joystick::set_render_cache`]
+    pub fn render_cache(&self) -> bool {
+        self.render_cache
+    }
+
+    /// whether the cached textures will be redrawn on the next [`Joystick::render`]
     ///
     /// # Examples
     /// ```
-    /// use macroquad::prelude::Vec2;
-    /// use macroquad_virtual_joystick::JoystickDirection;
+    /// use macroquad::prelude::{Mat3, Vec2};
+    /// use macroquad_virtual_joystick::Joystick;
     ///
-    /// let direction = JoystickDirection::Up;
-    /// assert_eq!(direction.to_local(), Vec2::new(0.0, -1.0))
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_render_cache(true);
+    /// // a different scale changes the drawn size of the elements
+    /// joystick.set_transform(Mat3::from_scale(Vec2::new(2.0, 2.0)));
+    /// assert!(joystick.is_render_cache_dirty());
     /// ```
-    pub fn to_local(&self) -> Vec2 {
-        let (x, y) = match self {
+    pub fn is_render_cache_dirty(&self) -> bool {
+        self.render_cache_dirty.get()
+    }
+
+    /// whether the cached textures have to be redrawn in this render
+    ///
+    /// the flag is only cleared if the cache draws the elements, so it's kept while the cache
+    /// is disabled or a style draws the elements
+    pub(crate) fn take_render_cache_dirty(&self) -> bool {
+        self.render_cache && self.style.is_none() && self.render_cache_dirty.replace(false)
+    }
+
+    /// render the joystick
+    ///
+    /// renders the background and knob
+    ///
+    /// call [`macroquad::prelude::set_default_camera()`] before or enable
+    /// [`Joystick::set_camera_aware`]!
+    pub fn render(&self) {
+        self.with_screen_camera(|| self.render_unchecked());
+    }
+
+    /// set whether the joystick renders with its own screen space camera
+    ///
+    /// The touches and the mouse are always in screen coordinates, but the joystick is drawn
+    /// with the active camera. If enabled, [`Joystick::render`] and
+    /// [`Joystick::render_sectors`] push the camera state, draw with the default camera and
+    /// pop the camera state afterwards, so a game can render the joystick while its world
+    /// camera is active.
+    ///
+    /// Defaults to `false`.
+    pub fn set_camera_aware(&mut self, camera_aware: bool) {
+        self.camera_aware = camera_aware;
+    }
+
+    /// run `draw` with the default camera if the joystick is camera aware
+    fn with_screen_camera(&self, draw: impl FnOnce()) {
+        if self.camera_aware {
+            push_camera_state();
+            set_default_camera();
+            draw();
+            pop_camera_state();
+        } else {
+            draw();
+        }
+    }
+
+    /// render the joystick with the active camera
+    fn render_unchecked(&self) {
+        let hidden = self.idle_appearance == IdleAppearance::Hidden && !self.is_active();
+        if !self.visible || hidden {
+            return;
+        }
+        if let Some(outer_ring) = self.outer_ring {
+            let color = self.faded(outer_ring.color);
+            self.draw_outline(self.center, outer_ring.radius, 2., color);
+        }
+        match self.idle_appearance {
+            IdleAppearance::Outline { thickness, color } if !self.is_active() => {
+                let color = self.faded(color);
+                self.draw_outline(self.center, self.background.radius, thickness, color);
+            }
+            _ => self.render_elements(),
+        }
+        if let Some(label) = &self.label {
+            self.render_label(label);
+        }
+    }
+
+    /// render the background and the knob with their outlines
+    fn render_elements(&self) {
+        let dirty = self.take_render_cache_dirty();
+        let transform = self.screen_transform();
+        let state = self.render_state();
+        if let Some(style) = &self.style {
+            let alpha = self.style_alpha(style);
+            style.draw_background(&self.background, &transform, alpha);
+            if style.border_width > 0. {
+                let color = Color {
+                    a: style.border_color.a * alpha,
+                    ..style.border_color
+                };
+                self.draw_outline(
+                    self.center,
+                    self.background.radius,
+                    style.border_width,
+                    color,
+                );
+            }
+        } else if self.render_cache {
+            self.background.render_cached(&transform, dirty, state);
+        } else {
+            self.background.render(&transform, state);
+        }
+        if let Some(hover_color) = self.hover_color {
+            if self.hover_fade > 0. {
+                let color = Color {
+                    a: hover_color.a * self.hover_fade * self.opacity(),
+                    ..hover_color
+                };
+                self.draw_outline(self.center, self.background.radius, 2., color);
+            }
+        }
+        // the knob element is at the knob, so move it to the rendered knob
+        let spring = self.rendered_knob_offset() - self.knob_offset();
+        let knob_transform = transform * Mat3::from_translation(spring);
+        if let Some(style) = &self.style {
+            let alpha = self.style_alpha(style);
+            style.draw_knob(&self.knob, &knob_transform, alpha);
+        } else if self.render_cache {
+            self.knob.render_cached(&knob_transform, dirty, state);
+        } else {
+            self.knob.render(&knob_transform, state);
+        }
+        if self.locked {
+            let knob = self.center + self.rendered_knob_offset();
+            self.draw_outline(knob, self.knob.radius, 2., self.faded(self.lock_color));
+        }
+    }
+
+    /// the color with the alpha multiplied by the current opacity, see [`Joystick::opacity`]
+    fn faded(&self, color: Color) -> Color {
+        Color {
+            a: color.a * self.opacity(),
+            ..color
+        }
+    }
+
+    /// the state which is passed to the custom renderers
+    ///
+    /// see [`Joystick::from_custom_renderers`]
+    pub fn render_state(&self) -> JoystickRenderState {
+        JoystickRenderState {
+            active: self.is_active(),
+            intensity: self.output.intensity,
+            angle: self.output.angle,
+            direction: self.output.direction,
+            opacity: self.opacity(),
+        }
+    }
+
+    /// the alpha of the style in the current state
+    fn style_alpha(&self, style: &JoystickStyle) -> f32 {
+        if self.is_active() {
+            style.active_alpha
+        } else {
+            style.idle_alpha * self.opacity()
+        }
+    }
+
+    /// render the direction sectors on top of the joystick, e.g. for tuning
+    ///
+    /// This draws the boundaries between the directions from the dead zone (see
+    /// [`Joystick::set_dead_zone`]) to the rim, the circle of the dead zone, the circle the
+    /// knob travels in and shades the sector of the reported direction.
+    /// The sectors are derived from the configuration which classifies the events
+    /// (e.g. [`Joystick::set_axis_scale`]), so they always agree with the reported directions.
+    /// Call it after [`Joystick::render`] (or after drawing custom elements).
+    pub fn render_sectors(&self) {
+        self.with_screen_camera(|| self.render_sectors_unchecked());
+    }
+
+    /// render the direction sectors with the active camera
+    fn render_sectors_unchecked(&self) {
+        /// the number of quads to shade the active sector
+        const FILL_STEPS: usize = 8;
+
+        let radius = self.travel_radius;
+        let inner_radius = radius * self.dead_zone;
+        let transform = self.screen_transform();
+        let point = |angle: f32, distance: f32| {
+            transform.transform_point2(self.center + self.sector_direction(angle) * distance)
+        };
+        let sector_angle = self.resolver.sector_angle();
+        if !self.output.is_idle() {
+            // the reported sector, e.g. after the hysteresis or the mirroring of the output
+            let start = self.resolver.sector_center(self.output.sector) - sector_angle / 2.;
+            for step in 0..FILL_STEPS {
+                let from = start + sector_angle * step as f32 / FILL_STEPS as f32;
+                let to = start + sector_angle * (step + 1) as f32 / FILL_STEPS as f32;
+                let inner_from = point(from, inner_radius);
+                let inner_to = point(to, inner_radius);
+                let outer_to = point(to, radius);
+                draw_triangle(
+                    inner_from,
+                    point(from, radius),
+                    outer_to,
+                    ACTIVE_SECTOR_COLOR,
+                );
+                if inner_radius > 0. {
+                    draw_triangle(inner_from, outer_to, inner_to, ACTIVE_SECTOR_COLOR);
+                }
+            }
+        }
+        for sector in 0..self.resolver.sectors() {
+            let angle = self.resolver.sector_center(sector) + sector_angle / 2.;
+            let from = point(angle, inner_radius);
+            let to = point(angle, radius);
+            draw_line(from.x, from.y, to.x, to.y, 1., SECTOR_LINE_COLOR);
+        }
+        if inner_radius > 0. {
+            self.draw_outline(self.center, inner_radius, 1., SECTOR_LINE_COLOR);
+        }
+        self.draw_outline(self.center, radius, 1., SECTOR_LINE_COLOR);
+    }
+
+    /// the direction of the knob offset which results in the angle of an event
+    fn sector_direction(&self, angle: f32) -> Vec2 {
+        (Vec2::new(angle.cos(), angle.sin()) / self.axis_scale).normalize()
+    }
+
+    /// draw a circle outline with the transform of the joystick
+    fn draw_outline(&self, center: Vec2, radius: f32, thickness: f32, color: Color) {
+        let transform = self.screen_transform();
+        let center = transform.transform_point2(center);
+        let scale = transform.determinant().abs().sqrt();
+        draw_circle_lines(center.x, center.y, radius * scale, thickness * scale, color);
+    }
+
+    /// the event of the last update
+    ///
+    /// this is the same event which was returned by the last update
+    pub fn event(&self) -> JoystickEvent {
+        self.output
+    }
+
+    /// whether the joystick is dragged
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// whether the joystick is dragged or its direction is locked
+    ///
+    /// this can be used by custom elements to render the joystick differently while idle,
+    /// see also [`Joystick::set_idle_appearance`]
+    pub fn is_active(&self) -> bool {
+        self.dragging || self.locked || self.external_active
+    }
+
+    /// set the appearance of the joystick while it is idle
+    ///
+    /// The joystick still accepts input while it is hidden or only drawn as an outline.
+    ///
+    /// Defaults to [`IdleAppearance::Full`].
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::{IdleAppearance, Joystick};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_idle_appearance(IdleAppearance::Hidden);
+    /// ```
+    pub fn set_idle_appearance(&mut self, idle_appearance: IdleAppearance) {
+        self.idle_appearance = idle_appearance;
+    }
+
+    /// render the label centered under the background
+    fn render_label(&self, label: &JoystickLabel) {
+        let transform = self.screen_transform();
+        let scale = transform.determinant().abs().sqrt();
+        let offset = if self.mirrored {
+            Vec2::new(-label.offset.x, label.offset.y)
+        } else {
+            label.offset
+        };
+        let anchor = Vec2::new(self.center.x, self.center.y + self.background.radius) + offset;
+        let anchor = transform.transform_point2(anchor);
+        let font_size = label.font_size.round() as u16;
+        let dimensions = measure_text(&label.text, label.font, font_size, scale);
+        draw_text_ex(
+            &label.text,
+            anchor.x - dimensions.width / 2.,
+            anchor.y + dimensions.offset_y,
+            TextParams {
+                font: label.font.unwrap_or_default(),
+                font_size,
+                font_scale: scale,
+                color: self.faded(label.color),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// set the label which is drawn under the joystick
+    ///
+    /// The label is centered horizontally under the background and moves with the joystick.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::{Joystick, JoystickLabel};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_label(Some(JoystickLabel::new("MOVE")));
+    /// ```
+    pub fn set_label(&mut self, label: Option<JoystickLabel>) {
+        self.label = label;
+    }
+
+    /// set the turbo of the intensity
+    ///
+    /// While the intensity is at least [`Turbo::threshold`], the reported intensity keeps
+    /// increasing by [`Turbo::rate_per_sec`] up to [`Turbo::max`]. The increase above the
+    /// normal intensity is also reported as [`JoystickEvent::charge`].
+    /// When the intensity drops below the threshold, the normal intensity is reported again.
+    ///
+    /// This doesn't change the rendering of the knob.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput, Turbo};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_turbo(Some(Turbo {
+    ///     threshold: 0.9,
+    ///     rate_per_sec: 1.0,
+    ///     max: 2.0,
+    /// }));
+    ///
+    /// // hold the knob at the edge
+    /// let edge = SimulatedInput::new().with_mouse(Vec2::new(124.0, 200.0), true);
+    /// let event = joystick.update_with_input(&edge, 0.0);
+    /// assert_eq!(event.intensity, 0.96);
+    /// let event = joystick.update_with_input(&edge, 0.5);
+    /// assert_eq!(event.intensity, 1.46);
+    /// assert_eq!(event.charge, 0.5);
+    /// // the intensity is capped
+    /// let event = joystick.update_with_input(&edge, 1.0);
+    /// assert_eq!(event.intensity, 2.0);
+    ///
+    /// // easing off resets the intensity
+    /// let half = SimulatedInput::new().with_mouse(Vec2::new(112.5, 200.0), true);
+    /// let event = joystick.update_with_input(&half, 0.1);
+    /// assert_eq!(event.intensity, 0.5);
+    /// assert_eq!(event.charge, 0.0);
+    /// ```
+    pub fn set_turbo(&mut self, turbo: Option<Turbo>) {
+        self.turbo = turbo;
+        if turbo.is_none() {
+            self.turbo_charge = 0.;
+        }
+    }
+
+    /// increase the intensity while it is above the threshold of the turbo
+    fn apply_turbo(&mut self, event: &mut JoystickEvent, dt: f32) {
+        let turbo = match self.turbo {
+            Some(turbo) => turbo,
+            None => return,
+        };
+        let raw_intensity = event.intensity;
+        // an idle event stays idle
+        if self.dragging && raw_intensity > 0. && raw_intensity >= turbo.threshold {
+            self.turbo_charge = (self.turbo_charge + turbo.rate_per_sec * dt)
+                .min(turbo.max - raw_intensity)
+                .max(0.);
+        } else {
+            self.turbo_charge = 0.;
+        }
+        event.intensity = raw_intensity + self.turbo_charge;
+        event.charge = self.turbo_charge;
+    }
+
+    /// set the identifier of the joystick
+    ///
+    /// the identifier is included in the [`Debug`] output and passed to the tracer
+    /// (see [`Joystick::set_tracer`]), which helps to distinguish several joysticks in logs
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::Joystick;
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_id("move");
+    /// assert_eq!(joystick.id(), Some("move"));
+    /// assert!(format!("{:?}", joystick).contains("move"));
+    /// ```
+    pub fn set_id(&mut self, id: impl Into<String>) {
+        self.id = Some(id.into());
+    }
+
+    /// get the identifier of the joystick
+    ///
+    /// see [`Joystick::set_id`]
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// set the tracer of the joystick
+    ///
+    /// The tracer is called with the identifier of the joystick (see [`Joystick::set_id`],
+    /// empty if not set) and the current event when the joystick is grabbed, released
+    /// or the direction changes.
+    /// This can be used for logging or analytics.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_id("move");
+    /// let log = Rc::new(RefCell::new(Vec::new()));
+    /// let tracer_log = Rc::clone(&log);
+    /// joystick.set_tracer(Some(Box::new(move |id: &str, event| {
+    ///     tracer_log.borrow_mut().push((id.to_string(), event.direction));
+    /// })));
+    ///
+    /// for x in [110.0, 112.0, 100.0] {
+    ///     let input = SimulatedInput::new().with_mouse(Vec2::new(x, 210.0), true);
+    ///     joystick.update_with_input(&input, 0.0);
+    /// }
+    /// joystick.update_with_input(&SimulatedInput::new(), 0.0);
+    ///
+    /// let log = log.borrow();
+    /// assert_eq!(log.len(), 3);
+    /// assert_eq!(log[0], ("move".to_string(), JoystickDirection::DownRight));
+    /// assert_eq!(log[1].1, JoystickDirection::Down);
+    /// assert_eq!(log[2].1, JoystickDirection::Idle);
+    /// ```
+    pub fn set_tracer(&mut self, tracer: Option<JoystickTracer>) {
+        self.tracer = tracer;
+    }
+
+    /// call the tracer if the state changed since the last update
+    fn trace(&mut self, was_dragging: bool, last_direction: JoystickDirection) {
+        if let Some(tracer) = &mut self.tracer {
+            if was_dragging != self.dragging || last_direction != self.output.direction {
+                tracer(self.id.as_deref().unwrap_or_default(), &self.output);
+            }
+        }
+    }
+
+    /// enable or disable the input of the joystick, e.g. in a pause menu
+    ///
+    /// Disabling the joystick drops its transient state like [`Joystick::clear`], so a drag
+    /// ends without reporting a release. While it's disabled, the update ignores the input and
+    /// returns the idle event. A touch or a mouse button which is still held when it's enabled
+    /// again is ignored until it is released.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), true);
+    /// joystick.update_with_input(&input, 0.0);
+    /// joystick.set_enabled(false);
+    /// assert!(!joystick.is_dragging());
+    /// assert!(joystick.update_with_input(&input, 0.0).is_idle());
+    ///
+    /// joystick.set_enabled(true);
+    /// assert!(joystick.update_with_input(&input, 0.0).is_idle());
+    /// ```
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if !enabled && self.enabled {
+            self.clear();
+        }
+        self.enabled = enabled;
+    }
+
+    /// whether the input of the joystick is enabled, see [`Joystick::set_enabled`]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// show or hide the joystick
+    ///
+    /// A hidden joystick still accepts input, disable it with [`Joystick::set_enabled`]
+    /// to ignore the input too.
+    ///
+    /// Defaults to `true`.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// whether the joystick is rendered, see [`Joystick::set_visible`]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// fade the joystick out after it wasn't used for a while
+    ///
+    /// The joystick fades in [`AutoFade::duration`] seconds to [`AutoFade::min_opacity`] once
+    /// [`Joystick::seconds_since_last_activity`] exceeds [`AutoFade::delay`], and it reappears
+    /// at full opacity when it's grabbed. The opacity applies to the background, the knob and
+    /// the idle outline and is passed to custom renderers in
+    /// [`JoystickRenderState::opacity`].
+    ///
+    /// Defaults to `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{AutoFade, Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_auto_fade(Some(AutoFade {
+    ///     delay: 2.0,
+    ///     duration: 1.0,
+    ///     min_opacity: 0.2,
+    /// }));
+    /// joystick.update_with_input(&SimulatedInput::new(), 2.5);
+    /// assert!((joystick.opacity() - 0.6).abs() < 1e-6);
+    /// joystick.update_with_input(&SimulatedInput::new(), 1.0);
+    /// assert!((joystick.opacity() - 0.2).abs() < 1e-6);
+    ///
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), true);
+    /// joystick.update_with_input(&input, 0.1);
+    /// assert_eq!(joystick.opacity(), 1.0);
+    /// ```
+    pub fn set_auto_fade(&mut self, auto_fade: Option<AutoFade>) {
+        self.auto_fade = auto_fade;
+    }
+
+    /// the current opacity of the joystick, see [`Joystick::set_auto_fade`]
+    pub fn opacity(&self) -> f32 {
+        let fade = match self.auto_fade {
+            Some(fade) if !self.is_active() => fade,
+            _ => return 1.,
+        };
+        let progress = if fade.duration > 0. {
+            (self.idle_time - fade.delay) / fade.duration
+        } else if self.idle_time >= fade.delay {
+            1.
+        } else {
+            0.
+        };
+        let min_opacity = fade.min_opacity.clamp(0., 1.);
+        1. - (1. - min_opacity) * progress.clamp(0., 1.)
+    }
+
+    /// set the receiver of the feedback hooks, e.g. for haptic or audio feedback
+    ///
+    /// see [`JoystickFeedback`]
+    ///
+    /// Defaults to `None`.
+    pub fn set_feedback(&mut self, feedback: Option<Box<dyn JoystickFeedback>>) {
+        self.feedback = feedback;
+    }
+
+    /// call the event callbacks and the feedback hooks for the output of the update
+    fn notify_feedback(&mut self, last_direction: JoystickDirection) {
+        let at_edge = self.output.intensity >= 1.;
+        let reached_edge = at_edge && !self.at_edge;
+        self.at_edge = at_edge;
+        let event = &self.output;
+        // the callbacks are a receiver of the hooks too
+        notify(&mut self.callbacks, event, last_direction, reached_edge);
+        if let Some(feedback) = &mut self.feedback {
+            notify(feedback.as_mut(), event, last_direction, reached_edge);
+        }
+    }
+
+    /// call the callback when the joystick is grabbed
+    ///
+    /// The callbacks are called at the end of the update with its event, in the order
+    /// press, drag, release. A tap which is grabbed and released in the same update calls
+    /// the callbacks of the press and the release. Each callback replaces the previous one.
+    ///
+    /// This is a shortcut for [`JoystickFeedback::on_start`], the callbacks are called before
+    /// the hooks of [`Joystick::set_feedback`]. See [`JoystickPhase`] and [`JoystickFeedback`]
+    /// for more hooks.
+    pub fn on_press(&mut self, callback: EventCallback) {
+        self.callbacks.press = Some(callback);
+    }
+
+    /// call the callback in each update in which the joystick is dragged after the press
+    ///
+    /// see [`Joystick::on_press`]
+    pub fn on_drag(&mut self, callback: EventCallback) {
+        self.callbacks.drag = Some(callback);
+    }
+
+    /// call the callback when the joystick is released, e.g. to dash
+    ///
+    /// The event is the one of the update with [`JoystickPhase::Release`], which is already
+    /// idle, so the direction of the drag has to be kept from [`Joystick::on_drag`] (or taken
+    /// from [`JoystickEvent::flick`] for a swipe). See [`Joystick::on_press`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    ///
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let heading = Rc::new(Cell::new(JoystickDirection::Idle));
+    /// let dash = Rc::new(Cell::new(None));
+    ///
+    /// let drag_heading = Rc::clone(&heading);
+    /// joystick.on_drag(Box::new(move |event| drag_heading.set(event.direction)));
+    /// let (release_heading, release_dash) = (Rc::clone(&heading), Rc::clone(&dash));
+    /// joystick.on_release(Box::new(move |_| release_dash.set(Some(release_heading.get()))));
+    ///
+    /// for (x, down) in [(110.0, true), (120.0, true), (120.0, false)] {
+    ///     let input = SimulatedInput::new().with_mouse(Vec2::new(x, 200.0), down);
+    ///     joystick.update_with_input(&input, 0.0);
+    /// }
+    /// assert_eq!(dash.get(), Some(JoystickDirection::Right));
+    /// ```
+    pub fn on_release(&mut self, callback: EventCallback) {
+        self.callbacks.release = Some(callback);
+    }
+
+    /// remove the callbacks of [`Joystick::on_press`], [`Joystick::on_drag`] and
+    /// [`Joystick::on_release`]
+    pub fn clear_event_callbacks(&mut self) {
+        self.callbacks = EventCallbacks::default();
+    }
+
+    /// the seconds since the joystick was last dragged or tapped
+    ///
+    /// The time is accumulated from the `dt` of the updates and starts at `0.0` when the
+    /// joystick is created. It saturates instead of overflowing.
+    pub fn seconds_since_last_activity(&self) -> f32 {
+        self.idle_time
+    }
+
+    /// call the callback once the joystick wasn't used for `secs` seconds
+    ///
+    /// The callback is called once when the threshold is crossed and again after the next
+    /// activity was followed by another `secs` seconds without activity.
+    /// It replaces the previous callback.
+    ///
+    /// see [`Joystick::seconds_since_last_activity`]
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    ///
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let dimmed = Rc::new(Cell::new(0));
+    /// let callback_dimmed = Rc::clone(&dimmed);
+    /// joystick.on_idle_timeout(5.0, Box::new(move || callback_dimmed.set(callback_dimmed.get() + 1)));
+    ///
+    /// for _ in 0..10 {
+    ///     joystick.update_with_input(&SimulatedInput::new(), 1.0);
+    /// }
+    /// assert_eq!(dimmed.get(), 1);
+    /// ```
+    pub fn on_idle_timeout(&mut self, secs: f32, callback: Box<dyn FnMut()>) {
+        self.idle_timeout = Some(IdleTimeout {
+            secs,
+            callback,
+            fired: self.idle_time >= secs,
+        });
+    }
+
+    /// remove the callback of [`Joystick::on_idle_timeout`]
+    pub fn clear_idle_timeout(&mut self) {
+        self.idle_timeout = None;
+    }
+
+    /// advance the idle time and call the idle timeout if the threshold was crossed
+    fn update_idle_time(&mut self, active: bool, dt: f32) {
+        if active {
+            self.idle_time = 0.;
+            if let Some(timeout) = &mut self.idle_timeout {
+                timeout.fired = false;
+            }
+            return;
+        }
+        self.idle_time = (self.idle_time + dt.max(0.)).min(f32::MAX);
+        if let Some(timeout) = &mut self.idle_timeout {
+            if !timeout.fired && self.idle_time >= timeout.secs {
+                timeout.fired = true;
+                (timeout.callback)();
+            }
+        }
+    }
+
+    /// enable or disable the collection of usage statistics
+    ///
+    /// The statistics count where the knob is held, how long and how often the joystick is
+    /// dragged and how long it's held at the rim, e.g. for playtesting.
+    /// Enabling them keeps the collected statistics, disabling them drops them.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_stats(true);
+    ///
+    /// let press = SimulatedInput::new().with_mouse(Vec2::new(100.0, 200.0), true);
+    /// joystick.update_with_input(&press, 0.5);
+    /// let edge = SimulatedInput::new().with_mouse(Vec2::new(130.0, 200.0), true);
+    /// joystick.update_with_input(&edge, 0.5);
+    ///
+    /// let stats = joystick.stats().unwrap();
+    /// assert_eq!(stats.grabs, 1);
+    /// assert_eq!(stats.drag_time, 1.0);
+    /// assert_eq!(stats.edge_time, 0.5);
+    /// // held in the center and at the right edge
+    /// assert_eq!(stats.histogram[8][8], 1);
+    /// assert_eq!(stats.histogram[8][15], 1);
+    /// ```
+    pub fn set_stats(&mut self, stats: bool) {
+        if !stats {
+            self.stats = None;
+        } else if self.stats.is_none() {
+            self.stats = Some(Box::default());
+        }
+    }
+
+    /// the collected usage statistics
+    ///
+    /// returns `None` if the statistics are disabled, see [`Joystick::set_stats`]
+    pub fn stats(&self) -> Option<JoystickStats> {
+        self.stats.as_deref().copied()
+    }
+
+    /// reset the collected usage statistics
+    ///
+    /// see [`Joystick::set_stats`]
+    pub fn reset_stats(&mut self) {
+        if let Some(stats) = &mut self.stats {
+            **stats = JoystickStats::default();
+        }
+    }
+
+    /// the rendered diameter of the background
+    ///
+    /// see [`Joystick::hit_radius`] and [`Joystick::travel_radius`]
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    /// the radius in which a press grabs the joystick, before the grab forgiveness
+    ///
+    /// see [`Joystick::set_hit_radius`]
+    pub fn hit_radius(&self) -> f32 {
+        self.hit_radius
+    }
+
+    /// set the radius in which a press grabs the joystick
+    ///
+    /// The size of the joystick is only used for rendering, the hit testing uses this radius
+    /// (multiplied by [`Joystick::set_grab_forgiveness`]) and the knob is clamped to the
+    /// travel radius (see [`Joystick::set_travel_radius`]). This allows a custom background
+    /// whose shape doesn't fill its bounding circle, e.g. a hexagon.
+    ///
+    /// Defaults to half of the size. Radii which are smaller than half a pixel (including NaN)
+    /// are clamped to half a pixel.
+    pub fn set_hit_radius(&mut self, hit_radius: f32) {
+        self.hit_radius = hit_radius.max(MIN_SIZE / 2.);
+    }
+
+    /// set a rectangle in which a press grabs the joystick in addition to the hit radius
+    ///
+    /// Unlike [`JoystickMode::Floating`], the joystick stays where it is, so a press far away
+    /// from the center starts with a high intensity. The rectangle is in the coordinates of the
+    /// joystick and is not changed by [`Joystick::set_position`] or [`Joystick::set_size`].
+    ///
+    /// Defaults to `None`. [`Joystick::validate`] reports a rectangle without an area.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{Rect, Vec2};
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// // the lower left corner of the screen
+    /// joystick.set_hit_rect(Some(Rect::new(0.0, 100.0, 250.0, 200.0)));
+    ///
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(200.0, 200.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::Right);
+    /// assert_eq!(event.intensity, 1.0);
+    /// ```
+    pub fn set_hit_rect(&mut self, hit_rect: Option<Rect>) {
+        self.hit_rect = hit_rect;
+    }
+
+    /// the rectangle in which a press grabs the joystick, see [`Joystick::set_hit_rect`]
+    pub fn hit_rect(&self) -> Option<Rect> {
+        self.hit_rect
+    }
+
+    /// the maximum distance of the knob from the center
+    ///
+    /// see [`Joystick::set_travel_radius`]
+    pub fn travel_radius(&self) -> f32 {
+        self.travel_radius
+    }
+
+    /// set the maximum distance of the knob from the center
+    ///
+    /// The knob is clamped to this radius and the intensity is relative to it,
+    /// independent of the rendered size and the hit radius (see [`Joystick::set_hit_radius`]).
+    ///
+    /// Defaults to half of the size. Radii which are smaller than half a pixel (including NaN)
+    /// are clamped to half a pixel.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_travel_radius(20.0);
+    ///
+    /// let press = SimulatedInput::new().with_mouse(Vec2::new(100.0, 200.0), true);
+    /// joystick.update_with_input(&press, 0.0);
+    /// let drag = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), true);
+    /// let event = joystick.update_with_input(&drag, 0.0);
+    /// assert_eq!(event.intensity, 0.5);
+    /// ```
+    pub fn set_travel_radius(&mut self, travel_radius: f32) {
+        self.travel_radius = travel_radius.max(MIN_SIZE / 2.);
+    }
+
+    /// set the forgiveness factor for grabbing the joystick
+    ///
+    /// A drag starts if the pointer is pressed within the hit radius (see
+    /// [`Joystick::set_hit_radius`]) multiplied by this factor, which makes it easier to hit
+    /// the joystick. After the joystick was grabbed, the knob is still clamped to the travel
+    /// radius and the intensity is relative to it.
+    ///
+    /// Defaults to `1.0`.
+    pub fn set_grab_forgiveness(&mut self, grab_forgiveness: f32) {
+        self.grab_forgiveness = grab_forgiveness;
+    }
+
+    /// set the scale of the axes
+    ///
+    /// The offset of the knob is scaled per axis before the angle, intensity and direction
+    /// are computed, so a scale of `(1.0, 0.6)` makes vertical drags report 60% of the
+    /// intensity and shifts the diagonal sectors towards the vertical axis.
+    ///
+    /// Defaults to `(1.0, 1.0)`, which doesn't change the event. Components which are smaller
+    /// than `0.01` (including NaN) are clamped to `0.01` (this panics in debug builds for
+    /// components which are not positive, use [`JoystickBuilder`] to handle it as an error).
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_axis_scale(Vec2::new(1.0, 0.6));
+    ///
+    /// let press = SimulatedInput::new().with_mouse(Vec2::new(100.0, 200.0), true);
+    /// joystick.update_with_input(&press, 0.0);
+    /// // drag straight up to the rim
+    /// let up = SimulatedInput::new().with_mouse(Vec2::new(100.0, 175.0), true);
+    /// let event = joystick.update_with_input(&up, 0.0);
+    /// assert!((event.intensity - 0.6).abs() < 1e-5);
+    /// ```
+    pub fn set_axis_scale(&mut self, axis_scale: Vec2) {
+        debug_assert!(
+            axis_scale.x > 0. && axis_scale.y > 0.,
+            "the components of the axis scale must be positive"
+        );
+        self.axis_scale = Vec2::new(
+            axis_scale.x.max(MIN_AXIS_SCALE),
+            axis_scale.y.max(MIN_AXIS_SCALE),
+        );
+    }
+
+    /// set the response curve of the intensity
+    ///
+    /// The curve is applied to the intensity after the dead zone and the saturation (see
+    /// [`Joystick::set_dead_zone`]) and before the response curves of the axes (see
+    /// [`Joystick::set_axis_response`]), so it doesn't change the angle. A curve which bends
+    /// downwards (e.g. [`ResponseCurve::Power`] with `2.0`) makes small drags finer, e.g. for
+    /// precise aiming.
+    ///
+    /// Defaults to [`ResponseCurve::Linear`].
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, ResponseCurve, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_response_curve(ResponseCurve::Power(2.0));
+    ///
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(100.0, 187.5), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(event.intensity, 0.25);
+    /// ```
+    pub fn set_response_curve(&mut self, curve: ResponseCurve) {
+        self.response_curve = curve;
+    }
+
+    /// apply the radial zones and the response curve to the intensity
+    fn apply_intensity_response(&self, intensity: f32) -> f32 {
+        let intensity = self.apply_radial_zones(intensity);
+        self.response_curve.apply(intensity).clamp(0., 1.)
+    }
+
+    /// set the response curves of the axes
+    ///
+    /// The curves are applied to the horizontal and vertical components of the knob offset
+    /// relative to the radius (from `-1.0` to `1.0`) after the axis scale (see
+    /// [`Joystick::set_axis_scale`]) and the clamping to the background.
+    /// The intensity, angle and direction of the event are computed from the adjusted offset,
+    /// with the intensity clamped to `1.0`.
+    ///
+    /// Defaults to [`ResponseCurve::Linear`] for both axes, which doesn't change the event.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, ResponseCurve, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// // gentle steering, linear throttle
+    /// joystick.set_axis_response(ResponseCurve::Power(2.0), ResponseCurve::Linear);
+    ///
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(112.5, 200.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(event.intensity, 0.25);
+    /// ```
+    pub fn set_axis_response(&mut self, x: ResponseCurve, y: ResponseCurve) {
+        self.axis_response = match (x, y) {
+            (ResponseCurve::Linear, ResponseCurve::Linear) => None,
+            curves => Some(curves),
+        };
+    }
+
+    /// apply the response curves of the axes to the intensity and angle of the event
+    fn apply_axis_response(&self, intensity: f32, angle: f32) -> (f32, f32) {
+        let (x, y) = match self.axis_response {
+            Some(curves) => curves,
+            None => return (intensity, angle),
+        };
+        let offset = Vec2::new(angle.cos(), angle.sin()) * intensity;
+        let offset = Vec2::new(x.apply(offset.x), y.apply(offset.y));
+        let angle = offset.y.atan2(offset.x);
+        // a curve can turn a small negative component into -0.0, keep the angle in (-PI, PI]
+        let angle = if angle <= -PI { PI } else { angle };
+        (offset.length().min(1.), angle)
+    }
+
+    /// set the axes on which the knob moves
+    ///
+    /// With [`JoystickAxis::Horizontal`] or [`JoystickAxis::Vertical`], the other component of
+    /// the drag is dropped before the event is computed, so the joystick behaves like a
+    /// slider: the knob only moves along the axis and the event only reports its two
+    /// directions.
+    ///
+    /// Defaults to [`JoystickAxis::Both`].
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickAxis, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_axis(JoystickAxis::Horizontal);
+    ///
+    /// let drag = SimulatedInput::new().with_mouse(Vec2::new(110.0, 190.0), true);
+    /// let event = joystick.update_with_input(&drag, 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::Right);
+    /// assert_eq!(event.intensity, 0.4);
+    /// ```
+    pub fn set_axis(&mut self, axis: JoystickAxis) {
+        self.axis = axis;
+    }
+
+    /// the axes on which the knob moves, see [`Joystick::set_axis`]
+    pub fn axis(&self) -> JoystickAxis {
+        self.axis
+    }
+
+    /// set the radius of the dead zone relative to the travel radius
+    ///
+    /// Drags inside of the dead zone report an intensity of `0.0` (and an idle event), so a
+    /// resting thumb doesn't make the character creep. Between the dead zone and the
+    /// saturation (see [`Joystick::set_saturation`]), the intensity is remapped linearly from
+    /// `0.0` to `1.0`. The zones are applied before the response curve of the intensity
+    /// (see [`Joystick::set_response_curve`]) and the response curves of the axes
+    /// (see [`Joystick::set_axis_response`]). The knob still follows the pointer.
+    ///
+    /// Defaults to `0.0`. [`Joystick::validate`] reports a dead zone which is not smaller than
+    /// the saturation.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_dead_zone(0.2);
+    /// joystick.set_saturation(0.8);
+    ///
+    /// let mut drag_to = |x: f32| {
+    ///     let input = SimulatedInput::new().with_mouse(Vec2::new(100.0 + x, 200.0), true);
+    ///     joystick.update_with_input(&input, 0.0).intensity
+    /// };
+    /// assert_eq!(drag_to(4.0), 0.0);
+    /// assert!((drag_to(12.5) - 0.5).abs() < 1e-5);
+    /// assert_eq!(drag_to(21.0), 1.0);
+    /// ```
+    pub fn set_dead_zone(&mut self, dead_zone: f32) {
+        self.dead_zone = dead_zone;
+    }
+
+    /// the radius of the dead zone, see [`Joystick::set_dead_zone`]
+    pub fn dead_zone(&self) -> f32 {
+        self.dead_zone
+    }
+
+    /// set the radius of the saturation relative to the travel radius
+    ///
+    /// Drags beyond the saturation report an intensity of `1.0`, so the full intensity can be
+    /// reached without dragging to the rim, see [`Joystick::set_dead_zone`].
+    ///
+    /// Defaults to `1.0`.
+    pub fn set_saturation(&mut self, saturation: f32) {
+        self.saturation = saturation;
+    }
+
+    /// the radius of the saturation, see [`Joystick::set_saturation`]
+    pub fn saturation(&self) -> f32 {
+        self.saturation
+    }
+
+    /// remap the intensity with the dead zone and the saturation
+    fn apply_radial_zones(&self, intensity: f32) -> f32 {
+        if intensity <= self.dead_zone {
+            0.
+        } else if intensity >= self.saturation {
+            1.
+        } else {
+            (intensity - self.dead_zone) / (self.saturation - self.dead_zone)
+        }
+    }
+
+    /// set whether the knob is rendered at the scaled offset
+    ///
+    /// see [`Joystick::set_axis_scale`]
+    ///
+    /// If disabled, the knob follows the pointer and only the event is scaled.
+    /// If enabled, the knob is drawn at the scaled offset, so it shows the reported intensity.
+    ///
+    /// Defaults to `false`.
+    pub fn set_scale_knob(&mut self, scale_knob: bool) {
+        self.scale_knob = scale_knob;
+    }
+
+    /// the radius in which a press grabs the joystick
+    fn grab_radius(&self) -> f32 {
+        self.hit_radius * self.grab_forgiveness
+    }
+
+    /// enable or disable the direction lock
+    ///
+    /// If enabled, releasing the joystick with an intensity of at least
+    /// [`Joystick::set_direction_lock_threshold`] latches the last event instead of resetting
+    /// the joystick, e.g. for auto-run. The joystick keeps reporting the latched event and the
+    /// knob stays at its position with an outline in the lock color until the joystick is
+    /// pressed again or [`Joystick::unlock`] is called.
+    ///
+    /// The lock takes precedence over all other actions on release:
+    /// a latched release is not reported as a release, so its phase is
+    /// [`JoystickPhase::Idle`] (or [`JoystickPhase::Start`] for a tap), it's not a flick and
+    /// the release callbacks are not called.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_direction_lock(true);
+    ///
+    /// // flick the knob up and release it
+    /// let up = Vec2::new(100.0, 176.0);
+    /// joystick.update_with_input(&SimulatedInput::new().with_mouse(up, true), 0.0);
+    /// let event = joystick.update_with_input(&SimulatedInput::new().with_mouse(up, false), 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::Up);
+    /// assert!(joystick.is_locked());
+    ///
+    /// joystick.unlock();
+    /// let event = joystick.update_with_input(&SimulatedInput::new(), 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::Idle);
+    /// ```
+    pub fn set_direction_lock(&mut self, direction_lock: bool) {
+        self.direction_lock = direction_lock;
+        if !direction_lock {
+            self.unlock();
+        }
+    }
+
+    /// set the minimum intensity to latch the direction on release
+    ///
+    /// see [`Joystick::set_direction_lock`]
+    ///
+    /// Defaults to `0.9`.
+    pub fn set_direction_lock_threshold(&mut self, threshold: f32) {
+        self.direction_lock_threshold = threshold;
+    }
+
+    /// make the knob stay where it was released, e.g. for the trim of a plane
+    ///
+    /// This is [`Joystick::set_direction_lock`] with a threshold of `0.0`, so every release
+    /// outside of the dead zone keeps the knob and the event until the joystick is pressed
+    /// again or [`Joystick::unlock`] is called. Disabling it restores the previous threshold.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_sticky(true);
+    ///
+    /// let trim = Vec2::new(105.0, 200.0);
+    /// joystick.update_with_input(&SimulatedInput::new().with_mouse(trim, true), 0.0);
+    /// joystick.update_with_input(&SimulatedInput::new().with_mouse(trim, false), 0.0);
+    /// let event = joystick.update_with_input(&SimulatedInput::new(), 0.0);
+    /// assert_eq!(event.intensity, 0.2);
+    /// assert_eq!(joystick.rendered_knob(), trim);
+    /// ```
+    pub fn set_sticky(&mut self, sticky: bool) {
+        if sticky {
+            if self.sticky_threshold.is_none() {
+                self.sticky_threshold = Some(self.direction_lock_threshold);
+            }
+            self.direction_lock_threshold = 0.;
+        } else if let Some(threshold) = self.sticky_threshold.take() {
+            self.direction_lock_threshold = threshold;
+        }
+        self.set_direction_lock(sticky);
+    }
+
+    /// set the color of the outline around the knob while the direction is locked
+    ///
+    /// see [`Joystick::set_direction_lock`]
+    pub fn set_lock_color(&mut self, lock_color: Color) {
+        self.lock_color = lock_color;
+    }
+
+    /// whether the direction is latched
+    ///
+    /// see [`Joystick::set_direction_lock`]
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// clear the latched direction and reset the joystick
+    ///
+    /// see [`Joystick::set_direction_lock`]
+    pub fn unlock(&mut self) {
+        if self.locked {
+            self.locked = false;
+            self.reset();
+        }
+    }
+
+    /// set the thresholds of the intensity bands
+    ///
+    /// The bands divide the intensity into named ranges, e.g. walk and run.
+    /// [`JoystickEvent::band`] is `0` while the joystick is idle, `1` below the first threshold,
+    /// `2` between the first and the second threshold and so on.
+    ///
+    /// # Arguments
+    /// * `thresholds`: the intensities at which the next band starts, in ascending order.
+    ///   Thresholds which are out of order are sorted (this panics in debug builds, use
+    ///   [`JoystickBuilder`] to handle it as an error).
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// // walk and run
+    /// joystick.set_bands(&[0.5]);
+    ///
+    /// assert_eq!(joystick.update_with_input(&SimulatedInput::new(), 0.0).band, 0);
+    /// let walk = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), true);
+    /// assert_eq!(joystick.update_with_input(&walk, 0.0).band, 1);
+    /// let run = SimulatedInput::new().with_mouse(Vec2::new(120.0, 200.0), true);
+    /// assert_eq!(joystick.update_with_input(&run, 0.0).band, 2);
+    /// ```
+    pub fn set_bands(&mut self, thresholds: &[f32]) {
+        debug_assert!(
+            thresholds.windows(2).all(|pair| pair[0] <= pair[1]),
+            "the thresholds of the bands must be in ascending order"
+        );
+        self.bands = thresholds.to_vec();
+        self.bands.sort_by(f32::total_cmp);
+    }
+
+    /// set the hysteresis of the intensity bands
+    ///
+    /// To move up a band, the intensity has to exceed the threshold plus the margin.
+    /// To move down a band, it has to drop below the threshold minus the margin.
+    /// This prevents flapping between two bands when the intensity is close to a threshold.
+    ///
+    /// Defaults to `0.0`.
+    pub fn set_band_hysteresis(&mut self, margin: f32) {
+        self.band_hysteresis = margin;
+    }
+
+    /// update the band of the event from its intensity
+    fn apply_band(&mut self, event: &mut JoystickEvent) {
+        let intensity = event.intensity;
+        let last_band = self.band;
+        let band = if event.is_idle() {
+            0
+        } else if last_band == 0 {
+            // the joystick was idle, so there is no band to stick to
+            1 + self.bands.iter().filter(|&&t| intensity >= t).count() as u8
+        } else {
+            let mut band = last_band;
+            let margin = self.band_hysteresis;
+            while let Some(&threshold) = self.bands.get(band as usize - 1) {
+                if intensity > threshold + margin {
+                    band += 1;
+                } else {
+                    break;
+                }
+            }
+            while band > 1 && intensity < self.bands[band as usize - 2] - margin {
+                band -= 1;
+            }
+            band
+        };
+        self.band = band;
+        event.band = band;
+    }
+
+    /// attach the joystick to a moving position
+    ///
+    /// At the start of each update, `center` is called to get the center of the joystick
+    /// in screen coordinates (before the transform of the joystick, see
+    /// [`Joystick::set_transform`], is inverted), e.g. the projected position of the player.
+    /// An active drag moves with the joystick, so the knob keeps its offset to the center.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let player = Rc::new(Cell::new(Vec2::new(300.0, 300.0)));
+    /// let attached_player = Rc::clone(&player);
+    /// joystick.attach_to(Box::new(move || attached_player.get()));
+    ///
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(310.0, 300.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(event.intensity, 0.4);
+    ///
+    /// // detaching restores the center from before
+    /// joystick.detach();
+    /// assert_eq!(joystick.center(), Vec2::new(100.0, 200.0));
+    /// ```
+    pub fn attach_to(&mut self, center: Box<dyn Fn() -> Vec2>) {
+        if self.attachment.is_none() {
+            self.static_center = self.center;
+        }
+        self.attachment = Some(center);
+    }
+
+    /// anchor the joystick to a corner or an edge of the screen
+    ///
+    /// this is [`Joystick::anchored`] for an existing joystick, e.g. one from a
+    /// [`JoystickBuilder`]
+    pub fn set_anchor(&mut self, anchor: Anchor, offset: Vec2) {
+        self.attach_to(Box::new(move || {
+            anchor.position(offset, Vec2::new(screen_width(), screen_height()))
+        }));
+    }
+
+    /// detach the joystick from the moving position
+    ///
+    /// the joystick moves back to its center from before [`Joystick::attach_to`]
+    pub fn detach(&mut self) {
+        if self.attachment.take().is_some() {
+            self.move_center(self.static_center);
+        }
+    }
+
+    /// the center of the joystick
+    pub fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    /// move the joystick to a new center
+    ///
+    /// A drag continues with the same offset of the knob. The new center is also the home of a
+    /// floating joystick (see [`Joystick::set_mode`]). An attached joystick
+    /// (see [`Joystick::attach_to`]) is moved back to its provider in the next update.
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        let center = Vec2::new(x, y);
+        self.static_center = center;
+        self.press_position += center - self.center;
+        self.drag_position += center - self.center;
+        self.smoothing_target += center - self.center;
+        self.move_center(center);
+    }
+
+    /// change the diameter of the joystick
+    ///
+    /// The knob, the hit radius and the travel radius (see [`Joystick::set_hit_radius`]) are
+    /// scaled by the same factor, as well as the offset of the knob, so a drag keeps its
+    /// intensity. Sizes which are below one pixel (including NaN) are clamped to one pixel.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::Joystick;
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_hit_radius(30.0);
+    /// joystick.set_size(100.0);
+    /// assert_eq!(joystick.size(), 100.0);
+    /// assert_eq!(joystick.hit_radius(), 60.0);
+    /// assert_eq!(joystick.travel_radius(), 50.0);
+    /// ```
+    pub fn set_size(&mut self, size: f32) {
+        let size = sanitize_size(size);
+        let scale = size / self.size;
+        let knob_offset = self.knob_offset() * scale;
+        self.size = size;
+        self.background.radius *= scale;
+        self.knob.radius *= scale;
+        self.hit_radius *= scale;
+        self.travel_radius *= scale;
+        self.knob.x = self.center.x + knob_offset.x;
+        self.knob.y = self.center.y + knob_offset.y;
+        self.spring_offset *= scale;
+        self.spring_velocity *= scale;
+        self.press_position = self.center + (self.press_position - self.center) * scale;
+        self.drag_position = self.center + (self.drag_position - self.center) * scale;
+        self.smoothing_target = self.center + (self.smoothing_target - self.center) * scale;
+        self.return_from *= scale;
+        self.render_cache_dirty.set(true);
+    }
+
+    /// the diameter of the knob
+    pub fn knob_size(&self) -> f32 {
+        self.knob.radius * 2.
+    }
+
+    /// change the diameter of the knob
+    ///
+    /// The size is clamped to the size of the joystick, sizes below one pixel (including NaN)
+    /// are clamped to one pixel. The travel radius and a drag are not affected.
+    /// The [`JoystickStyle::knob_scale`] of the style is updated to the new size.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::Joystick;
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// assert_eq!(joystick.knob_size(), 25.0);
+    /// joystick.set_knob_size(20.0);
+    /// assert_eq!(joystick.knob_size(), 20.0);
+    /// joystick.set_knob_size(80.0);
+    /// assert_eq!(joystick.knob_size(), 50.0);
+    /// ```
+    pub fn set_knob_size(&mut self, knob_size: f32) {
+        let knob_size = sanitize_size(knob_size).min(self.size);
+        self.knob.radius = knob_size / 2.;
+        if let Some(style) = &mut self.style {
+            style.knob_scale = knob_size / self.size;
+        }
+        self.render_cache_dirty.set(true);
+    }
+
+    /// move the center of the joystick and keep the offset of the knob
+    fn move_center(&mut self, center: Vec2) {
+        let offset = center - self.center;
+        self.center = center;
+        self.background.x = center.x;
+        self.background.y = center.y;
+        self.knob.x += offset.x;
+        self.knob.y += offset.y;
+    }
+
+    /// set the spring of the rendered knob
+    ///
+    /// With a spring, the rendered knob follows the drag with a small springy overshoot
+    /// instead of jumping, e.g. when the drag reverses quickly. This is purely visual:
+    /// the events are not affected. The rendered knob never leaves the travel radius.
+    ///
+    /// Defaults to `None`.
+    pub fn set_knob_spring(&mut self, knob_spring: Option<Spring>) {
+        self.knob_spring = knob_spring;
+        self.spring_offset = self.knob_offset();
+        self.spring_velocity = Vec2::ZERO;
+    }
+
+    /// the offset of the knob from the center
+    fn knob_offset(&self) -> Vec2 {
+        Vec2::new(self.knob.x, self.knob.y) - self.center
+    }
+
+    /// set the duration (in seconds) of the animation which moves the released knob back
+    ///
+    /// The rendered knob eases out towards the center instead of jumping back, with the curve
+    /// of [`Joystick::set_return_easing`]. This is purely visual: the event is idle from the
+    /// release on. A new drag stops the animation.
+    ///
+    /// Defaults to `0.0`, which moves the knob back immediately.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_return_duration(0.2);
+    /// joystick.update_with_input(&SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), true), 0.0);
+    /// joystick.update_with_input(&SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), false), 0.0);
+    /// assert!(joystick.event().is_idle());
+    /// // the knob is still on its way back
+    /// assert_eq!(joystick.rendered_knob(), Vec2::new(110.0, 200.0));
+    /// joystick.update_with_input(&SimulatedInput::new(), 0.1);
+    /// assert!((joystick.rendered_knob().x - 101.25).abs() < 1e-4);
+    /// joystick.update_with_input(&SimulatedInput::new(), 0.1);
+    /// assert_eq!(joystick.rendered_knob(), joystick.center());
+    /// ```
+    pub fn set_return_duration(&mut self, duration: f32) {
+        self.return_duration = duration.max(0.);
+        self.return_elapsed = self.return_elapsed.min(self.return_duration);
+    }
+
+    /// set the curve of the return animation, see [`Joystick::set_return_duration`]
+    ///
+    /// Defaults to [`Easing::CubicOut`].
+    pub fn set_return_easing(&mut self, easing: Easing) {
+        self.return_easing = easing;
+    }
+
+    /// set the smoothing of the drag
+    ///
+    /// The knob follows the pointer with a low-pass filter which damps jittery touch input
+    /// before it reaches the event. `alpha` is the fraction of the remaining distance which
+    /// the knob covers in 1/60 seconds, scaled to the duration of the update. The press
+    /// itself is not smoothed.
+    ///
+    /// Defaults to `None`, which is the same as an `alpha` of `1.0`. An `alpha` above `1.0` is
+    /// clamped to `1.0` and an `alpha` which is not positive (including NaN) is ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{TouchPhase, Vec2};
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_smoothing(Some(0.5));
+    /// let press = SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(100.0, 200.0));
+    /// joystick.update_with_input(&press, 1.0 / 60.0);
+    /// let drag = SimulatedInput::new().with_touch(0, TouchPhase::Moved, Vec2::new(120.0, 200.0));
+    /// let event = joystick.update_with_input(&drag, 1.0 / 60.0);
+    /// assert!((event.intensity - 0.4).abs() < 1e-5);
+    /// ```
+    pub fn set_smoothing(&mut self, alpha: Option<f32>) {
+        if alpha.is_some_and(|alpha| alpha.is_nan() || alpha <= 0.) {
+            return;
+        }
+        self.smoothing = alpha.map(|alpha| alpha.min(1.));
+        self.smoothing_target = self.drag_position;
+    }
+
+    /// the position of the pointer which drags the joystick, before the smoothing
+    ///
+    /// The position is in the coordinates of the joystick (see [`Joystick::set_transform`]).
+    /// `None` if the joystick is not dragged.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_smoothing(Some(0.5));
+    /// joystick.update_with_input(&SimulatedInput::new().with_mouse(Vec2::new(100.0, 200.0), true), 1.0 / 60.0);
+    /// joystick.update_with_input(&SimulatedInput::new().with_mouse(Vec2::new(120.0, 200.0), true), 1.0 / 60.0);
+    /// assert_eq!(joystick.raw_position(), Some(Vec2::new(120.0, 200.0)));
+    /// assert_eq!(joystick.smoothed_position(), Some(Vec2::new(110.0, 200.0)));
+    /// ```
+    pub fn raw_position(&self) -> Option<Vec2> {
+        if !self.dragging {
+            None
+        } else if self.smoothing.is_some() {
+            Some(self.smoothing_target)
+        } else {
+            Some(self.drag_position)
+        }
+    }
+
+    /// the position of the pointer after the smoothing, from which the event is computed
+    ///
+    /// this is the same as [`Joystick::raw_position`] without smoothing
+    pub fn smoothed_position(&self) -> Option<Vec2> {
+        if self.dragging {
+            Some(self.drag_position)
+        } else {
+            None
+        }
+    }
+
+    /// move the smoothed drag position towards the pointer
+    fn update_smoothing(&mut self, dt: f32) {
+        let alpha = match self.smoothing {
+            Some(alpha) => alpha,
+            None => return,
+        };
+        let factor = 1. - (1. - alpha).powf(dt.max(0.) * 60.);
+        self.moving(self.drag_position.lerp(self.smoothing_target, factor));
+    }
+
+    /// start or advance the return animation of the knob
+    fn update_knob_return(&mut self, was_active: bool, rendered_offset: Vec2, dt: f32) {
+        if self.dragging || self.external_active || self.locked {
+            self.return_elapsed = self.return_duration;
+            return;
+        }
+        if was_active {
+            self.return_from = rendered_offset;
+            self.return_elapsed = 0.;
+        } else {
+            self.return_elapsed = (self.return_elapsed + dt.max(0.)).min(self.return_duration);
+        }
+        match self.returning_offset() {
+            Some(offset) if self.knob_spring.is_some() => {
+                // continue with the spring where the animation is
+                self.spring_offset = offset;
+                self.spring_velocity = Vec2::ZERO;
+            }
+            _ => {}
+        }
+    }
+
+    /// the offset of the knob during the return animation
+    fn returning_offset(&self) -> Option<Vec2> {
+        if self.return_elapsed >= self.return_duration
+            || self.dragging
+            || self.external_active
+            || self.locked
+        {
+            return None;
+        }
+        let progress = self
+            .return_easing
+            .progress(self.return_elapsed / self.return_duration);
+        Some(self.return_from * (1. - progress))
+    }
+
+    /// the center of the knob as it is rendered
+    ///
+    /// this includes the spring (see [`Joystick::set_knob_spring`]) and the return animation
+    /// (see [`Joystick::set_return_duration`])
+    pub fn rendered_knob(&self) -> Vec2 {
+        self.center + self.rendered_knob_offset()
+    }
+
+    /// the offset of the rendered knob from the center
+    fn rendered_knob_offset(&self) -> Vec2 {
+        if let Some(offset) = self.returning_offset() {
+            offset
+        } else if self.knob_spring.is_some() {
+            self.spring_offset
+        } else {
+            self.knob_offset()
+        }
+    }
+
+    /// move the rendered knob towards the knob
+    fn update_knob_spring(&mut self, dt: f32) {
+        let spring = match self.knob_spring {
+            Some(spring) => spring,
+            None => return,
+        };
+        let target = self.knob_offset();
+        let radius = self.travel_radius;
+        spring.integrate(dt, |step| {
+            let acceleration = (target - self.spring_offset) * spring.stiffness
+                - self.spring_velocity * spring.damping;
+            self.spring_velocity += acceleration * step;
+            self.spring_offset += self.spring_velocity * step;
+            if self.spring_offset.length() > radius {
+                self.spring_offset = self.spring_offset.clamp_length_max(radius);
+                // drop the outward velocity so the knob doesn't stick to the rim
+                let normal = self.spring_offset / radius;
+                let outward = self.spring_velocity.dot(normal);
+                if outward > 0. {
+                    self.spring_velocity -= normal * outward;
+                }
+            }
+        });
+        let settled = (target - self.spring_offset).length() < SPRING_REST_DISTANCE
+            && self.spring_velocity.length() < SPRING_REST_DISTANCE;
+        if settled || !self.spring_offset.is_finite() || !self.spring_velocity.is_finite() {
+            self.spring_offset = target;
+            self.spring_velocity = Vec2::ZERO;
+        }
+    }
+
+    /// set the flick detection
+    ///
+    /// If set, the velocity of the knob is tracked over the last [`Flick::window`] seconds of
+    /// the drag and the release reports a [`FlickEvent`] in [`JoystickEvent::flick`] if the
+    /// knob moved faster than [`Flick::min_speed`], e.g. for a dash or a throw.
+    /// A drag which is held still before the release is not a flick.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Flick, Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_flick(Some(Flick {
+    ///     min_speed: 5.0,
+    ///     window: 0.1,
+    /// }));
+    ///
+    /// // from the center to the rim in 1/20 seconds
+    /// for x in [100.0, 112.5, 125.0] {
+    ///     let input = SimulatedInput::new().with_mouse(Vec2::new(x, 200.0), true);
+    ///     joystick.update_with_input(&input, 0.025);
+    /// }
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(125.0, 200.0), false);
+    /// let flick = joystick.update_with_input(&input, 0.025).flick.unwrap();
+    /// assert_eq!(flick.direction, JoystickDirection::Right);
+    /// assert!((flick.speed - 20.0).abs() < 1e-3);
+    /// ```
+    pub fn set_flick(&mut self, flick: Option<Flick>) {
+        self.flick = flick;
+        self.flick_samples.clear();
+    }
+
+    /// remember the knob offset of the update for the flick detection
+    fn record_flick_sample(&mut self) {
+        let window = match self.flick {
+            Some(flick) => flick.window,
+            None => return,
+        };
+        let offset = self.knob_offset() / self.travel_radius;
+        self.flick_samples.push((self.hold_time, offset));
+        let oldest = self.hold_time - window;
+        // keep one sample before the window, so the window is fully covered
+        let outdated = self
+            .flick_samples
+            .iter()
+            .rposition(|(time, _)| *time < oldest)
+            .unwrap_or(0);
+        self.flick_samples.drain(..outdated);
+    }
+
+    /// the flick of the drag which was just released
+    fn detect_flick(&mut self) -> Option<FlickEvent> {
+        let flick = self.flick?;
+        let samples = std::mem::take(&mut self.flick_samples);
+        let (first, last) = (samples.first()?, samples.last()?);
+        let duration = last.0 - first.0;
+        if duration <= 0. {
+            return None;
+        }
+        let velocity = (last.1 - first.1) / duration;
+        let speed = velocity.length();
+        if !speed.is_finite() || speed < flick.min_speed {
+            return None;
+        }
+        let angle = velocity.y.atan2(velocity.x);
+        Some(FlickEvent {
+            direction: self.resolver.direction(angle),
+            angle,
+            speed,
+        })
+    }
+
+    /// set the rim persistence
+    ///
+    /// When the knob is dragged along the rim, the pointer can briefly slip into the center
+    /// which results in idle events for a few frames. With the rim persistence, these short
+    /// dips are filtered: if the last event had an intensity above
+    /// [`RimPersistence::min_intensity`], it is held while the joystick is idle for at most
+    /// [`RimPersistence::max_frames`] updates and [`RimPersistence::max_duration`] seconds.
+    ///
+    /// A release still resets the joystick immediately.
+    ///
+    /// Defaults to `None`.
+    pub fn set_rim_persistence(&mut self, rim_persistence: Option<RimPersistence>) {
+        self.rim_persistence = rim_persistence;
+        self.rim_event = None;
+    }
+
+    /// hold the last strong event during short idle dips
+    fn apply_rim_persistence(&mut self, event: &mut JoystickEvent, dt: f32) {
+        let rim_persistence = match self.rim_persistence {
+            Some(rim_persistence) => rim_persistence,
+            None => return,
+        };
+        if !self.dragging {
+            self.rim_event = None;
+        } else if !event.is_idle() {
+            self.rim_dip_frames = 0;
+            self.rim_dip_time = 0.;
+            self.rim_event = if event.intensity > rim_persistence.min_intensity {
+                Some(*event)
+            } else {
+                None
+            };
+        } else if let Some(rim_event) = self.rim_event {
+            self.rim_dip_frames += 1;
+            self.rim_dip_time += dt;
+            if self.rim_dip_frames <= rim_persistence.max_frames
+                && self.rim_dip_time <= rim_persistence.max_duration
+            {
+                *event = JoystickEvent {
+                    source: event.source,
+                    ..rim_event
+                };
+            } else {
+                self.rim_event = None;
+            }
+        }
+    }
+
+    /// set the color of the hover outline
+    ///
+    /// If set, an outline in this color is drawn around the background while the mouse
+    /// hovers over the joystick and it isn't dragged. The outline fades in and out.
+    /// This shows desktop users that the joystick is interactive.
+    ///
+    /// Defaults to `None`. The [`JoystickStyle::hover_color`] of the style is updated to the
+    /// new color.
+    pub fn set_hover_color(&mut self, hover_color: Option<Color>) {
+        self.hover_color = hover_color;
+        if let Some(style) = &mut self.style {
+            style.hover_color = hover_color;
+        }
+    }
+
+    /// whether the mouse hovers over the joystick while it isn't dragged
+    ///
+    /// this is never set by touches
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    ///
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), false);
+    /// joystick.update_with_input(&input, 0.0);
+    /// assert!(joystick.is_hovered());
+    ///
+    /// // dragging is no hovering
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), true);
+    /// joystick.update_with_input(&input, 0.0);
+    /// assert!(!joystick.is_hovered());
+    /// ```
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    /// fade the hover outline in or out
+    fn update_hover_fade(&mut self, dt: f32) {
+        let step = dt / HOVER_FADE_TIME;
+        self.hover_fade = if self.hovered {
+            (self.hover_fade + step).min(1.)
+        } else {
+            (self.hover_fade - step).max(0.)
+        };
+    }
+
+    /// set the outer trigger ring
+    ///
+    /// The ring is drawn around the background. While the knob is dragged, the pointer
+    /// can be moved beyond the background into the ring, which is reported with
+    /// [`JoystickEvent::in_outer_ring`]. Releasing the pointer inside the ring sets
+    /// [`JoystickEvent::released_in_outer_ring`] for one update, which can be used to
+    /// trigger an action like a dash.
+    ///
+    /// The knob is still clamped to the background.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{Vec2, RED};
+    /// use macroquad_virtual_joystick::{Joystick, OuterRing, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_outer_ring(Some(OuterRing {
+    ///     radius: 40.0,
+    ///     color: RED,
+    /// }));
+    ///
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), true);
+    /// joystick.update_with_input(&input, 0.0);
+    /// // drag the pointer past the background into the ring
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(135.0, 200.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert!(event.in_outer_ring);
+    /// assert_eq!(event.intensity, 1.0);
+    ///
+    /// // and release it there
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(135.0, 200.0), false);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert!(event.released_in_outer_ring);
+    /// // the flag is only set for one update
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert!(!event.released_in_outer_ring);
+    /// ```
+    pub fn set_outer_ring(&mut self, outer_ring: Option<OuterRing>) {
+        self.outer_ring = outer_ring;
+    }
+
+    /// enable or disable tracking of the total rotation
+    ///
+    /// If enabled, [`JoystickEvent::total_rotation`] accumulates the change of the angle
+    /// while the knob is dragged, so dragging the knob around the rim twice reports
+    /// two full turns instead of wrapping around at ±π.
+    /// Only movements with an intensity above [`Joystick::set_rotation_min_intensity`]
+    /// are counted, so wobbling around the center doesn't change the rotation.
+    /// The rotation is reset when the joystick is released or with [`Joystick::reset_rotation`].
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f32::consts::TAU;
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let center = Vec2::new(100.0, 200.0);
+    /// let mut joystick = Joystick::new(center.x, center.y, 50.0);
+    /// joystick.set_track_rotation(true);
+    ///
+    /// // drag the knob once around the rim, crossing the seam at ±π
+    /// let mut event = Default::default();
+    /// for step in 0..=36 {
+    ///     let angle = step as f32 / 36.0 * TAU;
+    ///     let position = center + Vec2::new(angle.cos(), angle.sin()) * 20.0;
+    ///     let input = SimulatedInput::new().with_mouse(position, true);
+    ///     event = joystick.update_with_input(&input, 1.0 / 60.0);
+    /// }
+    /// assert!((event.total_rotation - TAU).abs() < 1e-4);
+    ///
+    /// // and back again
+    /// for step in (0..=36).rev() {
+    ///     let angle = step as f32 / 36.0 * TAU;
+    ///     let position = center + Vec2::new(angle.cos(), angle.sin()) * 20.0;
+    ///     let input = SimulatedInput::new().with_mouse(position, true);
+    ///     event = joystick.update_with_input(&input, 1.0 / 60.0);
+    /// }
+    /// assert!(event.total_rotation.abs() < 1e-4);
+    ///
+    /// // releasing the joystick resets the rotation
+    /// let event = joystick.update_with_input(&SimulatedInput::new(), 1.0 / 60.0);
+    /// assert_eq!(event.total_rotation, 0.0);
+    /// ```
+    pub fn set_track_rotation(&mut self, track_rotation: bool) {
+        self.track_rotation = track_rotation;
+        self.reset_rotation();
+    }
+
+    /// set the minimum intensity a movement needs to count for the total rotation
+    ///
+    /// see [`Joystick::set_track_rotation`]
+    ///
+    /// Defaults to `0.5`.
+    pub fn set_rotation_min_intensity(&mut self, min_intensity: f32) {
+        self.rotation_min_intensity = min_intensity;
+    }
+
+    /// reset the total rotation to zero
+    ///
+    /// see [`Joystick::set_track_rotation`]
+    pub fn reset_rotation(&mut self) {
+        self.total_rotation = 0.;
+        self.last_rotation_angle = None;
+        self.raw_event.total_rotation = 0.;
+    }
+
+    /// enable repeat events for the held direction
+    ///
+    /// Like a held key on a keyboard, the held direction is emitted once when it is entered,
+    /// again after `initial_delay` seconds and then every `interval` seconds.
+    /// The repeats start again whenever the direction changes and stop when the joystick is idle.
+    /// This is useful to navigate menus with the joystick.
+    ///
+    /// The repeat events can be taken with [`Joystick::take_repeats`] after each update.
+    ///
+    /// # Arguments
+    /// * `initial_delay`: seconds between the first and the second event
+    /// * `interval`: seconds between each following event, should be positive. An interval
+    ///   which is not positive (including NaN) disables the repeat events (this panics in
+    ///   debug builds).
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_repeat(0.5, 0.2);
+    /// let right = SimulatedInput::new().with_mouse(Vec2::new(120.0, 200.0), true);
+    ///
+    /// // the direction is emitted once on entry
+    /// joystick.update_with_input(&right, 0.0);
+    /// assert_eq!(joystick.take_repeats().collect::<Vec<_>>(), [JoystickDirection::Right]);
+    /// // not yet repeated after 0.3 seconds
+    /// joystick.update_with_input(&right, 0.3);
+    /// assert_eq!(joystick.take_repeats().count(), 0);
+    /// // the initial delay passed after 0.6 seconds
+    /// joystick.update_with_input(&right, 0.3);
+    /// assert_eq!(joystick.take_repeats().count(), 1);
+    /// // a long frame (1.05 seconds total) contains the repeats at 0.7 and 0.9 seconds
+    /// joystick.update_with_input(&right, 0.45);
+    /// assert_eq!(joystick.take_repeats().count(), 2);
+    ///
+    /// // a new direction is emitted immediately
+    /// let left = SimulatedInput::new().with_mouse(Vec2::new(80.0, 200.0), true);
+    /// joystick.update_with_input(&left, 0.1);
+    /// assert_eq!(joystick.take_repeats().collect::<Vec<_>>(), [JoystickDirection::Left]);
+    /// ```
+    pub fn set_repeat(&mut self, initial_delay: f32, interval: f32) {
+        debug_assert!(interval > 0., "the repeat interval must be positive");
+        if interval.is_nan() || interval <= 0. {
+            self.disable_repeat();
+            return;
+        }
+        self.repeat = Some(Repeat {
+            initial_delay,
+            interval,
+        });
+    }
+
+    /// disable the repeat events
+    ///
+    /// see [`Joystick::set_repeat`]
+    pub fn disable_repeat(&mut self) {
+        self.repeat = None;
+        self.repeat_direction = JoystickDirection::Idle;
+        self.repeats.clear();
+    }
+
+    /// set the interpolation of fast swipes
+    ///
+    /// A fast swipe can jump across the joystick between two updates, so the directions in
+    /// between are never reported. With the interpolation, a movement of more than
+    /// [`SwipeInterpolation::max_spacing`] is divided into samples at most `max_spacing`
+    /// apart (but at most [`SwipeInterpolation::max_samples`] of them), which are processed
+    /// in order. Their events are added to the queue (see [`Joystick::take_events`])
+    /// before the event of the update, which is computed from the final position as usual.
+    ///
+    /// Defaults to `None`.
+    pub fn set_swipe_interpolation(&mut self, swipe_interpolation: Option<SwipeInterpolation>) {
+        self.swipe_interpolation = swipe_interpolation;
+        if swipe_interpolation.is_none() {
+            self.events.clear();
+        }
+    }
+
+    /// take the queued events since the last call
+    ///
+    /// While the swipe interpolation is enabled (see [`Joystick::set_swipe_interpolation`]),
+    /// the event of each update of a drag (including the release) is queued after the events of
+    /// the interpolated samples. The events of the samples are not post-processed, e.g. they
+    /// don't include the turbo. At most 64 events are queued, older ones are dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput, SwipeInterpolation};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_swipe_interpolation(Some(SwipeInterpolation {
+    ///     max_spacing: 10.0,
+    ///     max_samples: 8,
+    /// }));
+    ///
+    /// // swipe from the top to the bottom in one update
+    /// for y in [180.0, 220.0] {
+    ///     let input = SimulatedInput::new().with_mouse(Vec2::new(100.0, y), true);
+    ///     joystick.update_with_input(&input, 0.0);
+    /// }
+    /// let directions: Vec<_> = joystick.take_events().map(|event| event.direction).collect();
+    /// assert_eq!(
+    ///     directions,
+    ///     [
+    ///         JoystickDirection::Up,
+    ///         JoystickDirection::Up,
+    ///         JoystickDirection::Idle,
+    ///         JoystickDirection::Down,
+    ///         JoystickDirection::Down,
+    ///     ]
+    /// );
+    /// ```
+    pub fn take_events(&mut self) -> impl Iterator<Item = JoystickEvent> + '_ {
+        self.events.drain(..)
+    }
+
+    /// move the knob to the position of the drag, interpolating fast swipes if enabled
+    fn drag(&mut self, position: Vec2) {
+        if self.smoothing.is_some() {
+            // the knob follows in the update, see `update_smoothing`
+            self.smoothing_target = position;
+            return;
+        }
+        if let Some(interpolation) = self.swipe_interpolation {
+            let from = self.drag_position;
+            let distance = (position - from).length();
+            if interpolation.max_spacing > 0. && distance > interpolation.max_spacing {
+                let samples = ((distance / interpolation.max_spacing).ceil() as usize - 1)
+                    .min(interpolation.max_samples);
+                for sample in 1..=samples {
+                    self.moving(from.lerp(position, sample as f32 / (samples + 1) as f32));
+                    push_capped(
+                        &mut self.events,
+                        JoystickEvent {
+                            phase: JoystickPhase::Drag,
+                            ..self.raw_event
+                        },
+                    );
+                }
+            }
+        }
+        self.moving(position);
+    }
+
+    /// take the repeat events since the last call
+    ///
+    /// At most 64 repeats are queued, older ones are dropped. See [`Joystick::set_repeat`].
+    pub fn take_repeats(&mut self) -> impl Iterator<Item = JoystickDirection> + '_ {
+        self.repeats.drain(..)
+    }
+
+    /// emit the repeat events of the current direction
+    fn update_repeat(&mut self, dt: f32) {
+        let repeat = match self.repeat {
+            Some(repeat) => repeat,
+            None => return,
+        };
+        let direction = self.output.direction;
+        if direction != self.repeat_direction {
+            self.repeat_direction = direction;
+            if direction != JoystickDirection::Idle {
+                push_capped(&mut self.repeats, direction);
+                self.repeat_timer = repeat.initial_delay;
+            }
+        } else if direction != JoystickDirection::Idle {
+            self.repeat_timer -= dt;
+            if self.repeat_timer <= 0. {
+                // the number of intervals which ended in this update
+                let count = (-self.repeat_timer / repeat.interval).floor() + 1.;
+                for _ in 0..(count as usize).min(MAX_QUEUED) {
+                    push_capped(&mut self.repeats, direction);
+                }
+                self.repeat_timer += count * repeat.interval;
+                if !self.repeat_timer.is_finite() {
+                    self.repeat_timer = repeat.interval;
+                }
+            }
+        }
+    }
+
+    /// update the joystick from touch
+    ///
+    /// only one touch can own the joystick at a time, all other touches are ignored
+    fn update_touch(&mut self, mut touches: Vec<Touch>) {
+        order_touch_phases(&mut touches);
+        for touch in &touches {
+            let position = self.to_local(touch.position);
+            match touch.phase {
+                // a touch starts in the joystick
+                TouchPhase::Started if !self.dragging && self.can_grab(position) => {
+                    self.touch_id = touch.id;
+                    let position = match self.predicted_start(touch, &touches) {
+                        Some(predicted) => self.to_local(predicted),
+                        None => position,
+                    };
+                    self.grab(InputSource::Touch, position);
+                }
+                TouchPhase::Moved if self.owns(touch) => {
+                    self.drag(position);
+                }
+                TouchPhase::Ended | TouchPhase::Cancelled if self.owns(touch) => {
+                    self.release();
+                    if self.touch_handoff && !self.dragging {
+                        self.hand_off(&touches);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// the last position of a started touch which also moved in the same frame
+    fn predicted_start(&self, started: &Touch, touches: &[Touch]) -> Option<Vec2> {
+        if !self.predictive_start {
+            return None;
+        }
+        touches
+            .iter()
+            .rev()
+            .find(|touch| touch.id == started.id && touch.phase == TouchPhase::Moved)
+            .map(|touch| touch.position)
+    }
+
+    /// enable or disable the prediction of the start of a touch
+    ///
+    /// On some platforms (e.g. WASM) the start of a touch arrives with a stale position and
+    /// the same frame already contains a movement of the touch. If enabled, such a touch grabs
+    /// the joystick where it started, but the first event and the press (see
+    /// [`Joystick::set_commit_distance`]) use the position of its last movement in the frame.
+    ///
+    /// The phases of each touch in a frame are always processed in the order start,
+    /// movement, end.
+    ///
+    /// Defaults to `false`.
+    pub fn set_predictive_start(&mut self, predictive_start: bool) {
+        self.predictive_start = predictive_start;
+    }
+
+    /// the id of the touch which drags the joystick
+    ///
+    /// `None` if the joystick isn't dragged or the mouse drags it
+    pub fn active_touch(&self) -> Option<u64> {
+        if self.dragging && self.source == InputSource::Touch {
+            Some(self.touch_id)
+        } else {
+            None
+        }
+    }
+
+    /// whether the touch drags the joystick or released it in the last update
+    ///
+    /// this can be used to ignore the touches of the joystick for other input, e.g. to shoot
+    /// with a tap on the screen, see [`Joystick::free_touches`]
+    pub fn uses_touch(&self, id: u64) -> bool {
+        let released = self.output.phase == JoystickPhase::Release
+            && self.output.source == InputSource::Touch
+            && self.touch_id == id;
+        self.active_touch() == Some(id) || released
+    }
+
+    /// the touches of the input which are not used by the joystick
+    ///
+    /// Call this after the update of the joystick, so a touch which grabbed the joystick in
+    /// the frame is already excluded. The touch which released the joystick in the last update
+    /// is excluded too (see [`Joystick::uses_touch`]).
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{TouchPhase, Vec2};
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let input = SimulatedInput::new()
+    ///     .with_touch(0, TouchPhase::Started, Vec2::new(110.0, 200.0))
+    ///     .with_touch(1, TouchPhase::Started, Vec2::new(500.0, 200.0));
+    /// joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(joystick.active_touch(), Some(0));
+    ///
+    /// let taps = joystick.free_touches(&input);
+    /// assert_eq!(taps.len(), 1);
+    /// assert_eq!(taps[0].id, 1);
+    /// ```
+    pub fn free_touches(&self, input: &impl JoystickInput) -> Vec<Touch> {
+        let mut touches = input.touches();
+        touches.retain(|touch| !self.uses_touch(touch.id));
+        touches
+    }
+
+    /// whether the mouse drags the joystick
+    pub(crate) fn owns_mouse(&self) -> bool {
+        self.dragging && self.source == InputSource::Mouse
+    }
+
+    /// whether the touch owns the joystick
+    fn owns(&self, touch: &Touch) -> bool {
+        self.dragging && self.source == InputSource::Touch && touch.id == self.touch_id
+    }
+
+    /// whether a press at `position` grabs the joystick
+    fn can_grab(&self, position: Vec2) -> bool {
+        if let JoystickMode::Floating { activation, .. } = self.mode {
+            if activation.contains(position) {
+                return true;
+            }
+        }
+        if let Some(hit_rect) = self.hit_rect {
+            if hit_rect.contains(position) {
+                return true;
+            }
+        }
+        (position - self.center).length() < self.grab_radius()
+    }
+
+    /// set the mode of the joystick
+    ///
+    /// In [`JoystickMode::Floating`], a press inside the activation area moves the joystick
+    /// under the press, so the drag starts at the center. A press inside the joystick but
+    /// outside of the activation area grabs it at its current position like in
+    /// [`JoystickMode::Fixed`]. Combine it with [`Joystick::set_idle_appearance`] to hide the
+    /// joystick until it is pressed.
+    ///
+    /// An attached joystick (see [`Joystick::attach_to`]) is moved back to its provider in the
+    /// next update.
+    ///
+    /// Defaults to [`JoystickMode::Fixed`].
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{Rect, Vec2};
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, JoystickMode, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_mode(JoystickMode::Floating {
+    ///     activation: Rect::new(0.0, 0.0, 400.0, 400.0),
+    ///     return_on_release: true,
+    /// });
+    ///
+    /// // the joystick spawns under the press
+    /// let press = SimulatedInput::new().with_mouse(Vec2::new(300.0, 300.0), true);
+    /// let event = joystick.update_with_input(&press, 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::Idle);
+    /// assert_eq!(joystick.center(), Vec2::new(300.0, 300.0));
+    ///
+    /// let drag = SimulatedInput::new().with_mouse(Vec2::new(300.0, 310.0), true);
+    /// let event = joystick.update_with_input(&drag, 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::Down);
+    ///
+    /// let release = SimulatedInput::new().with_mouse(Vec2::new(300.0, 310.0), false);
+    /// joystick.update_with_input(&release, 0.0);
+    /// assert_eq!(joystick.center(), Vec2::new(100.0, 200.0));
+    /// ```
+    pub fn set_mode(&mut self, mode: JoystickMode) {
+        self.mode = mode;
+    }
+
+    /// the mode of the joystick
+    ///
+    /// see [`Joystick::set_mode`]
+    pub fn mode(&self) -> JoystickMode {
+        self.mode
+    }
+
+    /// let the joystick follow a pointer which is dragged beyond the travel radius
+    ///
+    /// Instead of clamping the knob, the whole joystick slides after the pointer, so the knob
+    /// stays under it at the rim. After the release, a joystick in [`JoystickMode::Fixed`]
+    /// moves back to its position; in [`JoystickMode::Floating`] it only moves back if
+    /// `return_on_release` is set. While following, the pointer never reaches the
+    /// [`OuterRing`].
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_follow(true);
+    /// joystick.update_with_input(&SimulatedInput::new().with_mouse(Vec2::new(100.0, 200.0), true), 0.0);
+    /// let drag = SimulatedInput::new().with_mouse(Vec2::new(200.0, 200.0), true);
+    /// let event = joystick.update_with_input(&drag, 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::Right);
+    /// assert_eq!(joystick.center(), Vec2::new(175.0, 200.0));
+    ///
+    /// let release = SimulatedInput::new().with_mouse(Vec2::new(200.0, 200.0), false);
+    /// joystick.update_with_input(&release, 0.0);
+    /// assert_eq!(joystick.center(), Vec2::new(100.0, 200.0));
+    /// ```
+    pub fn set_follow(&mut self, follow: bool) {
+        self.follow = follow;
+    }
+
+    /// set the area in which the center stays while the joystick follows the pointer
+    ///
+    /// e.g. to keep a following joystick on its half of the screen; at the border of the
+    /// area, the knob is clamped like without [`Joystick::set_follow`]. The area is in the
+    /// coordinates of the joystick, like the activation area of [`JoystickMode::Floating`].
+    ///
+    /// Defaults to `None`, which lets the joystick follow anywhere.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{Rect, Vec2};
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_follow(true);
+    /// joystick.set_follow_bounds(Some(Rect::new(50.0, 150.0, 100.0, 100.0)));
+    /// joystick.update_with_input(&SimulatedInput::new().with_mouse(Vec2::new(100.0, 200.0), true), 0.0);
+    /// let drag = SimulatedInput::new().with_mouse(Vec2::new(300.0, 200.0), true);
+    /// let event = joystick.update_with_input(&drag, 0.0);
+    /// assert_eq!(joystick.center(), Vec2::new(150.0, 200.0));
+    /// assert_eq!(event.intensity, 1.0);
+    /// ```
+    pub fn set_follow_bounds(&mut self, follow_bounds: Option<Rect>) {
+        self.follow_bounds = follow_bounds;
+    }
+
+    /// hand the joystick off to another touch which is held inside of it
+    fn hand_off(&mut self, touches: &[Touch]) {
+        let next = touches.iter().find(|touch| {
+            touch.id != self.touch_id
+                && !matches!(touch.phase, TouchPhase::Ended | TouchPhase::Cancelled)
+                && self.can_grab(self.to_local(touch.position))
+        });
+        if let Some(next) = next {
+            self.touch_id = next.id;
+            self.grab(InputSource::Touch, self.to_local(next.position));
+        }
+    }
+
+    /// enable or disable the handoff between touches
+    ///
+    /// Only one touch can own the joystick. Other touches which start inside of the
+    /// joystick while it is dragged are ignored. If the handoff is enabled and the owning
+    /// touch is released, the joystick is handed off to another touch which is held inside
+    /// of it. Otherwise the joystick is released and the other touches stay ignored.
+    ///
+    /// Defaults to `false`.
+    pub fn set_touch_handoff(&mut self, touch_handoff: bool) {
+        self.touch_handoff = touch_handoff;
+    }
+
+    /// set whether the mouse data mirrors the touches
+    ///
+    /// macroquad (see `simulate_mouse_with_touch`) and some platforms synthesize mouse events
+    /// from touches, so the same gesture is reported as a touch and as the mouse. The mouse
+    /// is only used while there are no touches, but the synthesized release of the mouse can
+    /// arrive after the touch ended, which would grab the joystick again.
+    ///
+    /// If enabled, the mouse is ignored while any touch exists and afterwards until its button
+    /// is released. A drag of the mouse which started before the touch was reported is handed
+    /// over to the touch closest to the knob.
+    ///
+    /// Defaults to `false`.
+    pub fn set_touch_synthesizes_mouse(&mut self, touch_synthesizes_mouse: bool) {
+        self.touch_synthesizes_mouse = touch_synthesizes_mouse;
+        if !touch_synthesizes_mouse {
+            self.mouse_suppressed = false;
+        }
+    }
+
+    /// hand a drag of the mouse over to the touch which it mirrors
+    fn adopt_mouse_drag(&mut self, touches: &[Touch]) {
+        if !self.dragging || self.source != InputSource::Mouse {
+            return;
+        }
+        let distance =
+            |touch: &Touch| (self.to_local(touch.position) - self.drag_position).length();
+        let closest = touches
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)));
+        if let Some(touch) = closest {
+            self.source = InputSource::Touch;
+            self.touch_id = touch.id;
+        }
+    }
+
+    /// set how the angle of the events is quantized into directions and sectors
+    ///
+    /// The angle of the events is not changed, games which aim with the raw angle can
+    /// ignore the direction. The resolver also applies to the sectors drawn by
+    /// [`Joystick::render_sectors`].
+    ///
+    /// Defaults to [`DirectionResolver::eight_way`].
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{
+    ///     DirectionResolver, Joystick, JoystickDirection, SimulatedInput,
+    /// };
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_direction_resolver(DirectionResolver::four_way());
+    ///
+    /// // a drag up and a bit to the right
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(108.0, 185.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::Up);
+    /// assert_eq!(event.sector, 3);
+    /// ```
+    pub fn set_direction_resolver(&mut self, resolver: DirectionResolver) {
+        self.resolver = resolver;
+    }
+
+    /// the direction resolver, see [`Joystick::set_direction_resolver`]
+    pub fn direction_resolver(&self) -> DirectionResolver {
+        self.resolver
+    }
+
+    /// set the hysteresis (in radians) of the sector boundaries
+    ///
+    /// To move to the next sector, the angle has to pass the boundary by the margin, so the
+    /// direction doesn't flip between two directions while the knob is close to a boundary.
+    /// Like [`Joystick::set_band_hysteresis`], the margin only applies while the joystick
+    /// stays active. [`JoystickEvent::angle`] always reports the actual angle.
+    ///
+    /// Defaults to `0.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_direction_hysteresis(5f32.to_radians());
+    /// let drag = |joystick: &mut Joystick, degrees: f32| {
+    ///     let offset = Vec2::new(degrees.to_radians().cos(), degrees.to_radians().sin()) * 20.0;
+    ///     let input = SimulatedInput::new().with_mouse(Vec2::new(100.0, 200.0) + offset, true);
+    ///     joystick.update_with_input(&input, 0.0).direction
+    /// };
+    /// assert_eq!(drag(&mut joystick, 20.0), JoystickDirection::Right);
+    /// // just past the boundary at 22.5°
+    /// assert_eq!(drag(&mut joystick, 25.0), JoystickDirection::Right);
+    /// assert_eq!(drag(&mut joystick, 30.0), JoystickDirection::DownRight);
+    /// assert_eq!(drag(&mut joystick, 20.0), JoystickDirection::DownRight);
+    /// ```
+    pub fn set_direction_hysteresis(&mut self, margin: f32) {
+        self.direction_hysteresis = margin;
+    }
+
+    /// keep the sector of the last update while the angle is within its hysteresis
+    fn apply_direction_hysteresis(&self, event: &mut JoystickEvent) {
+        let last = &self.output;
+        if self.direction_hysteresis <= 0.
+            || event.is_idle()
+            || last.is_idle()
+            || event.sector == last.sector
+        {
+            return;
+        }
+        let center = self.resolver.sector_center(last.sector);
+        let distance = ((event.angle - center + PI).rem_euclid(2. * PI) - PI).abs();
+        if distance <= self.resolver.sector_angle() / 2. + self.direction_hysteresis {
+            event.sector = last.sector;
+            event.direction = last.direction;
+        }
+    }
+
+    /// snap the knob and the angle to the centers of the sectors, like a directional pad
+    ///
+    /// The knob only moves along the centers of the sectors of the direction resolver
+    /// (see [`Joystick::set_direction_resolver`]), so a drag which is almost diagonal looks
+    /// and reports like the diagonal. [`JoystickEvent::angle`] is the center of the sector,
+    /// the intensity is not changed.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_snap_knob(true);
+    ///
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(110.0, 201.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(event.angle, 0.0);
+    /// assert_eq!(joystick.rendered_knob().y, 200.0);
+    /// ```
+    pub fn set_snap_knob(&mut self, snap_knob: bool) {
+        self.snap_knob = snap_knob;
+    }
+
+    /// the angle snapped to the center of its sector if enabled, see [`Joystick::set_snap_knob`]
+    fn snap(&self, angle: f32) -> f32 {
+        if self.snap_knob {
+            self.resolver.snapped_angle(angle)
+        } else {
+            angle
+        }
+    }
+
+    /// bind keys which control the joystick, e.g. WASD or the arrow keys
+    ///
+    /// While no touch or mouse drags the joystick, the bound keys move the knob to the rim in
+    /// their direction (two keys move it diagonally) and the event is reported with
+    /// [`InputSource::Keyboard`], so desktop builds and tests can share the movement code with
+    /// the touch controls. A touch or the mouse takes over when it grabs the joystick.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::KeyCode;
+    /// use macroquad_virtual_joystick::{InputSource, Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.bind_keys(KeyCode::W, KeyCode::A, KeyCode::S, KeyCode::D);
+    ///
+    /// let input = SimulatedInput::new().with_key(KeyCode::W).with_key(KeyCode::D);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(event.direction, JoystickDirection::UpRight);
+    /// assert_eq!(event.intensity, 1.0);
+    /// assert_eq!(event.source, InputSource::Keyboard);
+    /// ```
+    pub fn bind_keys(&mut self, up: KeyCode, left: KeyCode, down: KeyCode, right: KeyCode) {
+        self.keys = Some(KeyBindings {
+            up,
+            left,
+            down,
+            right,
+        });
+    }
+
+    /// bind W, A, S and D, see [`Joystick::bind_keys`]
+    pub fn bind_wasd(&mut self) {
+        self.bind_keys(KeyCode::W, KeyCode::A, KeyCode::S, KeyCode::D);
+    }
+
+    /// bind the arrow keys, see [`Joystick::bind_keys`]
+    pub fn bind_arrow_keys(&mut self) {
+        self.bind_keys(KeyCode::Up, KeyCode::Left, KeyCode::Down, KeyCode::Right);
+    }
+
+    /// remove the key bindings, see [`Joystick::bind_keys`]
+    pub fn unbind_keys(&mut self) {
+        self.keys = None;
+    }
+
+    /// the direction of the bound keys which are down, not normalized
+    fn key_offset(&self, input: &impl JoystickInput) -> Vec2 {
+        let keys = match self.keys {
+            Some(keys) => keys,
+            None => return Vec2::ZERO,
+        };
+        let axis = |negative: KeyCode, positive: KeyCode| {
+            input.is_key_down(positive) as i8 as f32 - input.is_key_down(negative) as i8 as f32
+        };
+        self.axis.constrain(Vec2::new(
+            axis(keys.left, keys.right),
+            axis(keys.up, keys.down),
+        ))
+    }
+
+    /// the bound keys in the order up, left, down, right
+    pub(crate) fn bound_keys(&self) -> Option<[KeyCode; 4]> {
+        self.keys
+            .map(|keys| [keys.up, keys.left, keys.down, keys.right])
+    }
+
+    /// the offset of the gamepad stick, zero inside of the dead zone
+    fn stick_offset(&self, input: &impl JoystickInput) -> Vec2 {
+        let stick = self.axis.constrain(input.gamepad_stick());
+        if !stick.is_finite() || self.apply_radial_zones(stick.length()) <= 0. {
+            return Vec2::ZERO;
+        }
+        stick.clamp_length_max(1.)
+    }
+
+    /// update the joystick from the bound keys or the gamepad stick
+    ///
+    /// the offset is relative to the travel radius
+    fn update_external(&mut self, offset: Vec2, source: InputSource) {
+        if offset == Vec2::ZERO {
+            if self.external_active {
+                self.external_active = false;
+                self.reset();
+            }
+            return;
+        }
+        if !self.external_active {
+            self.external_active = true;
+            self.grabbed = true;
+            self.locked = false;
+            self.hold_time = 0.;
+        }
+        self.source = source;
+        let angle = self.snap(offset.y.atan2(offset.x));
+        let knob = self.center
+            + Vec2::new(angle.cos(), angle.sin()) * offset.length() * self.travel_radius;
+        self.knob.x = knob.x;
+        self.knob.y = knob.y;
+        let (intensity, angle) = if source == InputSource::Gamepad {
+            let intensity = self.apply_intensity_response(offset.length());
+            self.apply_axis_response(intensity, angle)
+        } else {
+            (1., angle)
+        };
+        self.raw_event = JoystickEvent::new(intensity, angle, self.resolver);
+        self.raw_event.source = source;
+    }
+
+    /// update the joystick from mouse drag
+    fn update_mouse(&mut self, input: &impl JoystickInput, mouse_pressed: bool) {
+        let mouse = self.to_local(input.mouse_position());
+        let mouse_down = input.is_mouse_down();
+        if self.dragging {
+            if mouse_down {
+                self.drag(mouse)
+            } else {
+                self.release();
+            }
+        } else if mouse_pressed && self.can_grab(mouse) {
+            self.grab(InputSource::Mouse, mouse);
+        }
+        self.hovered = !self.dragging && self.is_over(mouse);
+    }
+
+    /// whether the position is over the joystick, ignoring the grab forgiveness
+    fn is_over(&self, position: Vec2) -> bool {
+        (self.center - position).length() < self.hit_radius
+            || self
+                .hit_rect
+                .is_some_and(|hit_rect| hit_rect.contains(position))
+    }
+
+    /// whether nothing can change without input
+    ///
+    /// The joystick is quiescent if it's idle, not latched, not hovered, not attached
+    /// and no animation like the hover fade or the knob spring is running.
+    /// While it's quiescent and there are no touches, no pressed mouse button and the mouse
+    /// isn't over the joystick, [`Joystick::update`] returns the idle event right away and
+    /// only advances [`Joystick::seconds_since_last_activity`].
+    /// A game loop with many widgets can use this to skip them, and
+    /// [`Joystick::set_render_cache`] keeps rendering them cheap.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// assert!(joystick.is_quiescent());
+    ///
+    /// let press = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), true);
+    /// joystick.update_with_input(&press, 0.0);
+    /// assert!(!joystick.is_quiescent());
+    /// ```
+    pub fn is_quiescent(&self) -> bool {
+        let settled = |event: &JoystickEvent| {
+            event.is_idle()
+                && event.source == InputSource::None
+                && !event.released_in_outer_ring
+                && event.band == 0
+                && event.charge == 0.
+        };
+        !self.dragging
+            && !self.locked
+            && !self.hovered
+            && self.hover_fade == 0.
+            && self.attachment.is_none()
+            && settled(&self.raw_event)
+            && settled(&self.output)
+            && self.rim_event.is_none()
+            && self.turbo_charge == 0.
+            && self.repeat_direction == JoystickDirection::Idle
+            && self.spring_offset == Vec2::ZERO
+            && self.spring_velocity == Vec2::ZERO
+            && self.returning_offset().is_none()
+    }
+
+    /// start a drag at `position`
+    fn grab(&mut self, source: InputSource, position: Vec2) {
+        // a new press clears the latched direction
+        self.locked = false;
+        self.grabbed = true;
+        self.flick_samples.clear();
+        if let JoystickMode::Floating { activation, .. } = self.mode {
+            if activation.contains(position) {
+                self.move_center(position);
+            }
+        }
+        self.dragging = true;
+        self.source = source;
+        self.press_position = position;
+        self.committed = self.commit_distance <= 0.;
+        self.hold_time = 0.;
+        self.smoothing_target = position;
+        self.moving(position);
+    }
+
+    /// the duration (in seconds) of the current drag or press of the bound keys
+    ///
+    /// After the release, this is the duration of the last drag until the next drag starts.
+    pub fn hold_time(&self) -> f32 {
+        self.hold_time
+    }
+
+    /// set the distance the pointer has to move from the press before the drag commits
+    ///
+    /// Until the drag commits, the joystick reports idle events, so brushing past the
+    /// joystick doesn't move anything. Once the pointer moved at least `commit_distance`
+    /// pixels away from the press, the drag commits and the event is computed from the
+    /// current position as usual. A press which is released before it commits is still
+    /// reported with its [`JoystickEvent::source`] on release, e.g. to handle it as a tap.
+    ///
+    /// The distance is in the coordinates of the joystick, e.g. use `0.2 * size / 2.`
+    /// for 20% of the radius.
+    ///
+    /// Defaults to `0.0`, which commits every drag immediately.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_commit_distance(5.0);
+    ///
+    /// let press = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), true);
+    /// assert_eq!(joystick.update_with_input(&press, 0.0).direction, JoystickDirection::Idle);
+    /// let drag = SimulatedInput::new().with_mouse(Vec2::new(110.0, 206.0), true);
+    /// assert_eq!(joystick.update_with_input(&drag, 0.0).direction, JoystickDirection::DownRight);
+    /// ```
+    pub fn set_commit_distance(&mut self, commit_distance: f32) {
+        self.commit_distance = commit_distance;
+    }
+
+    /// end the drag
+    ///
+    /// this latches the event if the direction lock is enabled and the intensity is high enough,
+    /// otherwise the joystick is reset
+    fn release(&mut self) {
+        if self.direction_lock
+            && self.raw_event.intensity >= self.direction_lock_threshold
+            && !self.raw_event.is_idle()
+        {
+            self.dragging = false;
+            self.locked = true;
+        } else {
+            self.reset();
+        }
+    }
+
+    /// reset the joystick
+    fn reset(&mut self) {
+        let released_in_outer_ring = self.raw_event.in_outer_ring;
+        self.dragging = false;
+        self.knob.x = self.center.x;
+        self.knob.y = self.center.y;
+        self.raw_event = JoystickEvent::default();
+        self.raw_event.released_in_outer_ring = released_in_outer_ring;
+        // the source of the drag is still reported in the update of the release
+        self.raw_event.source = self.source;
+        self.source = InputSource::None;
+        self.reset_rotation();
+        let return_home = match self.mode {
+            JoystickMode::Fixed => self.follow,
+            JoystickMode::Floating {
+                return_on_release, ..
+            } => return_on_release,
+        };
+        if return_home {
+            self.move_center(self.static_center);
+        }
+    }
+
+    /// drop all transient state, e.g. when switching scenes
+    ///
+    /// This ends a drag without reporting a release and clears the latched direction,
+    /// the tracked rotation, the turbo charge, the current band, the timers and pending
+    /// events of the repeat, the held event of the rim persistence, the hover fade, the time
+    /// since the last activity and the spring of the knob. A floating or following joystick
+    /// moves back to its position. Afterwards the joystick behaves like a newly created one.
+    ///
+    /// The configuration is kept: the position, size, elements, transform, attachment,
+    /// mirroring, all options and callbacks and the usage statistics (see
+    /// [`Joystick::reset_stats`]).
+    /// A touch which is still held after the call is ignored until it is released.
+    pub fn clear(&mut self) {
+        self.dragging = false;
+        self.touch_id = 0;
+        self.source = InputSource::None;
+        if self.attachment.is_none() {
+            // the center of an attached joystick is moved by the next update
+            self.move_center(self.static_center);
+        }
+        self.knob.x = self.center.x;
+        self.knob.y = self.center.y;
+        self.raw_event = JoystickEvent::default();
+        self.output = JoystickEvent::default();
+        self.total_rotation = 0.;
+        self.last_rotation_angle = None;
+        self.repeat_direction = JoystickDirection::Idle;
+        self.repeat_timer = 0.;
+        self.repeats.clear();
+        self.turbo_charge = 0.;
+        self.hovered = false;
+        self.hover_fade = 0.;
+        self.locked = false;
+        self.band = 0;
+        self.rim_event = None;
+        self.rim_dip_frames = 0;
+        self.rim_dip_time = 0.;
+        self.idle_time = 0.;
+        if let Some(timeout) = &mut self.idle_timeout {
+            timeout.fired = false;
+        }
+        self.spring_offset = Vec2::ZERO;
+        self.spring_velocity = Vec2::ZERO;
+        self.press_position = self.center;
+        self.committed = false;
+        self.frame = None;
+        self.hold_time = 0.;
+        self.drag_position = self.center;
+        self.smoothing_target = self.center;
+        self.return_elapsed = self.return_duration;
+        self.events.clear();
+        self.mouse_suppressed = false;
+        self.flick_samples.clear();
+        self.external_active = false;
+        self.at_edge = false;
+    }
+
+    /// save the runtime state of the joystick, e.g. before a hot reload
+    ///
+    /// The state contains everything which [`Joystick::clear`] drops, so a drag continues
+    /// seamlessly after [`Joystick::restore_state`].
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{TouchPhase, Vec2};
+    /// use macroquad_virtual_joystick::{Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let press = SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(100.0, 210.0));
+    /// joystick.update_with_input(&press, 0.0);
+    /// let state = joystick.save_state();
+    ///
+    /// let mut reloaded = Joystick::new(100.0, 200.0, 50.0);
+    /// reloaded.restore_state(state);
+    /// let held = SimulatedInput::new().with_touch(0, TouchPhase::Stationary, Vec2::new(100.0, 210.0));
+    /// assert_eq!(reloaded.update_with_input(&held, 0.0).direction, JoystickDirection::Down);
+    /// ```
+    pub fn save_state(&self) -> JoystickRuntimeState {
+        JoystickRuntimeState {
+            size: self.size,
+            dragging: self.dragging,
+            touch_id: self.touch_id,
+            source: self.source,
+            knob_offset: self.knob_offset(),
+            raw_event: self.raw_event,
+            output: self.output,
+            total_rotation: self.total_rotation,
+            last_rotation_angle: self.last_rotation_angle,
+            repeat_direction: self.repeat_direction,
+            repeat_timer: self.repeat_timer,
+            repeats: self.repeats.clone(),
+            turbo_charge: self.turbo_charge,
+            hovered: self.hovered,
+            hover_fade: self.hover_fade,
+            locked: self.locked,
+            band: self.band,
+            rim_event: self.rim_event,
+            rim_dip_frames: self.rim_dip_frames,
+            rim_dip_time: self.rim_dip_time,
+            idle_time: self.idle_time,
+            idle_timeout_fired: self.idle_timeout.as_ref().is_some_and(|t| t.fired),
+            spring_offset: self.spring_offset,
+            spring_velocity: self.spring_velocity,
+            press_offset: self.press_position - self.center,
+            committed: self.committed,
+            hold_time: self.hold_time,
+            drag_offset: self.drag_position - self.center,
+            smoothing_offset: self.smoothing_target - self.center,
+            return_from: self.return_from,
+            return_elapsed: self.return_elapsed,
+            events: self.events.clone(),
+            mouse_suppressed: self.mouse_suppressed,
+            mouse_was_down: self.mouse_was_down,
+            flick_samples: self.flick_samples.clone(),
+            external_active: self.external_active,
+            at_edge: self.at_edge,
+        }
+    }
+
+    /// restore the runtime state which was saved by [`Joystick::save_state`]
+    ///
+    /// The configuration of the joystick is kept. The knob is placed relative to the current
+    /// center, and if the size changed, its offset is scaled to the new size and clamped to the
+    /// travel radius, so the intensity of a drag is kept.
+    pub fn restore_state(&mut self, state: JoystickRuntimeState) {
+        let radius = self.travel_radius;
+        let scale = if state.size > 0. {
+            self.size / state.size
+        } else {
+            1.
+        };
+        let knob_offset = (state.knob_offset * scale).clamp_length_max(radius);
+        self.dragging = state.dragging;
+        self.touch_id = state.touch_id;
+        self.source = state.source;
+        self.knob.x = self.center.x + knob_offset.x;
+        self.knob.y = self.center.y + knob_offset.y;
+        self.raw_event = state.raw_event;
+        self.output = state.output;
+        self.total_rotation = state.total_rotation;
+        self.last_rotation_angle = state.last_rotation_angle;
+        self.repeat_direction = state.repeat_direction;
+        self.repeat_timer = state.repeat_timer;
+        self.repeats = state.repeats;
+        self.turbo_charge = state.turbo_charge;
+        self.hovered = state.hovered;
+        self.hover_fade = state.hover_fade;
+        self.locked = state.locked;
+        self.band = state.band;
+        self.rim_event = state.rim_event;
+        self.rim_dip_frames = state.rim_dip_frames;
+        self.rim_dip_time = state.rim_dip_time;
+        self.idle_time = state.idle_time;
+        if let Some(timeout) = &mut self.idle_timeout {
+            timeout.fired = state.idle_timeout_fired;
+        }
+        self.spring_offset = (state.spring_offset * scale).clamp_length_max(radius);
+        self.spring_velocity = state.spring_velocity * scale;
+        self.press_position = self.center + state.press_offset * scale;
+        self.committed = state.committed;
+        self.hold_time = state.hold_time;
+        self.drag_position = self.center + state.drag_offset * scale;
+        self.smoothing_target = self.center + state.smoothing_offset * scale;
+        self.return_from = state.return_from * scale;
+        self.return_elapsed = state.return_elapsed.min(self.return_duration);
+        self.events = state.events;
+        self.mouse_suppressed = state.mouse_suppressed;
+        self.mouse_was_down = state.mouse_was_down;
+        self.flick_samples = state.flick_samples;
+        self.external_active = state.external_active;
+        self.at_edge = state.at_edge;
+    }
+
+    /// update the joystick
+    ///
+    /// this updates the joystick and returns the current [`JoystickEvent`]
+    ///
+    /// The joystick is only updated once per frame of macroquad: further calls in the same
+    /// frame return the event of the first call (see [`Joystick::update_in_frame`]).
+    ///
+    /// # Examples
+    /// see [`Joystick`]
+    pub fn update(&mut self) -> JoystickEvent {
+        self.update_in_frame(&MacroquadInput, FrameStamp::current())
+    }
+
+    /// update the joystick from the given input once per frame
+    ///
+    /// If the joystick was already updated in the same frame (see [`FrameStamp::is_same_frame`]),
+    /// the event of that update is returned without processing the input again, so presses
+    /// and repeats are not reported twice. Otherwise this is the same as
+    /// [`Joystick::update_with_input`] with the frame time of the stamp.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{TouchPhase, Vec2};
+    /// use macroquad_virtual_joystick::{FrameStamp, Joystick, JoystickDirection, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let press = SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(110.0, 200.0));
+    /// let frame = FrameStamp::new(0.016, 1.0);
+    /// joystick.update_in_frame(&press, frame);
+    ///
+    /// // the release isn't processed in the same frame
+    /// let release = SimulatedInput::new().with_touch(0, TouchPhase::Ended, Vec2::new(110.0, 200.0));
+    /// let event = joystick.update_in_frame(&release, FrameStamp::new(0.016, 1.001));
+    /// assert_eq!(event.direction, JoystickDirection::Right);
+    /// ```
+    pub fn update_in_frame(
+        &mut self,
+        input: &impl JoystickInput,
+        frame: FrameStamp,
+    ) -> JoystickEvent {
+        if let Some(first) = self.frame {
+            if first.is_same_frame(&frame) {
+                return self.output;
+            }
+        }
+        self.frame = Some(frame);
+        self.update_with_input(input, frame.frame_time)
+    }
+
+    /// set the scale of the time of the joystick, e.g. for slow motion
+    ///
+    /// The time since the last update is multiplied by the scale before it's used by the
+    /// joystick. This affects all time-based features: the repeat (see
+    /// [`Joystick::set_repeat`]), the turbo, the rim persistence, the hover fade, the knob
+    /// spring, [`Joystick::hold_time`], [`Joystick::seconds_since_last_activity`] and the
+    /// usage statistics. The input is processed as usual, so a scale of `0.0` freezes all
+    /// timers and animations while the knob still follows the pointer.
+    ///
+    /// Leave this at `1.0` to keep the joystick in real time while the game is slowed down,
+    /// or pass the already scaled time to [`Joystick::update_with_dt`] instead.
+    ///
+    /// Defaults to `1.0`. Negative scales (including NaN) are clamped to `0.0`.
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale.max(0.);
+    }
+
+    /// update the joystick with the given frame time
+    ///
+    /// this is the same as [`Joystick::update`] but uses `dt` (in seconds) as the
+    /// time since the last update instead of [`macroquad::prelude::get_frame_time()`]
+    ///
+    /// Unlike [`Joystick::update`], every call is processed, e.g. for fixed time steps.
+    /// It's the job of the caller to pass each `dt` only once.
+    pub fn update_with_dt(&mut self, dt: f32) -> JoystickEvent {
+        self.update_with_input(&MacroquadInput, dt)
+    }
+
+    /// update the joystick from the given input
+    ///
+    /// this is the same as [`Joystick::update_with_dt`] but reads the touches and the mouse
+    /// from `input` instead of macroquad
+    ///
+    /// # Examples
+    /// see [`SimulatedInput`]
+    pub fn update_with_input(&mut self, input: &impl JoystickInput, dt: f32) -> JoystickEvent {
+        let dt = dt * self.time_scale;
+        if !self.enabled {
+            // a button which is held while the joystick is enabled doesn't grab it
+            self.mouse_was_down = input.is_mouse_down();
+            return self.output;
+        }
+        // flags of the last update which are only set for one update
+        self.raw_event.released_in_outer_ring = false;
+        self.raw_event.source = self.source;
+        let was_dragging = self.dragging;
+        let was_active = self.dragging || self.external_active;
+        let last_direction = self.output.direction;
+        let rendered_offset = self.rendered_knob_offset();
+        self.grabbed = false;
+
+        let touches = input.touches();
+        let mouse_down = input.is_mouse_down();
+        let mouse_pressed = mouse_down && !self.mouse_was_down;
+        self.mouse_was_down = mouse_down;
+        if !mouse_down {
+            self.mouse_suppressed = false;
+        }
+        let key_offset = self.key_offset(input);
+        let (external_offset, external_source) = if key_offset == Vec2::ZERO {
+            (self.stick_offset(input), InputSource::Gamepad)
+        } else {
+            (key_offset.normalize(), InputSource::Keyboard)
+        };
+        if touches.is_empty() && self.is_quiescent() && !mouse_down && external_offset == Vec2::ZERO
+        {
+            let mouse = self.to_local(input.mouse_position());
+            if !self.is_over(mouse) {
+                self.output.phase = JoystickPhase::Idle;
+                self.output.just_pressed = false;
+                self.output.just_released = false;
+                self.output.direction_changed = false;
+                self.update_idle_time(false, dt);
+                return self.output;
+            }
+        }
+
+        if let Some(attachment) = &self.attachment {
+            // attached joysticks are not mirrored
+            let center = self.inverse_transform.transform_point2(attachment());
+            self.move_center(center);
+        }
+
+        if touches.is_empty() {
+            if self.mouse_suppressed {
+                self.hovered = false;
+            } else {
+                self.update_mouse(input, mouse_pressed);
+            }
+        } else {
+            self.hovered = false;
+            if self.touch_synthesizes_mouse {
+                self.mouse_suppressed = true;
+                self.adopt_mouse_drag(&touches);
+            }
+            self.update_touch(touches);
+        }
+        if self.dragging {
+            // a pointer takes precedence over the keys
+            self.external_active = false;
+        } else if !was_dragging {
+            // the keys take over in the frame after the release of a pointer
+            self.update_external(external_offset, external_source);
+        }
+        if self.dragging && !self.grabbed {
+            self.update_smoothing(dt);
+        }
+        if self.dragging || self.external_active {
+            self.hold_time += dt;
+        }
+        if self.dragging {
+            self.record_flick_sample();
+        }
+        self.update_hover_fade(dt);
+        self.update_knob_return(was_active, rendered_offset, dt);
+        self.update_knob_spring(dt);
+
+        // post-process the event of the input
+        let mut event = self.raw_event;
+        self.apply_mirror_output(&mut event);
+        self.apply_rim_persistence(&mut event, dt);
+        self.apply_direction_hysteresis(&mut event);
+        self.apply_turbo(&mut event, dt);
+        self.apply_band(&mut event);
+        // a latched release is not a release
+        let latched = self.locked && !self.dragging;
+        event.flick = if was_dragging && !self.dragging && !latched {
+            self.detect_flick()
+        } else {
+            None
+        };
+        let active = self.dragging || self.external_active;
+        event.phase = if active && (self.grabbed || !was_active) {
+            JoystickPhase::Start
+        } else if active {
+            JoystickPhase::Drag
+        } else if latched {
+            if self.grabbed {
+                JoystickPhase::Start
+            } else {
+                JoystickPhase::Idle
+            }
+        } else if self.grabbed || was_active {
+            JoystickPhase::Release
+        } else {
+            JoystickPhase::Idle
+        };
+        event.duration = if active || was_active || self.grabbed {
+            self.hold_time
+        } else {
+            0.
+        };
+        event.just_pressed = event.phase == JoystickPhase::Start
+            || (event.phase == JoystickPhase::Release && self.grabbed);
+        event.just_released = event.phase == JoystickPhase::Release;
+        event.direction_changed = event.direction != last_direction;
+        self.output = event;
+
+        self.update_repeat(dt);
+        if self.swipe_interpolation.is_some() && (was_dragging || self.dragging) {
+            push_capped(&mut self.events, self.output);
+        }
+        self.update_idle_time(was_active || active || self.grabbed, dt);
+        if let Some(stats) = &mut self.stats {
+            stats.record(was_dragging, self.dragging, &self.raw_event, dt);
+        }
+        self.trace(was_dragging, last_direction);
+        self.notify_feedback(last_direction);
+        self.output
+    }
+
+    /// move the knob to the center while dragging and report an idle event
+    fn center_knob(&mut self) {
+        self.knob.x = self.center.x;
+        self.knob.y = self.center.y;
+        self.raw_event = JoystickEvent {
+            source: self.source,
+            ..JoystickEvent::default()
+        };
+    }
+
+    /// move the knob according to the drag position and update the raw event
+    fn moving(&mut self, position: Vec2) {
+        self.drag_position = position;
+        let radius = self.travel_radius;
+        let delta = self.axis.constrain(position - self.center);
+        if radius.is_nan() || radius <= 0. || !delta.is_finite() {
+            // a degenerate joystick or position can't produce a meaningful event
+            self.center_knob();
+            return;
+        }
+        if !self.committed {
+            if (position - self.press_position).length() < self.commit_distance {
+                self.center_knob();
+                return;
+            }
+            self.committed = true;
+        }
+        let delta = if self.follow && delta.length() > radius {
+            // slide the joystick so the pointer is at the rim
+            let position = self.center + delta;
+            let mut center = position - delta.normalize() * radius;
+            if let Some(bounds) = self.follow_bounds {
+                center = center.clamp(bounds.point(), bounds.point() + bounds.size());
+            }
+            self.move_center(center);
+            position - center
+        } else {
+            delta
+        };
+        let scaled = delta * self.axis_scale;
+        let angle = scaled.y.atan2(scaled.x);
+
+        // maximum distance for the knob is the travel radius
+        let dist = f32::min(scaled.length(), radius);
+
+        let (knob_angle, knob_dist) = if self.scale_knob {
+            (angle, dist)
+        } else {
+            (delta.y.atan2(delta.x), f32::min(delta.length(), radius))
+        };
+        let knob_angle = self.snap(knob_angle);
+        self.knob.x = self.center.x + knob_dist * knob_angle.cos();
+        self.knob.y = self.center.y + knob_dist * knob_angle.sin();
+
+        let intensity = self.apply_intensity_response(dist / radius);
+        let (intensity, angle) = self.apply_axis_response(intensity, angle);
+        let angle = self.snap(angle);
+        self.raw_event = JoystickEvent::new(intensity, angle, self.resolver);
+        self.raw_event.source = self.source;
+        if let Some(outer_ring) = self.outer_ring {
+            let pointer_dist = delta.length();
+            self.raw_event.in_outer_ring =
+                pointer_dist > radius && pointer_dist <= outer_ring.radius;
+        }
+
+        if self.track_rotation {
+            self.update_rotation(angle, intensity);
+        }
+    }
+
+    /// accumulate the change of the angle into the total rotation
+    fn update_rotation(&mut self, angle: f32, intensity: f32) {
+        if intensity <= self.rotation_min_intensity {
+            // don't count the jump when the knob comes back from the center
+            self.last_rotation_angle = None;
+        } else {
+            if let Some(last_angle) = self.last_rotation_angle {
+                let mut delta = angle - last_angle;
+                // take the shorter way around the seam at ±π
+                if delta > PI {
+                    delta -= 2. * PI;
+                } else if delta < -PI {
+                    delta += 2. * PI;
+                }
+                self.total_rotation += delta;
+            }
+            self.last_rotation_angle = Some(angle);
+        }
+        self.raw_event.total_rotation = self.total_rotation;
+    }
+}
+
+/// state of the [`Joystick`] which is passed to the custom renderers
+///
+/// see [`Joystick::from_custom_renderers`]
+#[derive(Clone, Copy, Debug)]
+pub struct JoystickRenderState {
+    /// whether the joystick is dragged or its direction is locked, see [`Joystick::is_active`]
+    pub active: bool,
+    /// the intensity of the last event
+    pub intensity: f32,
+    /// the angle of the last event
+    pub angle: f32,
+    /// the direction of the last event
+    pub direction: JoystickDirection,
+    /// the factor of the alpha of the colors, see [`Joystick::set_auto_fade`]
+    pub opacity: f32,
+}
+
+/// declarative look of the [`Joystick`]
+///
+/// see [`Joystick::with_style`] and [`Joystick::set_style`]
+#[derive(Clone, Copy, Debug)]
+pub struct JoystickStyle {
+    /// the color of the background, also the tint of its texture
+    pub background_color: Color,
+    /// the color of the knob, also the tint of its texture
+    pub knob_color: Color,
+    /// the texture of the background, drawn instead of the circle
+    pub background_texture: Option<Texture2D>,
+    /// the texture of the knob, drawn instead of the circle
+    pub knob_texture: Option<Texture2D>,
+    /// the part of the background texture to draw, e.g. in a texture atlas
+    pub background_source: Option<Rect>,
+    /// the part of the knob texture to draw, e.g. in a texture atlas
+    pub knob_source: Option<Rect>,
+    /// the diameter of the knob relative to the size of the joystick
+    pub knob_scale: f32,
+    /// the factor of the alpha of the colors while the joystick is idle
+    pub idle_alpha: f32,
+    /// the factor of the alpha of the colors while the joystick is active
+    /// (see [`Joystick::is_active`])
+    pub active_alpha: f32,
+    /// the color of the border around the background
+    pub border_color: Color,
+    /// the thickness of the border around the background, `0.0` draws no border
+    pub border_width: f32,
+    /// the color of the hover outline, see [`Joystick::set_hover_color`]
+    pub hover_color: Option<Color>,
+}
+
+impl Default for JoystickStyle {
+    /// the look of [`Joystick::new`]
+    fn default() -> Self {
+        Self {
+            background_color: BACKGROUND_COLOR,
+            knob_color: KNOB_COLOR,
+            background_texture: None,
+            knob_texture: None,
+            background_source: None,
+            knob_source: None,
+            knob_scale: 0.5,
+            idle_alpha: 1.,
+            active_alpha: 1.,
+            border_color: KNOB_COLOR,
+            border_width: 0.,
+            hover_color: None,
+        }
+    }
+}
+
+impl JoystickStyle {
+    /// the style of the textures without a tint
+    ///
+    /// the textures are centered and scaled to the diameters of the background and the knob
+    pub fn textured(background: Texture2D, knob: Texture2D) -> Self {
+        Self {
+            background_color: WHITE,
+            knob_color: WHITE,
+            background_texture: Some(background),
+            knob_texture: Some(knob),
+            ..Self::default()
+        }
+    }
+
+    fn draw_background(&self, element: &JoystickElement, transform: &Mat3, alpha: f32) {
+        let color = Color {
+            a: self.background_color.a * alpha,
+            ..self.background_color
+        };
+        draw_styled(
+            element,
+            transform,
+            color,
+            self.background_texture,
+            self.background_source,
+        );
+    }
+
+    fn draw_knob(&self, element: &JoystickElement, transform: &Mat3, alpha: f32) {
+        let color = Color {
+            a: self.knob_color.a * alpha,
+            ..self.knob_color
+        };
+        draw_styled(
+            element,
+            transform,
+            color,
+            self.knob_texture,
+            self.knob_source,
+        );
+    }
+}
+
+/// draw the element as a circle or a texture which is centered and scaled to the diameter
+fn draw_styled(
+    element: &JoystickElement,
+    transform: &Mat3,
+    color: Color,
+    texture: Option<Texture2D>,
+    source: Option<Rect>,
+) {
+    let center = transform.transform_point2(Vec2::new(element.x, element.y));
+    let radius = element.radius * transform.determinant().abs().sqrt();
+    match texture {
+        Some(texture) => draw_texture_ex(
+            texture,
+            center.x - radius,
+            center.y - radius,
+            color,
+            DrawTextureParams {
+                dest_size: Some(Vec2::splat(radius * 2.)),
+                source,
+                ..Default::default()
+            },
+        ),
+        None => draw_circle(center.x, center.y, radius, color),
+    }
+}
+
+/// axes on which the knob of the [`Joystick`] moves
+///
+/// see [`Joystick::set_axis`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoystickAxis {
+    /// both axes
+    #[default]
+    Both,
+    /// only the horizontal axis
+    Horizontal,
+    /// only the vertical axis
+    Vertical,
+}
+
+impl JoystickAxis {
+    /// drop the component of the offset which is not on the axes
+    fn constrain(self, offset: Vec2) -> Vec2 {
+        match self {
+            Self::Both => offset,
+            Self::Horizontal => Vec2::new(offset.x, 0.),
+            Self::Vertical => Vec2::new(0., offset.y),
+        }
+    }
+}
+
+/// corner or edge of the screen to which a [`Joystick`] is anchored
+///
+/// see [`Joystick::anchored`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    /// the top left corner
+    TopLeft,
+    /// the top right corner
+    TopRight,
+    /// the bottom left corner
+    BottomLeft,
+    /// the bottom right corner
+    BottomRight,
+    /// the middle of the top edge
+    TopCenter,
+    /// the middle of the bottom edge
+    BottomCenter,
+    /// the middle of the left edge
+    CenterLeft,
+    /// the middle of the right edge
+    CenterRight,
+}
+
+impl Anchor {
+    /// the position at `offset` from the anchor on a screen with the size `screen`
+    ///
+    /// the offset points into the screen, so both components should be positive; along an
+    /// edge, the offset moves away from the middle to the right or the bottom
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::Anchor;
+    ///
+    /// let screen = Vec2::new(800.0, 600.0);
+    /// let offset = Vec2::new(100.0, 50.0);
+    /// assert_eq!(Anchor::BottomLeft.position(offset, screen), Vec2::new(100.0, 550.0));
+    /// assert_eq!(Anchor::TopRight.position(offset, screen), Vec2::new(700.0, 50.0));
+    /// assert_eq!(Anchor::BottomCenter.position(offset, screen), Vec2::new(500.0, 550.0));
+    /// ```
+    pub fn position(self, offset: Vec2, screen: Vec2) -> Vec2 {
+        match self {
+            Self::TopLeft => offset,
+            Self::TopRight => Vec2::new(screen.x - offset.x, offset.y),
+            Self::BottomLeft => Vec2::new(offset.x, screen.y - offset.y),
+            Self::BottomRight => screen - offset,
+            Self::TopCenter => Vec2::new(screen.x / 2. + offset.x, offset.y),
+            Self::BottomCenter => Vec2::new(screen.x / 2. + offset.x, screen.y - offset.y),
+            Self::CenterLeft => Vec2::new(offset.x, screen.y / 2. + offset.y),
+            Self::CenterRight => Vec2::new(screen.x - offset.x, screen.y / 2. + offset.y),
+        }
+    }
+}
+
+/// keys which control the [`Joystick`], see [`Joystick::bind_keys`]
+#[derive(Clone, Copy, Debug)]
+struct KeyBindings {
+    up: KeyCode,
+    left: KeyCode,
+    down: KeyCode,
+    right: KeyCode,
+}
+
+/// mode of the [`Joystick`]
+///
+/// see [`Joystick::set_mode`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoystickMode {
+    /// the joystick stays at its center
+    #[default]
+    Fixed,
+    /// the joystick is spawned under a press inside the activation area
+    Floating {
+        /// the area in which a press spawns the joystick, in the coordinates of the joystick
+        #[cfg_attr(feature = "serde", serde(with = "config::RectDef"))]
+        activation: Rect,
+        /// whether the joystick moves back to its center after the release
+        return_on_release: bool,
+    },
+}
+
+/// appearance of the [`Joystick`] while it is idle
+///
+/// see [`Joystick::set_idle_appearance`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IdleAppearance {
+    /// draw the background and the knob
+    Full,
+    /// only draw an outline of the background
+    Outline {
+        /// the thickness of the outline
+        thickness: f32,
+        /// the color of the outline
+        color: Color,
+    },
+    /// draw nothing
+    Hidden,
+}
+
+/// fading of the [`Joystick`] while it isn't used
+///
+/// see [`Joystick::set_auto_fade`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AutoFade {
+    /// the seconds without activity before the joystick starts to fade
+    pub delay: f32,
+    /// the seconds of the fade
+    pub duration: f32,
+    /// the opacity after the fade, `0.0` hides the joystick
+    pub min_opacity: f32,
+}
+
+/// secondary ring around the background of the [`Joystick`]
+///
+/// see [`Joystick::set_outer_ring`]
+#[derive(Clone, Copy, Debug)]
+pub struct OuterRing {
+    /// the radius of the ring, should be bigger than the travel radius
+    pub radius: f32,
+    /// the color of the ring
+    pub color: Color,
+}
+
+/// text label which is drawn under the [`Joystick`]
+///
+/// see [`Joystick::set_label`]
+#[derive(Clone, Debug)]
+pub struct JoystickLabel {
+    /// the text of the label
+    pub text: String,
+    /// the font size of the text
+    pub font_size: f32,
+    /// the color of the text
+    pub color: Color,
+    /// offset of the text from its default position directly under the background
+    pub offset: Vec2,
+    /// the font of the text, uses the default font of macroquad if `None`
+    pub font: Option<Font>,
+}
+
+impl JoystickLabel {
+    /// create a new label with the given text
+    ///
+    /// the label has a font size of 20, the color of the knob, no offset and the default font
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            font_size: 20.,
+            color: KNOB_COLOR,
+            offset: Vec2::ZERO,
+            font: None,
+        }
+    }
+}
+
+/// turbo of the intensity of the [`Joystick`]
+///
+/// see [`Joystick::set_turbo`]
+#[derive(Clone, Copy, Debug)]
+pub struct Turbo {
+    /// the intensity from which the turbo starts
+    pub threshold: f32,
+    /// the increase of the intensity per second
+    pub rate_per_sec: f32,
+    /// the maximum intensity
+    pub max: f32,
+}
+
+/// configuration of the flick detection
+///
+/// see [`Joystick::set_flick`]
+#[derive(Clone, Copy, Debug)]
+pub struct Flick {
+    /// the minimum speed of the knob (in travel radii per second)
+    pub min_speed: f32,
+    /// the duration (in seconds) before the release over which the speed is measured
+    pub window: f32,
+}
+
+/// flick which was detected on the release
+///
+/// see [`Joystick::set_flick`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlickEvent {
+    /// the direction of the flick
+    pub direction: JoystickDirection,
+    /// the angle of the flick, like [`JoystickEvent::angle`]
+    pub angle: f32,
+    /// the speed of the knob (in travel radii per second)
+    pub speed: f32,
+}
+
+/// filter for short idle dips while dragging along the rim
+///
+/// see [`Joystick::set_rim_persistence`]
+#[derive(Clone, Copy, Debug)]
+pub struct RimPersistence {
+    /// the minimum intensity of the last event to hold it
+    pub min_intensity: f32,
+    /// the maximum number of updates a dip is filtered
+    pub max_frames: u32,
+    /// the maximum duration (in seconds) a dip is filtered
+    pub max_duration: f32,
+}
+
+/// interpolation of fast swipes
+///
+/// see [`Joystick::set_swipe_interpolation`]
+#[derive(Clone, Copy, Debug)]
+pub struct SwipeInterpolation {
+    /// the maximum distance between two samples
+    pub max_spacing: f32,
+    /// the maximum number of samples between two updates
+    pub max_samples: usize,
+}
+
+/// curve which maps the input of an axis to its output
+///
+/// The curves are symmetric: they are applied to the absolute value and keep the sign.
+///
+/// see [`Joystick::set_axis_response`]
+#[derive(Clone, Copy, Debug)]
+pub enum ResponseCurve {
+    /// the output is the input
+    Linear,
+    /// the input raised to the given power, e.g. `2.0` for finer control near the center
+    Power(f32),
+    /// an exponential curve with the given steepness, `(e^(k * x) - 1) / (e^k - 1)`
+    ///
+    /// a positive steepness gives finer control near the center, a steepness of `0.0` is
+    /// linear
+    Exponential(f32),
+    /// a custom curve which maps `0.0..=1.0` to `0.0..=1.0`
+    Custom(fn(f32) -> f32),
+}
+
+impl ResponseCurve {
+    /// apply the curve to a value from `-1.0` to `1.0`
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::ResponseCurve;
+    ///
+    /// assert_eq!(ResponseCurve::Linear.apply(-0.5), -0.5);
+    /// assert_eq!(ResponseCurve::Power(2.0).apply(-0.5), -0.25);
+    /// assert_eq!(ResponseCurve::Exponential(3.0).apply(1.0), 1.0);
+    /// assert_eq!(ResponseCurve::Custom(|v| v.sqrt()).apply(0.25), 0.5);
+    /// ```
+    pub fn apply(&self, value: f32) -> f32 {
+        let magnitude = value.abs();
+        let output = match self {
+            Self::Linear => return value,
+            Self::Power(exponent) => magnitude.powf(*exponent),
+            Self::Exponential(steepness) if steepness.abs() < f32::EPSILON => magnitude,
+            Self::Exponential(steepness) => (steepness * magnitude).exp_m1() / steepness.exp_m1(),
+            Self::Custom(curve) => curve(magnitude),
+        };
+        output.copysign(value)
+    }
+}
+
+/// usage statistics of a [`Joystick`]
+///
+/// see [`Joystick::set_stats`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JoystickStats {
+    /// the number of updates in which the knob was held at an offset
+    ///
+    /// The offset relative to the radius (like [`JoystickEvent::axes`]) is divided into
+    /// [`JoystickStats::BINS`] bins per axis, indexed by `[y][x]` from the top left.
+    pub histogram: [[u32; JoystickStats::BINS]; JoystickStats::BINS],
+    /// the total time (in seconds) the joystick was dragged
+    pub drag_time: f32,
+    /// the number of drags
+    pub grabs: u32,
+    /// the number of drags which were released within [`JoystickStats::TAP_TIME`]
+    pub taps: u32,
+    /// the total time (in seconds) the knob was held at the rim
+    pub edge_time: f32,
+    /// the duration of the current drag
+    current_drag_time: f32,
+}
+
+impl JoystickStats {
+    /// the number of bins per axis of the histogram
+    pub const BINS: usize = 16;
+    /// the maximum duration (in seconds) of a drag which is counted as a tap
+    pub const TAP_TIME: f32 = 0.2;
+
+    /// record an update of the joystick
+    fn record(&mut self, was_dragging: bool, dragging: bool, event: &JoystickEvent, dt: f32) {
+        if dragging {
+            if !was_dragging {
+                self.grabs += 1;
+                self.current_drag_time = 0.;
+            }
+            self.drag_time += dt;
+            self.current_drag_time += dt;
+            if event.intensity >= 1. {
+                self.edge_time += dt;
+            }
+            let bin = |value: f32| {
+                let bin = (value + 1.) / 2. * Self::BINS as f32;
+                (bin.max(0.) as usize).min(Self::BINS - 1)
+            };
+            let axes = event.axes();
+            self.histogram[bin(axes.y)][bin(axes.x)] += 1;
+        } else if was_dragging && self.current_drag_time < Self::TAP_TIME {
+            self.taps += 1;
+        }
+    }
+}
+
+/// spring of the rendered knob
+///
+/// see [`Joystick::set_knob_spring`]
+#[derive(Clone, Copy, Debug)]
+pub struct Spring {
+    /// the acceleration towards the knob per pixel of distance
+    pub stiffness: f32,
+    /// the deceleration per pixel per second of velocity
+    pub damping: f32,
+}
+
+impl Spring {
+    /// run `step` in fixed time steps of at most `SPRING_MAX_STEP` seconds for `dt` seconds
+    ///
+    /// `dt` is capped at `SPRING_MAX_TIME`, a negative or NaN `dt` doesn't step at all
+    pub(crate) fn integrate(self, dt: f32, mut step: impl FnMut(f32)) {
+        let mut remaining = if dt > 0. { dt.min(SPRING_MAX_TIME) } else { 0. };
+        while remaining > 0. {
+            let time = remaining.min(SPRING_MAX_STEP);
+            remaining -= time;
+            step(time);
+        }
+    }
+}
+
+/// curve of the return animation of the knob
+///
+/// see [`Joystick::set_return_easing`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    /// a constant speed
+    Linear,
+    /// decelerate with a quadratic
+    QuadOut,
+    /// decelerate with a cubic
+    #[default]
+    CubicOut,
+    /// overshoot the center by about 10% and settle back
+    BackOut,
+}
+
+impl Easing {
+    /// the progress of the animation at `t` (from 0 to 1) of its duration
+    ///
+    /// starts at `0.0` and ends at `1.0`, [`Easing::BackOut`] exceeds `1.0` in between
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::Easing;
+    ///
+    /// assert_eq!(Easing::Linear.progress(0.25), 0.25);
+    /// assert_eq!(Easing::CubicOut.progress(0.5), 0.875);
+    /// assert!(Easing::BackOut.progress(0.8) > 1.0);
+    /// ```
+    pub fn progress(self, t: f32) -> f32 {
+        /// the overshoot of the back easing
+        const BACK: f32 = 1.70158;
+
+        let t = t.clamp(0., 1.);
+        let remaining = 1. - t;
+        match self {
+            Self::Linear => t,
+            Self::QuadOut => 1. - remaining.powi(2),
+            Self::CubicOut => 1. - remaining.powi(3),
+            Self::BackOut => 1. + (BACK + 1.) * (t - 1.).powi(3) + BACK * (t - 1.).powi(2),
+        }
+    }
+}
+
+/// the runtime state of a [`Joystick`]
+///
+/// see [`Joystick::save_state`]
+///
+/// With the `serde` feature, the state can be serialized, e.g. across hot reloads.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JoystickRuntimeState {
+    size: f32,
+    dragging: bool,
+    touch_id: u64,
+    source: InputSource,
+    knob_offset: Vec2,
+    raw_event: JoystickEvent,
+    output: JoystickEvent,
+    total_rotation: f32,
+    last_rotation_angle: Option<f32>,
+    repeat_direction: JoystickDirection,
+    repeat_timer: f32,
+    repeats: Vec<JoystickDirection>,
+    turbo_charge: f32,
+    hovered: bool,
+    hover_fade: f32,
+    locked: bool,
+    band: u8,
+    rim_event: Option<JoystickEvent>,
+    rim_dip_frames: u32,
+    rim_dip_time: f32,
+    idle_time: f32,
+    idle_timeout_fired: bool,
+    spring_offset: Vec2,
+    spring_velocity: Vec2,
+    press_offset: Vec2,
+    committed: bool,
+    hold_time: f32,
+    drag_offset: Vec2,
+    smoothing_offset: Vec2,
+    return_from: Vec2,
+    return_elapsed: f32,
+    events: Vec<JoystickEvent>,
+    mouse_suppressed: bool,
+    mouse_was_down: bool,
+    flick_samples: Vec<(f32, Vec2)>,
+    external_active: bool,
+    at_edge: bool,
+}
+
+/// configuration of the repeat events, see [`Joystick::set_repeat`]
+#[derive(Clone, Copy, Debug)]
+struct Repeat {
+    initial_delay: f32,
+    interval: f32,
+}
+
+/// the callbacks of [`Joystick::on_press`], [`Joystick::on_drag`] and [`Joystick::on_release`]
+#[derive(Default)]
+struct EventCallbacks {
+    press: Option<EventCallback>,
+    drag: Option<EventCallback>,
+    release: Option<EventCallback>,
+}
+
+impl JoystickFeedback for EventCallbacks {
+    fn on_start(&mut self, event: &JoystickEvent) {
+        if let Some(callback) = &mut self.press {
+            callback(event);
+        }
+    }
+
+    fn on_drag(&mut self, event: &JoystickEvent) {
+        if let Some(callback) = &mut self.drag {
+            callback(event);
+        }
+    }
+
+    fn on_release(&mut self, event: &JoystickEvent) {
+        if let Some(callback) = &mut self.release {
+            callback(event);
+        }
+    }
+}
+
+/// call the hooks of the receiver for the event of an update
+fn notify(
+    receiver: &mut dyn JoystickFeedback,
+    event: &JoystickEvent,
+    last_direction: JoystickDirection,
+    reached_edge: bool,
+) {
+    if event.just_pressed {
+        receiver.on_start(event);
+    }
+    if event.phase == JoystickPhase::Drag {
+        receiver.on_drag(event);
+    }
+    if event.direction_changed {
+        receiver.on_direction_change(last_direction, event);
+    }
+    if reached_edge {
+        receiver.on_edge_reach(event);
+    }
+    if event.phase == JoystickPhase::Release {
+        receiver.on_release(event);
+    }
+}
+
+/// the callback of [`Joystick::on_idle_timeout`]
+struct IdleTimeout {
+    secs: f32,
+    callback: Box<dyn FnMut()>,
+    /// whether the callback was called since the last activity
+    fired: bool,
+}
+
+impl fmt::Debug for Joystick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Joystick")
+            .field("id", &self.id)
+            .field("center", &self.center)
+            .field("size", &self.size)
+            .field("dragging", &self.dragging)
+            .field("event", &self.output)
+            .finish_non_exhaustive()
+    }
+}
+
+/// element of the [`Joystick`]
+///
+/// can be used for the background or the knob
+struct JoystickElement {
+    x: f32,
+    y: f32,
+    radius: f32,
+    drawable: RefCell<JoystickRenderer>,
+    /// whether the drawable always draws the same and can be cached in a texture
+    cacheable: bool,
+    cache: RefCell<Option<RenderTarget>>,
+}
+
+impl JoystickElement {
+    fn new(x: f32, y: f32, radius: f32, drawable: Box<dyn Fn(f32, f32, f32)>) -> Self {
+        Self::with_state(
+            x,
+            y,
+            radius,
+            Box::new(move |x, y, radius, _| drawable(x, y, radius)),
+        )
+    }
+
+    /// create an element whose drawable gets the state of the joystick
+    fn with_state(x: f32, y: f32, radius: f32, drawable: JoystickRenderer) -> Self {
+        Self {
+            x,
+            y,
+            radius,
+            drawable: RefCell::new(drawable),
+            cacheable: false,
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// mark the element as cacheable
+    fn cacheable(mut self) -> Self {
+        self.cacheable = true;
+        self
+    }
+
+    /// render the element
+    ///
+    /// the center is transformed by `transform` and the radius is scaled
+    /// by the average scale of the transform
+    pub fn render(&self, transform: &Mat3, state: JoystickRenderState) {
+        let center = transform.transform_point2(Vec2::new(self.x, self.y));
+        let scale = transform.determinant().abs().sqrt();
+        (self.drawable.borrow_mut())(center.x, center.y, self.radius * scale, state);
+    }
+
+    /// render the element from the cached texture
+    ///
+    /// the texture is (re)drawn if it doesn't exist yet or `dirty` is set.
+    /// falls back to [`JoystickElement::render`] if the element is not cacheable
+    pub fn render_cached(&self, transform: &Mat3, dirty: bool, state: JoystickRenderState) {
+        if !self.cacheable {
+            self.render(transform, state);
+            return;
+        }
+        let center = transform.transform_point2(Vec2::new(self.x, self.y));
+        let radius = self.radius * transform.determinant().abs().sqrt();
+        // one pixel padding on each side so the edge isn't cut off
+        let size = (radius * 2.).ceil() + 2.;
+
+        let mut cache = self.cache.borrow_mut();
+        if dirty || cache.is_none() {
+            if let Some(old) = cache.take() {
+                old.delete();
+            }
+            let target = render_target(size as u32, size as u32);
+            push_camera_state();
+            set_camera(&Camera2D {
+                render_target: Some(target),
+                ..Camera2D::from_display_rect(Rect::new(0., 0., size, size))
+            });
+            clear_background(BLANK);
+            // the opacity is applied when the cache is drawn
+            let state = JoystickRenderState {
+                opacity: 1.,
+                ..state
+            };
+            (self.drawable.borrow_mut())(size / 2., size / 2., radius, state);
+            pop_camera_state();
+            *cache = Some(target);
+        }
+        if let Some(target) = *cache {
+            draw_texture_ex(
+                target.texture,
+                center.x - size / 2.,
+                center.y - size / 2.,
+                Color::new(1., 1., 1., state.opacity),
+                DrawTextureParams {
+                    dest_size: Some(Vec2::new(size, size)),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+}
+
+/// phase of the interaction with the [`Joystick`] in an update
+///
+/// see [`JoystickEvent::phase`]
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::Vec2;
+/// use macroquad_virtual_joystick::{Joystick, JoystickPhase, SimulatedInput};
+///
+/// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+/// let mut phase = |down: bool| {
+///     let input = SimulatedInput::new().with_mouse(Vec2::new(110.0, 200.0), down);
+///     joystick.update_with_input(&input, 0.0).phase
+/// };
+/// assert_eq!(phase(true), JoystickPhase::Start);
+/// assert_eq!(phase(true), JoystickPhase::Drag);
+/// assert_eq!(phase(false), JoystickPhase::Release);
+/// assert_eq!(phase(false), JoystickPhase::Idle);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoystickPhase {
+    /// the joystick was grabbed in the update
+    ///
+    /// this is also reported if the joystick was handed off to another touch
+    /// (see [`Joystick::set_touch_handoff`])
+    Start,
+    /// the joystick is dragged since an earlier update
+    Drag,
+    /// the joystick was released in the update
+    ///
+    /// this is also reported if it was grabbed and released in the same update
+    Release,
+    /// the joystick is not dragged
+    Idle,
+}
+
+#[allow(missing_docs)]
+/// different directions of the [`Joystick`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoystickDirection {
+    Up,
+    UpLeft,
+    Left,
+    DownLeft,
+    Down,
+    DownRight,
+    Right,
+    UpRight,
+    Idle,
+}
+
+impl JoystickDirection {
+    /// calculate a JoystickDirection from degrees
+    ///
+    /// 0 degrees are on the positive X-Axis and then it rotates clockwise
+    ///
+    /// this quantizes to all eight directions, see [`DirectionResolver::direction_from_degrees`]
+    /// for other granularities
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad_virtual_joystick::JoystickDirection;
+    ///
+    /// let degrees = 153.5;
+    /// let direction = JoystickDirection::from_degrees(degrees);
+    ///
+    /// assert_eq!(direction, JoystickDirection::DownLeft);
+    /// ```
+    pub fn from_degrees(degrees: f64) -> Self {
+        if degrees > -22.5 && degrees <= 22.5 {
+            Self::Right
+        } else if degrees > 22.5 && degrees <= 67.5 {
+            Self::DownRight
+        } else if degrees > 67.5 && degrees <= 112.5 {
+            Self::Down
+        } else if degrees > 112.5 && degrees <= 157.5 {
+            Self::DownLeft
+        } else if (degrees > 157.5 && degrees <= 180.) || (degrees > -180. && degrees <= -157.5) {
+            Self::Left
+        } else if degrees > -157.5 && degrees <= -112.5 {
+            Self::UpLeft
+        } else if degrees > -112.5 && degrees <= -67.5 {
+            Self::Up
+        } else if degrees > -67.5 && degrees <= -22.5 {
+            Self::UpRight
+        } else {
+            Self::Idle
+        }
+    }
+
+    /// calculate a JoystickDirection from radians
+    ///
+    /// this uses the same convention as [`JoystickEvent::angle`]
+    /// and is the same as [`JoystickDirection::from_degrees`]
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f32::consts::FRAC_PI_2;
+    /// use macroquad_virtual_joystick::JoystickDirection;
+    ///
+    /// assert_eq!(JoystickDirection::from_radians(-FRAC_PI_2), JoystickDirection::Up);
+    /// ```
+    pub fn from_radians(radians: f32) -> Self {
+        Self::from_degrees(radians.to_degrees() as f64)
+    }
+
+    /// convert the direction to a Vec2 with x and y
+    ///
+    /// x and y are both one of these: [-1.0, 0.0, 1.0]
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::JoystickDirection;
+    ///
+    /// let direction = JoystickDirection::Up;
+    /// assert_eq!(direction.to_local(), Vec2::new(0.0, -1.0))
+    /// ```
+    pub fn to_local(&self) -> Vec2 {
+        let (x, y) = match self {
             Self::Right => (1., 0.),
             Self::DownRight => (1., 1.),
             Self::Down => (0., 1.),
@@ -381,10 +5145,16 @@ impl JoystickDirection {
 ///
 /// call [`Joystick::update`] to get the current event
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct JoystickEvent {
     /// the direction to which the knob was moved
     pub direction: JoystickDirection,
 
+    /// the sector of the angle, see [`Joystick::set_direction_resolver`]
+    ///
+    /// `0` if the joystick is idle
+    pub sector: u16,
+
     /// the intensity of the knob move, from 0 (center) to 1 (edge)
     pub intensity: f32,
 
@@ -392,14 +5162,298 @@ pub struct JoystickEvent {
     ///
     /// starting on the positive x-axis and rotating counter-clockwise
     pub angle: f32,
+
+    /// the accumulated rotation of the knob (in radians)
+    ///
+    /// this is only tracked if enabled with [`Joystick::set_track_rotation`],
+    /// otherwise it is always `0`
+    pub total_rotation: f32,
+
+    /// whether the pointer is beyond the background inside the [`OuterRing`]
+    pub in_outer_ring: bool,
+
+    /// whether the drag was released inside the [`OuterRing`]
+    ///
+    /// this is only set for the update in which the joystick was released
+    pub released_in_outer_ring: bool,
+
+    /// the source of the input which controls the joystick
+    ///
+    /// this is also reported in the update in which the joystick was released
+    /// and [`InputSource::None`] when the joystick is idle
+    pub source: InputSource,
+
+    /// the additional intensity of the turbo, see [`Joystick::set_turbo`]
+    ///
+    /// this is already included in [`JoystickEvent::intensity`]
+    pub charge: f32,
+
+    /// the band of the intensity, see [`Joystick::set_bands`]
+    ///
+    /// `0` if the joystick is idle
+    pub band: u8,
+
+    /// the phase of the interaction in the update, e.g. to fire on the release
+    pub phase: JoystickPhase,
+
+    /// the flick of the drag, see [`Joystick::set_flick`]
+    ///
+    /// this is only set for the update in which the joystick was released
+    pub flick: Option<FlickEvent>,
+
+    /// how long (in seconds) the joystick has been held, e.g. for charge mechanics
+    ///
+    /// this is the [`Joystick::hold_time`] including the update in which the joystick was
+    /// released and `0` while it is idle
+    pub duration: f32,
+
+    /// whether the joystick was grabbed in the update
+    ///
+    /// unlike [`JoystickPhase::Start`], this is also set for a tap which is grabbed and
+    /// released in the same update
+    pub just_pressed: bool,
+
+    /// whether the joystick was released in the update, i.e. the phase is
+    /// [`JoystickPhase::Release`]
+    pub just_released: bool,
+
+    /// whether the direction differs from the direction of the last update
+    pub direction_changed: bool,
 }
 
 impl JoystickEvent {
-    fn new(direction: JoystickDirection, intensity: f32, angle: f32) -> Self {
+    /// create a new event with the direction classified from the final `intensity` and `angle`
+    ///
+    /// an `intensity` of zero is [`JoystickDirection::Idle`] with an angle of zero
+    fn new(intensity: f32, angle: f32, resolver: DirectionResolver) -> Self {
+        let (direction, sector, angle) = if intensity > 0. {
+            (resolver.direction(angle), resolver.sector(angle), angle)
+        } else {
+            (JoystickDirection::Idle, 0, 0.)
+        };
         Self {
             direction,
-            intensity,
+            sector,
+            intensity: intensity.max(0.),
             angle,
+            total_rotation: 0.,
+            in_outer_ring: false,
+            released_in_outer_ring: false,
+            source: InputSource::None,
+            charge: 0.,
+            band: 0,
+            phase: JoystickPhase::Idle,
+            flick: None,
+            duration: 0.,
+            just_pressed: false,
+            just_released: false,
+            direction_changed: false,
+        }
+    }
+
+    /// the angle and the intensity of the event
+    ///
+    /// returns `None` exactly if the direction is [`JoystickDirection::Idle`]
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickEvent, SimulatedInput};
+    ///
+    /// assert_eq!(JoystickEvent::default().polar(), None);
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(100.0, 212.5), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert_eq!(event.polar(), Some((std::f32::consts::FRAC_PI_2, 0.5)));
+    /// ```
+    pub fn polar(&self) -> Option<(f32, f32)> {
+        if self.is_idle() {
+            None
+        } else {
+            Some((self.angle, self.intensity))
+        }
+    }
+
+    /// the event as x and y axes like the stick of a gamepad
+    ///
+    /// this is the vector in the direction of [`JoystickEvent::angle`] with the length of
+    /// [`JoystickEvent::intensity`], so the dead zone, the response curve, the axis scale and
+    /// the snapping of the knob are included. it's not the raw offset of the knob divided by
+    /// the travel radius: the gate is always circular, so the vector has at most a length of
+    /// `1.0` and both axes are in the range `-1.0..=1.0`.
+    /// x is positive to the right and y is positive downwards, like the screen coordinates.
+    /// an additional intensity of the turbo (see [`Joystick::set_turbo`]) is not included.
+    ///
+    /// this is the recommended way to move something with the joystick
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(100.0, 190.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert!(event.axes().abs_diff_eq(Vec2::new(0.0, -0.4), 1e-6));
+    /// ```
+    pub fn axes(&self) -> Vec2 {
+        if self.is_idle() {
+            Vec2::ZERO
+        } else {
+            Vec2::new(self.angle.cos(), self.angle.sin()) * self.intensity.min(1.)
+        }
+    }
+
+    /// the signed value of [`JoystickEvent::axes`] on the axis, e.g. for a throttle
+    ///
+    /// this is the x axis for [`JoystickAxis::Horizontal`] and the y axis (positive downwards)
+    /// for [`JoystickAxis::Vertical`]. [`JoystickAxis::Both`] has no sign, so it is the
+    /// length of the axes from `0.0` to `1.0`, like the clamped intensity.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, JoystickAxis, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// joystick.set_axis(JoystickAxis::Vertical);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(105.0, 190.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert!((event.axis_value(JoystickAxis::Vertical) + 0.4).abs() < 1e-6);
+    /// assert!((event.axis_value(JoystickAxis::Both) - 0.4).abs() < 1e-6);
+    /// ```
+    pub fn axis_value(&self, axis: JoystickAxis) -> f32 {
+        let axes = self.axes();
+        match axis {
+            JoystickAxis::Both => axes.length(),
+            JoystickAxis::Horizontal => axes.x,
+            JoystickAxis::Vertical => axes.y,
+        }
+    }
+
+    /// [`JoystickEvent::axes`] with y positive upwards, e.g. for a world with a y-up camera
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(100.0, 190.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert!(event.axes_y_up().abs_diff_eq(Vec2::new(0.0, 0.4), 1e-6));
+    /// ```
+    pub fn axes_y_up(&self) -> Vec2 {
+        self.axes() * Vec2::new(1., -1.)
+    }
+
+    /// the unit vector in the direction of the knob
+    ///
+    /// Unlike [`JoystickDirection::to_local`], this is not snapped to the eight directions.
+    /// It uses the same convention as [`JoystickEvent::axes`] (y is positive downwards),
+    /// which is the offset of the knob scaled by the intensity.
+    ///
+    /// [`Vec2::ZERO`] if the joystick is idle.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(103.0, 204.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert!(event.direction_vector().abs_diff_eq(Vec2::new(0.6, 0.8), 1e-6));
+    /// ```
+    pub fn direction_vector(&self) -> Vec2 {
+        if self.is_idle() {
+            Vec2::ZERO
+        } else {
+            Vec2::new(self.angle.cos(), self.angle.sin())
+        }
+    }
+
+    /// the velocity of something which moves with `speed` at full intensity
+    ///
+    /// Unlike [`JoystickEvent::axes`], this includes the additional intensity of the turbo
+    /// (see [`Joystick::set_turbo`]), so the length can exceed `speed`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(100.0, 190.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// assert!(event.velocity(100.0).abs_diff_eq(Vec2::new(0.0, -40.0), 1e-4));
+    /// ```
+    pub fn velocity(&self, speed: f32) -> Vec2 {
+        self.direction_vector() * self.intensity * speed
+    }
+
+    /// the distance which something moves with `speed` at full intensity in `dt` seconds
+    ///
+    /// this is [`JoystickEvent::velocity`] scaled by the frame time, so the movement doesn't
+    /// depend on the frame rate
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(120.0, 200.0), true);
+    /// let event = joystick.update_with_input(&input, 0.0);
+    /// // 60 frames at 60 fps move as far as 30 frames at 30 fps
+    /// let slow = event.displacement(100.0, 1.0 / 30.0) * 30.0;
+    /// let fast = event.displacement(100.0, 1.0 / 60.0) * 60.0;
+    /// assert!(slow.abs_diff_eq(fast, 1e-3));
+    /// assert!(fast.abs_diff_eq(Vec2::new(80.0, 0.0), 1e-3));
+    /// ```
+    pub fn displacement(&self, speed: f32, dt: f32) -> Vec2 {
+        self.velocity(speed) * dt
+    }
+
+    /// whether the joystick is idle
+    pub fn is_idle(&self) -> bool {
+        self.direction == JoystickDirection::Idle
+    }
+
+    /// whether the intensity and the angle of both events are equal within the tolerances
+    ///
+    /// the angles are compared on the circle, so an angle slightly below `π` is close
+    /// to an angle slightly above `-π`.
+    /// an idle event is only equal to another idle event.
+    ///
+    /// # Arguments
+    /// * `intensity_eps`: the maximum difference of the intensities
+    /// * `angle_eps`: the maximum difference of the angles (in radians)
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::{Joystick, SimulatedInput};
+    ///
+    /// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(112.0, 200.0), true);
+    /// let a = joystick.update_with_input(&input, 0.0);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(112.1, 200.1), true);
+    /// let b = joystick.update_with_input(&input, 0.0);
+    ///
+    /// assert!(a.approx_eq(&b, 0.01, 0.01));
+    /// assert!(!a.approx_eq(&b, 0.0001, 0.01));
+    /// ```
+    pub fn approx_eq(&self, other: &Self, intensity_eps: f32, angle_eps: f32) -> bool {
+        match (self.polar(), other.polar()) {
+            (None, None) => true,
+            (Some((angle, intensity)), Some((other_angle, other_intensity))) => {
+                let angle_diff = (angle - other_angle).rem_euclid(2. * PI);
+                let angle_diff = angle_diff.min(2. * PI - angle_diff);
+                (intensity - other_intensity).abs() <= intensity_eps && angle_diff <= angle_eps
+            }
+            _ => false,
         }
     }
 }
@@ -408,8 +5462,21 @@ impl Default for JoystickEvent {
     fn default() -> Self {
         Self {
             direction: JoystickDirection::Idle,
+            sector: 0,
             intensity: 0.,
             angle: 0.,
+            total_rotation: 0.,
+            in_outer_ring: false,
+            released_in_outer_ring: false,
+            source: InputSource::None,
+            charge: 0.,
+            band: 0,
+            phase: JoystickPhase::Idle,
+            flick: None,
+            duration: 0.,
+            just_pressed: false,
+            just_released: false,
+            direction_changed: false,
         }
     }
 }
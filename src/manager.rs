@@ -0,0 +1,621 @@
+//! several controls which share the input
+//!
+//! Without the [`JoystickManager`], each [`Joystick`] and [`TouchButton`] sees all touches
+//! and the mouse, so overlapping controls (e.g. floating joysticks, see
+//! [`JoystickMode`](crate::JoystickMode)) can claim the same touch.
+
+use macroquad::prelude::{
+    draw_circle_lines, draw_rectangle_lines, Color, KeyCode, Touch, TouchPhase, Vec2,
+};
+
+use crate::{
+    ButtonShape, FrameStamp, Joystick, JoystickEvent, JoystickInput, MacroquadInput, TouchButton,
+};
+
+/// the color of the outlines of the controls in the edit mode
+const EDIT_COLOR: Color = Color::new(1., 1., 1., 0.75);
+/// the part of the radius at the rim of a control which resizes it in the edit mode
+const EDIT_HANDLE: f32 = 0.25;
+/// the minimum radius (in pixels) of a control which is resized in the edit mode
+const MIN_EDIT_RADIUS: f32 = 8.;
+
+/// owner of several [`Joystick`]s and [`TouchButton`]s which assigns each touch to exactly
+/// one of them
+///
+/// The joysticks are updated in the order they were added, followed by the buttons.
+/// A touch or the mouse which drags a control is only passed to this control until it's
+/// released, and a press is only passed to the next control if no earlier control grabbed
+/// it in the same update.
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::{Rect, TouchPhase, Vec2};
+/// use macroquad_virtual_joystick::{
+///     Joystick, JoystickDirection, JoystickManager, JoystickMode, SimulatedInput,
+/// };
+///
+/// let mut manager = JoystickManager::new();
+/// for name in ["move", "aim"] {
+///     let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+///     joystick.set_mode(JoystickMode::Floating {
+///         activation: Rect::new(0.0, 0.0, 400.0, 400.0),
+///         return_on_release: true,
+///     });
+///     manager.add(name, joystick);
+/// }
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(300.0, 300.0));
+/// manager.update_with_input(&input, 0.0);
+/// let input = SimulatedInput::new()
+///     .with_touch(0, TouchPhase::Moved, Vec2::new(310.0, 300.0))
+///     .with_touch(1, TouchPhase::Started, Vec2::new(50.0, 50.0));
+/// manager.update_with_input(&input, 0.0);
+///
+/// assert_eq!(manager.event("move").unwrap().direction, JoystickDirection::Right);
+/// // the second touch spawned the other joystick
+/// assert_eq!(manager.get("aim").unwrap().center(), Vec2::new(50.0, 50.0));
+/// ```
+#[derive(Default)]
+pub struct JoystickManager {
+    joysticks: Vec<(String, Joystick)>,
+    buttons: Vec<(String, TouchButton)>,
+    edit: Option<EditState>,
+    on_layout_changed: Option<LayoutCallback>,
+    /// the frame of the first update in the current frame
+    frame: Option<FrameStamp>,
+}
+
+/// the kind of a control of the [`JoystickManager`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ControlKind {
+    /// a [`Joystick`]
+    Joystick,
+    /// a [`TouchButton`]
+    Button,
+}
+
+/// the callback of [`JoystickManager::on_layout_changed`]
+pub type LayoutCallback = Box<dyn FnMut(&LayoutChange)>;
+
+/// a control which was moved or resized in the edit mode of the [`JoystickManager`]
+///
+/// see [`JoystickManager::on_layout_changed`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutChange {
+    /// the name of the control
+    pub name: String,
+    /// the kind of the control
+    pub kind: ControlKind,
+    /// the new shape of the control, a joystick is a circle with its center and half its size
+    pub shape: ButtonShape,
+}
+
+impl JoystickManager {
+    /// create a new manager without controls
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a joystick and return its index
+    ///
+    /// the name should be unique, [`JoystickManager::get`] returns the first joystick with it
+    pub fn add(&mut self, name: impl Into<String>, joystick: Joystick) -> usize {
+        self.joysticks.push((name.into(), joystick));
+        self.joysticks.len() - 1
+    }
+
+    /// add a button and return its index
+    ///
+    /// the name should be unique, [`JoystickManager::button`] returns the first button with it
+    pub fn add_button(&mut self, name: impl Into<String>, button: TouchButton) -> usize {
+        self.buttons.push((name.into(), button));
+        self.buttons.len() - 1
+    }
+
+    /// remove the joystick with the name and return it
+    ///
+    /// The indices of the following joysticks decrease by one. A touch which dragged the
+    /// removed joystick is free again in the next update, and a drag of the edit mode ends.
+    pub fn remove(&mut self, name: &str) -> Option<Joystick> {
+        let index = self.index_of(name)?;
+        self.cancel_edit_drag();
+        Some(self.joysticks.remove(index).1)
+    }
+
+    /// remove the button with the name and return it, see [`JoystickManager::remove`]
+    pub fn remove_button(&mut self, name: &str) -> Option<TouchButton> {
+        let index = self.buttons.iter().position(|(n, _)| n == name)?;
+        self.cancel_edit_drag();
+        Some(self.buttons.remove(index).1)
+    }
+
+    /// end the drag of the edit mode, e.g. because the indices of the controls changed
+    fn cancel_edit_drag(&mut self) {
+        if let Some(edit) = &mut self.edit {
+            edit.drag = None;
+        }
+    }
+
+    /// the number of joysticks
+    pub fn len(&self) -> usize {
+        self.joysticks.len()
+    }
+
+    /// whether the manager has no joysticks
+    ///
+    /// the buttons are not counted, like in [`JoystickManager::len`]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// the index of the joystick with the name
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.joysticks.iter().position(|(n, _)| n == name)
+    }
+
+    /// the joystick with the name
+    pub fn get(&self, name: &str) -> Option<&Joystick> {
+        self.index_of(name).map(|index| &self.joysticks[index].1)
+    }
+
+    /// the joystick with the name
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Joystick> {
+        let index = self.index_of(name)?;
+        Some(&mut self.joysticks[index].1)
+    }
+
+    /// the joystick at the index
+    pub fn joystick(&self, index: usize) -> Option<&Joystick> {
+        self.joysticks.get(index).map(|(_, joystick)| joystick)
+    }
+
+    /// the joystick at the index
+    pub fn joystick_mut(&mut self, index: usize) -> Option<&mut Joystick> {
+        self.joysticks.get_mut(index).map(|(_, joystick)| joystick)
+    }
+
+    /// the event of the last update of the joystick with the name
+    pub fn event(&self, name: &str) -> Option<JoystickEvent> {
+        self.get(name).map(Joystick::event)
+    }
+
+    /// the event of the last update of the joystick at the index
+    pub fn event_at(&self, index: usize) -> Option<JoystickEvent> {
+        self.joystick(index).map(Joystick::event)
+    }
+
+    /// the names and events of the last update of all joysticks
+    pub fn events(&self) -> impl Iterator<Item = (&str, JoystickEvent)> {
+        self.joysticks
+            .iter()
+            .map(|(name, joystick)| (name.as_str(), joystick.event()))
+    }
+
+    /// the button with the name
+    pub fn button(&self, name: &str) -> Option<&TouchButton> {
+        self.buttons
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, button)| button)
+    }
+
+    /// the button with the name
+    pub fn button_mut(&mut self, name: &str) -> Option<&mut TouchButton> {
+        self.buttons
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .map(|(_, button)| button)
+    }
+
+    /// the button at the index
+    pub fn button_at(&self, index: usize) -> Option<&TouchButton> {
+        self.buttons.get(index).map(|(_, button)| button)
+    }
+
+    /// enable or disable the edit mode in which the player can rearrange the controls
+    ///
+    /// In the edit mode, the update doesn't pass the input to the controls. Instead, a drag
+    /// which starts inside of a control moves it and a drag which starts at its rim (the
+    /// outer quarter of the radius) resizes it. After each drag, the callback of
+    /// [`JoystickManager::on_layout_changed`] is called if the control was changed.
+    ///
+    /// Entering the edit mode releases all controls without reporting the release.
+    /// Joysticks are moved with [`Joystick::set_position`] and resized with
+    /// [`Joystick::set_size`], so an attached joystick (see [`Joystick::attach_to`]) moves back
+    /// to its provider in the next update outside of the edit mode.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{TouchPhase, Vec2};
+    /// use macroquad_virtual_joystick::{Joystick, JoystickManager, SimulatedInput};
+    ///
+    /// let mut manager = JoystickManager::new();
+    /// manager.add("move", Joystick::new(100.0, 200.0, 50.0));
+    /// manager.set_edit_mode(true);
+    ///
+    /// let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(100.0, 200.0));
+    /// manager.update_with_input(&input, 0.0);
+    /// let input = SimulatedInput::new().with_touch(0, TouchPhase::Moved, Vec2::new(150.0, 180.0));
+    /// manager.update_with_input(&input, 0.0);
+    ///
+    /// let joystick = manager.get("move").unwrap();
+    /// assert_eq!(joystick.center(), Vec2::new(150.0, 180.0));
+    /// assert!(joystick.event().is_idle());
+    /// ```
+    pub fn set_edit_mode(&mut self, edit_mode: bool) {
+        if edit_mode == self.edit.is_some() {
+            return;
+        }
+        if edit_mode {
+            for (_, joystick) in &mut self.joysticks {
+                joystick.clear();
+            }
+            for (_, button) in &mut self.buttons {
+                button.cancel();
+            }
+            self.edit = Some(EditState::default());
+        } else {
+            self.edit = None;
+        }
+    }
+
+    /// whether the edit mode is enabled, see [`JoystickManager::set_edit_mode`]
+    pub fn is_edit_mode(&self) -> bool {
+        self.edit.is_some()
+    }
+
+    /// call the callback after a control was moved or resized in the edit mode
+    ///
+    /// see [`JoystickManager::set_edit_mode`]. It replaces the previous callback.
+    pub fn on_layout_changed(&mut self, callback: LayoutCallback) {
+        self.on_layout_changed = Some(callback);
+    }
+
+    /// update all controls with the input of macroquad
+    ///
+    /// The controls are only updated once per frame of macroquad: further calls in the same
+    /// frame do nothing (see [`JoystickManager::update_in_frame`]).
+    pub fn update(&mut self) {
+        self.update_in_frame(&MacroquadInput, FrameStamp::current());
+    }
+
+    /// update all controls from the given input once per frame
+    ///
+    /// If the controls were already updated in the same frame (see
+    /// [`FrameStamp::is_same_frame`]), the input is not processed again. Otherwise this is the
+    /// same as [`JoystickManager::update_with_input`] with the frame time of the stamp.
+    pub fn update_in_frame(&mut self, input: &impl JoystickInput, frame: FrameStamp) {
+        if let Some(first) = self.frame {
+            if first.is_same_frame(&frame) {
+                return;
+            }
+        }
+        self.frame = Some(frame);
+        self.update_with_input(input, frame.frame_time);
+    }
+
+    /// update all controls with the input of macroquad and the given frame time
+    ///
+    /// see [`Joystick::update_with_dt`]
+    pub fn update_with_dt(&mut self, dt: f32) {
+        self.update_with_input(&MacroquadInput, dt);
+    }
+
+    /// update all controls from the input
+    ///
+    /// see [`Joystick::update_with_input`] and [`TouchButton::update_with_input`]
+    pub fn update_with_input(&mut self, input: &impl JoystickInput, dt: f32) {
+        if self.edit.is_some() {
+            self.update_edit(input);
+            return;
+        }
+        // the owners from before the update keep their touches and the mouse
+        let owners: Vec<Owner> = self
+            .joysticks
+            .iter()
+            .map(|(_, joystick)| Owner {
+                touch: joystick.active_touch(),
+                mouse: joystick.owns_mouse(),
+            })
+            .chain(self.buttons.iter().map(|(_, button)| Owner {
+                touch: button.owned_touch(),
+                mouse: button.owns_mouse(),
+            }))
+            .collect();
+        let mut assignment = Assignment {
+            input,
+            touches: input.touches(),
+            mouse_position: input.mouse_position(),
+            mouse_down: input.is_mouse_down(),
+            claimed: owners.iter().filter_map(|owner| owner.touch).collect(),
+            mouse_owner: owners.iter().position(|owner| owner.mouse),
+        };
+
+        for (index, (_, joystick)) in self.joysticks.iter_mut().enumerate() {
+            joystick.update_with_input(&assignment.input_for(index, owners[index]), dt);
+            assignment.claim(index, joystick.active_touch(), joystick.owns_mouse());
+        }
+        let offset = self.joysticks.len();
+        for (index, (_, button)) in self.buttons.iter_mut().enumerate() {
+            let index = offset + index;
+            button.update_with_input(&assignment.input_for(index, owners[index]), dt);
+            assignment.claim(index, button.owned_touch(), button.owns_mouse());
+        }
+    }
+
+    /// the touches of the input which are not used by any of the controls
+    ///
+    /// Call this after the update, e.g. to shoot with a tap on the screen which didn't grab a
+    /// control. A touch which released a control in the last update is not free either, see
+    /// [`Joystick::uses_touch`]. In the edit mode all touches are free.
+    pub fn free_touches(&self, input: &impl JoystickInput) -> Vec<Touch> {
+        let mut touches = input.touches();
+        if self.edit.is_none() {
+            touches.retain(|touch| {
+                !self
+                    .joysticks
+                    .iter()
+                    .any(|(_, joystick)| joystick.uses_touch(touch.id))
+                    && !self
+                        .buttons
+                        .iter()
+                        .any(|(_, button)| button.uses_touch(touch.id))
+            });
+        }
+        touches
+    }
+
+    /// move or resize the controls with the input, see [`JoystickManager::set_edit_mode`]
+    fn update_edit(&mut self, input: &impl JoystickInput) {
+        let mut edit = self.edit.take().unwrap_or_default();
+        let touches = input.touches();
+        let mouse_down = input.is_mouse_down();
+
+        if let Some(drag) = edit.drag {
+            let position = match drag.pointer {
+                EditPointer::Touch(id) => touches
+                    .iter()
+                    .find(|touch| touch.id == id)
+                    .filter(|touch| {
+                        touch.phase != TouchPhase::Ended && touch.phase != TouchPhase::Cancelled
+                    })
+                    .map(|touch| touch.position),
+                EditPointer::Mouse if mouse_down => Some(input.mouse_position()),
+                EditPointer::Mouse => None,
+            };
+            match position {
+                Some(position) => self.apply_edit(&drag, position),
+                None => {
+                    edit.drag = None;
+                    self.finish_edit(&drag);
+                }
+            }
+        } else if let Some(touch) = touches
+            .iter()
+            .find(|touch| touch.phase == TouchPhase::Started)
+        {
+            edit.drag = self.start_edit(EditPointer::Touch(touch.id), touch.position);
+        } else if touches.is_empty() && mouse_down && !edit.mouse_was_down {
+            edit.drag = self.start_edit(EditPointer::Mouse, input.mouse_position());
+        }
+        edit.mouse_was_down = mouse_down;
+        self.edit = Some(edit);
+    }
+
+    /// the control under the pointer, the last rendered one first
+    fn start_edit(&self, pointer: EditPointer, position: Vec2) -> Option<EditDrag> {
+        let joysticks = (0..self.joysticks.len()).map(|index| (ControlKind::Joystick, index));
+        let buttons = (0..self.buttons.len()).map(|index| (ControlKind::Button, index));
+        joysticks.chain(buttons).rev().find_map(|(kind, index)| {
+            let shape = self.control_shape(kind, index);
+            let local = self.to_control(kind, index, position);
+            if !shape.contains(local) {
+                return None;
+            }
+            let distance = (local - shape.center()).length();
+            Some(EditDrag {
+                kind,
+                index,
+                pointer,
+                start: local,
+                shape,
+                resize: distance >= shape.inner_radius() * (1. - EDIT_HANDLE),
+            })
+        })
+    }
+
+    /// move or resize the control of the drag to the pointer
+    fn apply_edit(&mut self, drag: &EditDrag, position: Vec2) {
+        let local = self.to_control(drag.kind, drag.index, position);
+        let shape = if drag.resize {
+            let center = drag.shape.center();
+            let factor = (local - center).length() / (drag.start - center).length();
+            let min_factor = MIN_EDIT_RADIUS / drag.shape.inner_radius();
+            if !factor.is_finite() {
+                return;
+            }
+            drag.shape.scaled(factor.max(min_factor))
+        } else {
+            drag.shape.translated(local - drag.start)
+        };
+        match drag.kind {
+            ControlKind::Joystick => {
+                let joystick = &mut self.joysticks[drag.index].1;
+                let center = shape.center();
+                joystick.set_position(center.x, center.y);
+                joystick.set_size(shape.inner_radius() * 2.);
+            }
+            ControlKind::Button => self.buttons[drag.index].1.set_shape(shape),
+        }
+    }
+
+    /// report the change of the control of the drag
+    fn finish_edit(&mut self, drag: &EditDrag) {
+        let shape = self.control_shape(drag.kind, drag.index);
+        if shape == drag.shape {
+            return;
+        }
+        let name = match drag.kind {
+            ControlKind::Joystick => &self.joysticks[drag.index].0,
+            ControlKind::Button => &self.buttons[drag.index].0,
+        };
+        let change = LayoutChange {
+            name: name.clone(),
+            kind: drag.kind,
+            shape,
+        };
+        if let Some(callback) = &mut self.on_layout_changed {
+            callback(&change);
+        }
+    }
+
+    /// the shape of the control which is edited
+    fn control_shape(&self, kind: ControlKind, index: usize) -> ButtonShape {
+        match kind {
+            ControlKind::Joystick => {
+                let joystick = &self.joysticks[index].1;
+                ButtonShape::Circle {
+                    center: joystick.center(),
+                    radius: joystick.size() / 2.,
+                }
+            }
+            ControlKind::Button => self.buttons[index].1.shape(),
+        }
+    }
+
+    /// the position in the coordinates of the control
+    fn to_control(&self, kind: ControlKind, index: usize, position: Vec2) -> Vec2 {
+        match kind {
+            ControlKind::Joystick => self.joysticks[index].1.to_local(position),
+            ControlKind::Button => position,
+        }
+    }
+
+    /// render all joysticks and then all buttons in the order they were added
+    ///
+    /// in the edit mode (see [`JoystickManager::set_edit_mode`]) the controls are outlined
+    pub fn render(&self) {
+        for (_, joystick) in &self.joysticks {
+            joystick.render();
+            if self.edit.is_some() {
+                joystick.draw_outline(joystick.center(), joystick.size() / 2., 2., EDIT_COLOR);
+            }
+        }
+        for (_, button) in &self.buttons {
+            button.render();
+            if self.edit.is_some() {
+                match button.shape() {
+                    ButtonShape::Circle { center, radius } => {
+                        draw_circle_lines(center.x, center.y, radius, 2., EDIT_COLOR)
+                    }
+                    ButtonShape::Rect(rect) => {
+                        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2., EDIT_COLOR)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// the state of the edit mode, see [`JoystickManager::set_edit_mode`]
+#[derive(Default)]
+struct EditState {
+    drag: Option<EditDrag>,
+    mouse_was_down: bool,
+}
+
+/// the pointer which edits a control
+#[derive(Clone, Copy)]
+enum EditPointer {
+    Touch(u64),
+    Mouse,
+}
+
+/// a control which is moved or resized in the edit mode
+#[derive(Clone, Copy)]
+struct EditDrag {
+    kind: ControlKind,
+    index: usize,
+    pointer: EditPointer,
+    /// the position of the press in the coordinates of the control
+    start: Vec2,
+    /// the shape of the control before the drag
+    shape: ButtonShape,
+    resize: bool,
+}
+
+/// the input which a control owned before the update
+#[derive(Clone, Copy)]
+struct Owner {
+    touch: Option<u64>,
+    mouse: bool,
+}
+
+/// the input of an update and the controls which claimed it
+struct Assignment<'a, I> {
+    input: &'a I,
+    touches: Vec<Touch>,
+    mouse_position: Vec2,
+    mouse_down: bool,
+    claimed: Vec<u64>,
+    mouse_owner: Option<usize>,
+}
+
+/// the pointer input which is assigned to a control, the keys are shared by all controls
+struct AssignedInput<'a, I> {
+    input: &'a I,
+    touches: Vec<Touch>,
+    mouse_position: Vec2,
+    mouse_down: bool,
+}
+
+impl<I: JoystickInput> JoystickInput for AssignedInput<'_, I> {
+    fn touches(&self) -> Vec<Touch> {
+        self.touches.clone()
+    }
+
+    fn mouse_position(&self) -> Vec2 {
+        self.mouse_position
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        self.mouse_down
+    }
+
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        self.input.is_key_down(key)
+    }
+
+    fn gamepad_stick(&self) -> Vec2 {
+        self.input.gamepad_stick()
+    }
+}
+
+impl<'a, I: JoystickInput> Assignment<'a, I> {
+    /// the input which is visible to the control at the index
+    fn input_for(&self, index: usize, owner: Owner) -> AssignedInput<'a, I> {
+        let visible =
+            |touch: &&Touch| owner.touch == Some(touch.id) || !self.claimed.contains(&touch.id);
+        AssignedInput {
+            input: self.input,
+            touches: self.touches.iter().filter(visible).cloned().collect(),
+            mouse_position: self.mouse_position,
+            mouse_down: self.mouse_down
+                && (self.mouse_owner.is_none() || self.mouse_owner == Some(index)),
+        }
+    }
+
+    /// claim the input which the control at the index owns after its update
+    fn claim(&mut self, index: usize, touch: Option<u64>, mouse: bool) {
+        if let Some(id) = touch {
+            if !self.claimed.contains(&id) {
+                self.claimed.push(id);
+            }
+        }
+        if self.mouse_owner.is_none() && mouse {
+            self.mouse_owner = Some(index);
+        }
+    }
+}
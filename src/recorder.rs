@@ -0,0 +1,163 @@
+//! recording and replay of the input of the [`Joystick`](crate::Joystick)
+//!
+//! The [`JoystickRecorder`] captures the input of each update together with the resulting
+//! event. The input is replayed through a joystick, so the knob moves like in the recording
+//! and all events (including the transient ones like flicks and repeats) are reproduced.
+
+use crate::{Joystick, JoystickEvent, JoystickInput, SimulatedInput};
+
+/// one update of a [`JoystickRecorder`]
+#[derive(Clone, Debug)]
+pub struct RecordedFrame {
+    /// the time (in seconds) since the start of the recording at the end of the update
+    pub time: f32,
+    /// the time since the last update which was passed to the joystick
+    pub dt: f32,
+    /// the input of the update
+    ///
+    /// only the keys which are bound to the joystick are recorded
+    pub input: SimulatedInput,
+    /// the event of the update
+    pub event: JoystickEvent,
+}
+
+/// recorder of the updates of a [`Joystick`], e.g. for ghost replays, tutorials or tests
+///
+/// Replay the recording through a joystick with the same configuration and the same state as
+/// at the start of the recording (see [`JoystickRecorder::replay`]), so it produces the same
+/// events.
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::Vec2;
+/// use macroquad_virtual_joystick::{Joystick, JoystickRecorder, SimulatedInput};
+///
+/// let mut joystick = Joystick::new(100.0, 200.0, 50.0);
+/// let mut recorder = JoystickRecorder::new();
+/// for (x, down) in [(110.0, true), (120.0, true), (120.0, false)] {
+///     let input = SimulatedInput::new().with_mouse(Vec2::new(x, 200.0), down);
+///     recorder.record(&mut joystick, &input, 1.0 / 60.0);
+/// }
+/// assert_eq!(recorder.frames().len(), 3);
+///
+/// let mut ghost = Joystick::new(100.0, 200.0, 50.0);
+/// let mut replay = recorder.replay();
+/// while let Some(event) = replay.step(&mut ghost) {
+///     assert_eq!(event.direction, recorder.frames()[replay.position() - 1].event.direction);
+/// }
+/// assert!(replay.is_finished());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JoystickRecorder {
+    frames: Vec<RecordedFrame>,
+    time: f32,
+}
+
+impl JoystickRecorder {
+    /// create a new empty recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// update the joystick from the input and record the update
+    ///
+    /// this is the same as [`Joystick::update_with_input`], e.g. with
+    /// [`MacroquadInput`](crate::MacroquadInput) and the frame time of macroquad
+    pub fn record(
+        &mut self,
+        joystick: &mut Joystick,
+        input: &impl JoystickInput,
+        dt: f32,
+    ) -> JoystickEvent {
+        let keys = joystick
+            .bound_keys()
+            .into_iter()
+            .flatten()
+            .filter(|key| input.is_key_down(*key))
+            .collect();
+        let recorded = SimulatedInput {
+            touches: input.touches(),
+            mouse_position: input.mouse_position(),
+            mouse_down: input.is_mouse_down(),
+            keys,
+            gamepad_stick: input.gamepad_stick(),
+        };
+        let event = joystick.update_with_input(&recorded, dt);
+        self.time += dt;
+        self.frames.push(RecordedFrame {
+            time: self.time,
+            dt,
+            input: recorded,
+            event,
+        });
+        event
+    }
+
+    /// the recorded updates in their order
+    pub fn frames(&self) -> &[RecordedFrame] {
+        &self.frames
+    }
+
+    /// the duration (in seconds) of the recording
+    pub fn duration(&self) -> f32 {
+        self.time
+    }
+
+    /// remove all recorded updates
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.time = 0.;
+    }
+
+    /// replay the recording update by update
+    pub fn replay(&self) -> JoystickReplay<'_> {
+        JoystickReplay {
+            frames: &self.frames,
+            position: 0,
+        }
+    }
+}
+
+/// the replay of a [`JoystickRecorder`], see [`JoystickRecorder::replay`]
+#[derive(Clone, Debug)]
+pub struct JoystickReplay<'a> {
+    frames: &'a [RecordedFrame],
+    position: usize,
+}
+
+impl JoystickReplay<'_> {
+    /// update the joystick with the next recorded input
+    ///
+    /// returns the event of the joystick or `None` if the replay is finished
+    pub fn step(&mut self, joystick: &mut Joystick) -> Option<JoystickEvent> {
+        let frame = self.frames.get(self.position)?;
+        self.position += 1;
+        Some(joystick.update_with_input(&frame.input, frame.dt))
+    }
+
+    /// replay all updates up to the time (in seconds) since the start of the recording
+    ///
+    /// this follows the recording with the time of the game, e.g. for a ghost replay;
+    /// returns the events of the replayed updates
+    pub fn advance_to(&mut self, joystick: &mut Joystick, time: f32) -> Vec<JoystickEvent> {
+        let mut events = Vec::new();
+        while self
+            .frames
+            .get(self.position)
+            .is_some_and(|frame| frame.time <= time)
+        {
+            events.extend(self.step(joystick));
+        }
+        events
+    }
+
+    /// the number of replayed updates
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// whether all updates were replayed
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.frames.len()
+    }
+}
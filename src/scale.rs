@@ -0,0 +1,121 @@
+//! density-independent sizes of the [`Joystick`](crate::Joystick)
+
+use macroquad::prelude::{screen_height, screen_width, Vec2};
+use macroquad::window::get_internal_gl;
+
+/// the factor from density-independent units to the screen coordinates of macroquad
+///
+/// The position, the size and the knob size of a [`JoystickBuilder`](crate::JoystickBuilder)
+/// are multiplied by the scale, see [`JoystickBuilder::ui_scale`](crate::JoystickBuilder::ui_scale).
+///
+/// Defaults to `1.0`, i.e. the units are screen coordinates. The scale is not read from the
+/// window by default, because it's only known once the window exists and a window with
+/// `high_dpi` already reports its screen coordinates in logical units.
+///
+/// # Examples
+/// ```
+/// use macroquad_virtual_joystick::{JoystickBuilder, UiScale};
+///
+/// // designed for a screen with the density 1.0
+/// let joystick = JoystickBuilder::new(80.0, 200.0, 120.0)
+///     .ui_scale(UiScale(2.0))
+///     .build()
+///     .unwrap();
+/// assert_eq!(joystick.size(), 240.0);
+/// assert_eq!(joystick.center().x, 160.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UiScale(pub f32);
+
+impl UiScale {
+    /// the scale which keeps the units as screen coordinates
+    pub const ONE: Self = Self(1.);
+
+    /// the ratio of the pixels of the framebuffer to the screen coordinates
+    ///
+    /// This is the pixel ratio of the display if the window was created with `high_dpi`
+    /// (and `1.0` otherwise), e.g. to size the joystick in pixels of a render target.
+    ///
+    /// # Panics
+    /// if it's called before the window of macroquad was created
+    pub fn pixel_ratio() -> Self {
+        // SAFETY: the dpi scale is only read, the state of the gl context is not changed
+        let gl = unsafe { get_internal_gl() };
+        Self(gl.quad_context.dpi_scale())
+    }
+
+    /// the scale of a layout for a screen of `width` x `height` to the current screen
+    ///
+    /// the layout is scaled uniformly, so it fits into the current screen
+    ///
+    /// # Panics
+    /// if it's called before the window of macroquad was created
+    pub fn reference(width: f32, height: f32) -> Self {
+        Self::fit(
+            Vec2::new(width, height),
+            Vec2::new(screen_width(), screen_height()),
+        )
+    }
+
+    /// the scale of a layout for a screen of the size `reference` to a screen of the size
+    /// `screen`
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::UiScale;
+    ///
+    /// let reference = Vec2::new(800.0, 600.0);
+    /// assert_eq!(UiScale::fit(reference, Vec2::new(1600.0, 1500.0)), UiScale(2.0));
+    /// ```
+    pub fn fit(reference: Vec2, screen: Vec2) -> Self {
+        let scale = (screen / reference).min_element();
+        if scale.is_finite() && scale > 0. {
+            Self(scale)
+        } else {
+            Self::ONE
+        }
+    }
+
+    /// the scale of a layout in which the shorter side of the current screen is `reference`
+    /// units long
+    ///
+    /// With a reference of `1.0`, the lengths are fractions of the shorter side, so the
+    /// joystick has the same relative size in portrait and landscape.
+    ///
+    /// # Panics
+    /// if it's called before the window of macroquad was created
+    pub fn shorter_side(reference: f32) -> Self {
+        Self::fit_shorter_side(reference, Vec2::new(screen_width(), screen_height()))
+    }
+
+    /// the scale of a layout in which the shorter side of a screen of the size `screen` is
+    /// `reference` units long
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::Vec2;
+    /// use macroquad_virtual_joystick::UiScale;
+    ///
+    /// // the joystick is a quarter of the shorter side
+    /// let scale = UiScale::fit_shorter_side(1.0, Vec2::new(1920.0, 1080.0));
+    /// assert_eq!(scale.scale(0.25), 270.0);
+    /// let scale = UiScale::fit_shorter_side(1.0, Vec2::new(1080.0, 1920.0));
+    /// assert_eq!(scale.scale(0.25), 270.0);
+    /// ```
+    pub fn fit_shorter_side(reference: f32, screen: Vec2) -> Self {
+        Self::fit(Vec2::splat(reference), Vec2::splat(screen.min_element()))
+    }
+
+    /// the length in screen coordinates
+    pub fn scale(self, length: f32) -> f32 {
+        length * self.0
+    }
+}
+
+impl Default for UiScale {
+    fn default() -> Self {
+        Self::ONE
+    }
+}
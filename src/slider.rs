@@ -0,0 +1,287 @@
+//! a virtual slider which reports a value along one axis, e.g. for a throttle or the zoom
+
+use macroquad::prelude::{draw_circle, draw_rectangle, Rect, Touch, TouchPhase, Vec2};
+
+use crate::{
+    order_touch_phases, InputSource, JoystickInput, MacroquadInput, BACKGROUND_COLOR, KNOB_COLOR,
+};
+
+/// the axis of a [`VirtualSlider`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliderOrientation {
+    /// the value increases from the left to the right
+    Horizontal,
+    /// the value increases from the bottom to the top
+    Vertical,
+}
+
+/// renderer of the [`VirtualSlider`], see [`VirtualSlider::set_render`]
+///
+/// the function gets the track, the center of the knob and whether the slider is pressed
+pub type SliderRenderer = Box<dyn Fn(&Rect, Vec2, bool)>;
+
+/// a virtual slider which is dragged by a touch or the mouse
+///
+/// A touch or a click which starts on the track moves the knob under it until it is
+/// released, even if it leaves the track in between. The value is `0.0` at the start and
+/// `1.0` at the end of the track. Like the [`Joystick`](crate::Joystick), the mouse is only
+/// used while there are no touches and only one touch can drag the slider.
+///
+/// The slider is sticky by default: the value stays where the slider was released, e.g. for a
+/// throttle. See [`VirtualSlider::set_sticky`] for a slider which springs back.
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::{Rect, TouchPhase, Vec2};
+/// use macroquad_virtual_joystick::{SimulatedInput, SliderOrientation, VirtualSlider};
+///
+/// let mut throttle = VirtualSlider::new(Rect::new(300.0, 100.0, 40.0, 200.0), SliderOrientation::Vertical);
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(320.0, 250.0));
+/// assert_eq!(throttle.update_with_input(&input), 0.25);
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Moved, Vec2::new(320.0, 150.0));
+/// assert_eq!(throttle.update_with_input(&input), 0.75);
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Ended, Vec2::new(320.0, 150.0));
+/// assert_eq!(throttle.update_with_input(&input), 0.75);
+/// assert!(!throttle.is_pressed());
+/// ```
+pub struct VirtualSlider {
+    track: Rect,
+    orientation: SliderOrientation,
+    render_fn: SliderRenderer,
+    value: f32,
+    sticky: bool,
+    rest_value: f32,
+    detents: Vec<f32>,
+    detent_snap: f32,
+    pressed: bool,
+    source: InputSource,
+    touch_id: u64,
+    mouse_was_down: bool,
+}
+
+impl VirtualSlider {
+    /// create a new slider with the value `0.0`
+    ///
+    /// # Arguments
+    /// * `track`: the area in which the knob moves, which is also the hit area
+    /// * `orientation`: the axis along which the value changes
+    pub fn new(track: Rect, orientation: SliderOrientation) -> Self {
+        Self {
+            track,
+            orientation,
+            render_fn: Box::new(render_default),
+            value: 0.,
+            sticky: true,
+            rest_value: 0.,
+            detents: Vec::new(),
+            detent_snap: 0.05,
+            pressed: false,
+            source: InputSource::None,
+            touch_id: 0,
+            mouse_was_down: false,
+        }
+    }
+
+    /// set a custom drawing function
+    pub fn set_render(&mut self, render: SliderRenderer) {
+        self.render_fn = render;
+    }
+
+    /// the area in which the knob moves
+    pub fn track(&self) -> Rect {
+        self.track
+    }
+
+    /// set the area in which the knob moves, e.g. after the screen was resized
+    pub fn set_track(&mut self, track: Rect) {
+        self.track = track;
+    }
+
+    /// the value of the slider, from `0.0` to `1.0`
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// set the value of the slider, e.g. to reset a throttle
+    ///
+    /// the value is clamped to `0.0..=1.0`
+    pub fn set_value(&mut self, value: f32) {
+        self.value = clamp_value(value);
+    }
+
+    /// set whether the value stays where the slider was released
+    ///
+    /// A slider which is not sticky moves back to the rest value (see
+    /// [`VirtualSlider::set_rest_value`]) on the release, e.g. for the zoom.
+    ///
+    /// Defaults to `true`.
+    pub fn set_sticky(&mut self, sticky: bool) {
+        self.sticky = sticky;
+    }
+
+    /// set the value to which a slider which is not sticky moves on the release
+    ///
+    /// the value is clamped to `0.0..=1.0`
+    ///
+    /// Defaults to `0.0`.
+    pub fn set_rest_value(&mut self, rest_value: f32) {
+        self.rest_value = clamp_value(rest_value);
+        if !self.sticky && !self.pressed {
+            self.value = self.rest_value;
+        }
+    }
+
+    /// set the values at which the knob snaps, e.g. the gears of a throttle
+    ///
+    /// While the slider is dragged, a value closer than the snap distance (see
+    /// [`VirtualSlider::set_detent_snap`]) to a detent is reported as the detent.
+    ///
+    /// Defaults to no detents.
+    ///
+    /// # Examples
+    /// ```
+    /// use macroquad::prelude::{Rect, Vec2};
+    /// use macroquad_virtual_joystick::{SimulatedInput, SliderOrientation, VirtualSlider};
+    ///
+    /// let mut slider = VirtualSlider::new(Rect::new(0.0, 0.0, 200.0, 40.0), SliderOrientation::Horizontal);
+    /// slider.set_detents(vec![0.5]);
+    /// let input = SimulatedInput::new().with_mouse(Vec2::new(108.0, 20.0), true);
+    /// assert_eq!(slider.update_with_input(&input), 0.5);
+    /// ```
+    pub fn set_detents(&mut self, detents: Vec<f32>) {
+        self.detents = detents.into_iter().map(clamp_value).collect();
+    }
+
+    /// set the distance (as a value) from which the knob snaps to a detent
+    ///
+    /// Defaults to `0.05`.
+    pub fn set_detent_snap(&mut self, detent_snap: f32) {
+        self.detent_snap = detent_snap.max(0.);
+    }
+
+    /// whether the slider is dragged
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// the center of the knob in screen coordinates
+    pub fn knob_position(&self) -> Vec2 {
+        let center = self.track.center();
+        match self.orientation {
+            SliderOrientation::Horizontal => {
+                Vec2::new(self.track.x + self.track.w * self.value, center.y)
+            }
+            SliderOrientation::Vertical => {
+                Vec2::new(center.x, self.track.y + self.track.h * (1. - self.value))
+            }
+        }
+    }
+
+    /// update the slider with the input of macroquad
+    ///
+    /// this should be called once per frame
+    pub fn update(&mut self) -> f32 {
+        self.update_with_input(&MacroquadInput)
+    }
+
+    /// update the slider from the input
+    ///
+    /// returns the value of the slider
+    pub fn update_with_input(&mut self, input: &impl JoystickInput) -> f32 {
+        let mut touches = input.touches();
+        let mouse_down = input.is_mouse_down();
+        if touches.is_empty() {
+            self.update_mouse(input.mouse_position(), mouse_down);
+        } else {
+            order_touch_phases(&mut touches);
+            self.update_touch(&touches);
+        }
+        self.mouse_was_down = mouse_down;
+        self.value
+    }
+
+    /// render the slider
+    pub fn render(&self) {
+        (self.render_fn)(&self.track, self.knob_position(), self.pressed);
+    }
+
+    fn update_touch(&mut self, touches: &[Touch]) {
+        for touch in touches {
+            match touch.phase {
+                TouchPhase::Started if !self.pressed && self.track.contains(touch.position) => {
+                    self.touch_id = touch.id;
+                    self.press(InputSource::Touch, touch.position);
+                }
+                TouchPhase::Moved if self.owns(touch) => self.drag(touch.position),
+                TouchPhase::Ended | TouchPhase::Cancelled if self.owns(touch) => {
+                    self.release();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn update_mouse(&mut self, position: Vec2, mouse_down: bool) {
+        if self.pressed {
+            if mouse_down {
+                self.drag(position);
+            } else {
+                self.release();
+            }
+        } else if mouse_down && !self.mouse_was_down && self.track.contains(position) {
+            self.press(InputSource::Mouse, position);
+        }
+    }
+
+    /// whether the touch drags the slider
+    fn owns(&self, touch: &Touch) -> bool {
+        self.pressed && self.source == InputSource::Touch && touch.id == self.touch_id
+    }
+
+    fn press(&mut self, source: InputSource, position: Vec2) {
+        self.pressed = true;
+        self.source = source;
+        self.drag(position);
+    }
+
+    fn drag(&mut self, position: Vec2) {
+        let value = match self.orientation {
+            SliderOrientation::Horizontal => (position.x - self.track.x) / self.track.w,
+            SliderOrientation::Vertical => 1. - (position.y - self.track.y) / self.track.h,
+        };
+        let value = clamp_value(value);
+        let detent = self
+            .detents
+            .iter()
+            .copied()
+            .filter(|detent| (detent - value).abs() <= self.detent_snap)
+            .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()));
+        self.value = detent.unwrap_or(value);
+    }
+
+    fn release(&mut self) {
+        self.pressed = false;
+        self.source = InputSource::None;
+        if !self.sticky {
+            self.value = self.rest_value;
+        }
+    }
+}
+
+/// clamp the value to `0.0..=1.0`, NaN is `0.0`
+fn clamp_value(value: f32) -> f32 {
+    if value.is_nan() {
+        0.
+    } else {
+        value.clamp(0., 1.)
+    }
+}
+
+/// draw the track and the knob in the colors of the joystick
+fn render_default(track: &Rect, knob: Vec2, _pressed: bool) {
+    draw_rectangle(track.x, track.y, track.w, track.h, BACKGROUND_COLOR);
+    draw_circle(knob.x, knob.y, track.w.min(track.h) / 2., KNOB_COLOR);
+}
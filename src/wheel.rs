@@ -0,0 +1,296 @@
+//! a virtual steering wheel which reports a rotation, e.g. for driving games
+
+use std::f32::consts::{PI, TAU};
+
+use macroquad::prelude::{draw_circle, get_frame_time, Touch, TouchPhase, Vec2};
+
+use crate::{
+    order_touch_phases, InputSource, JoystickInput, MacroquadInput, Spring, BACKGROUND_COLOR,
+    KNOB_COLOR,
+};
+
+/// the rotation (in radians) and angular velocity (in radians per second) below which the
+/// return spring comes to rest
+const SPRING_REST_ANGLE: f32 = 0.001;
+
+/// the radius relative to the wheel in which the pointer doesn't turn the wheel, because
+/// its angle is unstable next to the center
+const CENTER_DEAD_ZONE: f32 = 0.1;
+
+/// renderer of the [`VirtualWheel`], see [`VirtualWheel::set_render`]
+///
+/// the function gets the center, the radius, the rotation and whether the wheel is held
+pub type WheelRenderer = Box<dyn Fn(Vec2, f32, f32, bool)>;
+
+/// a virtual steering wheel which is turned by a touch or the mouse
+///
+/// A touch or a click which starts inside of the wheel turns it by the change of its angle
+/// around the center until it is released, so the wheel doesn't jump to the pointer. The
+/// rotation is positive clockwise on the screen and clamped to the limits, see
+/// [`VirtualWheel::set_limits`]. After the release, the wheel turns back to zero.
+///
+/// # Examples
+/// ```
+/// use macroquad::prelude::{TouchPhase, Vec2};
+/// use macroquad_virtual_joystick::{SimulatedInput, VirtualWheel};
+///
+/// let mut wheel = VirtualWheel::new(100.0, 200.0, 50.0);
+///
+/// // grab the wheel at the top and turn it a quarter to the right
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, Vec2::new(100.0, 160.0));
+/// wheel.update_with_input(&input, 0.0);
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Moved, Vec2::new(140.0, 200.0));
+/// let rotation = wheel.update_with_input(&input, 0.0);
+/// assert!((rotation - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+/// assert!((wheel.value() - 0.5).abs() < 1e-6);
+///
+/// let input = SimulatedInput::new().with_touch(0, TouchPhase::Ended, Vec2::new(140.0, 200.0));
+/// assert_eq!(wheel.update_with_input(&input, 0.0), 0.0);
+/// ```
+pub struct VirtualWheel {
+    center: Vec2,
+    radius: f32,
+    render_fn: WheelRenderer,
+    rotation: f32,
+    min_rotation: f32,
+    max_rotation: f32,
+    return_spring: Option<Spring>,
+    velocity: f32,
+    /// the angle of the pointer in the last update
+    grab_angle: Option<f32>,
+    pressed: bool,
+    source: InputSource,
+    touch_id: u64,
+    mouse_was_down: bool,
+}
+
+impl VirtualWheel {
+    /// create a new wheel
+    ///
+    /// # Arguments
+    /// * `x`, `y`: center of the wheel
+    /// * `radius`: radius of the wheel, which is also the hit area
+    pub fn new(x: f32, y: f32, radius: f32) -> Self {
+        Self {
+            center: Vec2::new(x, y),
+            radius,
+            render_fn: Box::new(render_default),
+            rotation: 0.,
+            min_rotation: -PI,
+            max_rotation: PI,
+            return_spring: None,
+            velocity: 0.,
+            grab_angle: None,
+            pressed: false,
+            source: InputSource::None,
+            touch_id: 0,
+            mouse_was_down: false,
+        }
+    }
+
+    /// set a custom drawing function
+    pub fn set_render(&mut self, render: WheelRenderer) {
+        self.render_fn = render;
+    }
+
+    /// the center of the wheel
+    pub fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    /// move the wheel to a new center, e.g. after the screen was resized
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        self.center = Vec2::new(x, y);
+    }
+
+    /// set the minimum and the maximum rotation (in radians)
+    ///
+    /// The limits can exceed a full turn, e.g. `-3.0 * PI..=3.0 * PI` for the wheel of a car.
+    /// The minimum should not be positive and the maximum should not be negative.
+    ///
+    /// Defaults to half a turn in both directions.
+    pub fn set_limits(&mut self, min_rotation: f32, max_rotation: f32) {
+        self.min_rotation = min_rotation.min(0.);
+        self.max_rotation = max_rotation.max(0.);
+        self.rotation = self.rotation.clamp(self.min_rotation, self.max_rotation);
+    }
+
+    /// set the spring which turns the released wheel back to zero
+    ///
+    /// Defaults to `None`, which turns the wheel back immediately.
+    pub fn set_return_spring(&mut self, return_spring: Option<Spring>) {
+        self.return_spring = return_spring;
+        self.velocity = 0.;
+    }
+
+    /// the rotation (in radians) of the wheel, positive clockwise on the screen
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// the rotation relative to the limits, from `-1.0` (minimum) to `1.0` (maximum)
+    ///
+    /// e.g. the steering of a car
+    pub fn value(&self) -> f32 {
+        if self.rotation > 0. {
+            self.rotation / self.max_rotation
+        } else if self.rotation < 0. {
+            -self.rotation / self.min_rotation
+        } else {
+            0.
+        }
+    }
+
+    /// whether the wheel is held
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// update the wheel with the input of macroquad
+    ///
+    /// this should be called once per frame
+    pub fn update(&mut self) -> f32 {
+        self.update_with_input(&MacroquadInput, get_frame_time())
+    }
+
+    /// update the wheel with the input of macroquad and the given frame time
+    ///
+    /// see [`Joystick::update_with_dt`](crate::Joystick::update_with_dt)
+    pub fn update_with_dt(&mut self, dt: f32) -> f32 {
+        self.update_with_input(&MacroquadInput, dt)
+    }
+
+    /// update the wheel from the input
+    ///
+    /// returns the rotation of the wheel
+    pub fn update_with_input(&mut self, input: &impl JoystickInput, dt: f32) -> f32 {
+        let mut touches = input.touches();
+        let mouse_down = input.is_mouse_down();
+        if touches.is_empty() {
+            self.update_mouse(input.mouse_position(), mouse_down);
+        } else {
+            order_touch_phases(&mut touches);
+            self.update_touch(&touches);
+        }
+        self.mouse_was_down = mouse_down;
+        if !self.pressed {
+            self.update_return(dt);
+        }
+        self.rotation
+    }
+
+    /// render the wheel
+    pub fn render(&self) {
+        (self.render_fn)(self.center, self.radius, self.rotation, self.pressed);
+    }
+
+    fn update_touch(&mut self, touches: &[Touch]) {
+        for touch in touches {
+            match touch.phase {
+                TouchPhase::Started if !self.pressed && self.contains(touch.position) => {
+                    self.touch_id = touch.id;
+                    self.press(InputSource::Touch, touch.position);
+                }
+                TouchPhase::Moved if self.owns(touch) => self.turn(touch.position),
+                TouchPhase::Ended | TouchPhase::Cancelled if self.owns(touch) => {
+                    self.release();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn update_mouse(&mut self, position: Vec2, mouse_down: bool) {
+        if self.pressed {
+            if mouse_down {
+                self.turn(position);
+            } else {
+                self.release();
+            }
+        } else if mouse_down && !self.mouse_was_down && self.contains(position) {
+            self.press(InputSource::Mouse, position);
+        }
+    }
+
+    /// whether the touch holds the wheel
+    fn owns(&self, touch: &Touch) -> bool {
+        self.pressed && self.source == InputSource::Touch && touch.id == self.touch_id
+    }
+
+    fn contains(&self, position: Vec2) -> bool {
+        (position - self.center).length() < self.radius
+    }
+
+    fn press(&mut self, source: InputSource, position: Vec2) {
+        self.pressed = true;
+        self.source = source;
+        self.velocity = 0.;
+        self.grab_angle = self.pointer_angle(position);
+    }
+
+    /// turn the wheel by the change of the angle of the pointer
+    fn turn(&mut self, position: Vec2) {
+        let angle = match self.pointer_angle(position) {
+            Some(angle) => angle,
+            None => return,
+        };
+        if let Some(last) = self.grab_angle {
+            // the shortest way, so crossing the negative x-axis doesn't turn a full circle
+            let delta = (angle - last + PI).rem_euclid(TAU) - PI;
+            self.rotation = (self.rotation + delta).clamp(self.min_rotation, self.max_rotation);
+        }
+        self.grab_angle = Some(angle);
+    }
+
+    /// the angle of the pointer around the center, `None` next to the center
+    fn pointer_angle(&self, position: Vec2) -> Option<f32> {
+        let delta = position - self.center;
+        if !delta.is_finite() || delta.length() <= self.radius * CENTER_DEAD_ZONE {
+            return None;
+        }
+        Some(delta.y.atan2(delta.x))
+    }
+
+    fn release(&mut self) {
+        self.pressed = false;
+        self.source = InputSource::None;
+        self.grab_angle = None;
+        if self.return_spring.is_none() {
+            self.rotation = 0.;
+        }
+    }
+
+    /// turn the released wheel back with the spring
+    fn update_return(&mut self, dt: f32) {
+        let spring = match self.return_spring {
+            Some(spring) => spring,
+            None => return,
+        };
+        if self.rotation == 0. && self.velocity == 0. {
+            return;
+        }
+        spring.integrate(dt, |step| {
+            let acceleration = -self.rotation * spring.stiffness - self.velocity * spring.damping;
+            self.velocity += acceleration * step;
+            self.rotation += self.velocity * step;
+            if self.rotation < self.min_rotation || self.rotation > self.max_rotation {
+                self.rotation = self.rotation.clamp(self.min_rotation, self.max_rotation);
+                self.velocity = 0.;
+            }
+        });
+        let settled =
+            self.rotation.abs() < SPRING_REST_ANGLE && self.velocity.abs() < SPRING_REST_ANGLE;
+        if settled || !self.rotation.is_finite() || !self.velocity.is_finite() {
+            self.rotation = 0.;
+            self.velocity = 0.;
+        }
+    }
+}
+
+/// draw the wheel in the colors of the joystick with a marker at the top of the wheel
+fn render_default(center: Vec2, radius: f32, rotation: f32, _pressed: bool) {
+    draw_circle(center.x, center.y, radius, BACKGROUND_COLOR);
+    let angle = rotation - PI / 2.;
+    let marker = center + Vec2::new(angle.cos(), angle.sin()) * radius * 0.75;
+    draw_circle(marker.x, marker.y, radius * 0.2, KNOB_COLOR);
+}
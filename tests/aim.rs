@@ -0,0 +1,53 @@
+use macroquad::prelude::{TouchPhase, Vec2};
+use macroquad_virtual_joystick::{AimEvent, AimJoystick, JoystickPhase, SimulatedInput};
+
+const CENTER: Vec2 = Vec2::new(100.0, 200.0);
+
+fn touch(aim: &mut AimJoystick, phase: TouchPhase, offset: Vec2) -> AimEvent {
+    let input = SimulatedInput::new().with_touch(0, phase, CENTER + offset);
+    aim.update_with_input(&input, 0.016)
+}
+
+#[test]
+fn aim_keeps_the_angle_in_the_dead_zone_and_shoots_on_release() {
+    let mut aim = AimJoystick::new(CENTER.x, CENTER.y, 50.0);
+    aim.joystick_mut().set_dead_zone(0.2);
+    aim.set_fire_threshold(0.8);
+
+    // inside of the dead zone there is no aim yet
+    let event = touch(&mut aim, TouchPhase::Started, Vec2::new(2.0, 0.0));
+    assert_eq!(event.aim_angle, None);
+    assert!(!event.firing);
+
+    let event = touch(&mut aim, TouchPhase::Moved, Vec2::new(0.0, -15.0));
+    let up = -std::f32::consts::FRAC_PI_2;
+    assert!((event.aim_angle.unwrap() - up).abs() < 1e-6);
+    assert!(!event.firing);
+
+    let event = touch(&mut aim, TouchPhase::Moved, Vec2::new(0.0, -25.0));
+    assert!(event.firing);
+    assert!(aim.is_firing());
+
+    // passing the center keeps the last aim
+    let event = touch(&mut aim, TouchPhase::Moved, Vec2::new(1.0, 0.0));
+    assert!((event.aim_angle.unwrap() - up).abs() < 1e-6);
+    assert!(!event.firing);
+
+    let event = touch(&mut aim, TouchPhase::Ended, Vec2::new(1.0, 0.0));
+    assert_eq!(event.event.phase, JoystickPhase::Release);
+    assert!((event.released_shot.unwrap() - up).abs() < 1e-6);
+    assert_eq!(event.aim_angle, None);
+
+    let event = touch(&mut aim, TouchPhase::Ended, Vec2::new(1.0, 0.0));
+    assert_eq!(event.released_shot, None);
+}
+
+#[test]
+fn release_without_aiming_does_not_shoot() {
+    let mut aim = AimJoystick::new(CENTER.x, CENTER.y, 50.0);
+    aim.joystick_mut().set_dead_zone(0.2);
+    touch(&mut aim, TouchPhase::Started, Vec2::new(2.0, 0.0));
+    let event = touch(&mut aim, TouchPhase::Ended, Vec2::new(2.0, 0.0));
+    assert_eq!(event.event.phase, JoystickPhase::Release);
+    assert_eq!(event.released_shot, None);
+}
@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use macroquad::prelude::{Rect, TouchPhase, Vec2};
+use macroquad_virtual_joystick::{
+    ButtonShape, Joystick, JoystickDirection, JoystickManager, SimulatedInput, TouchButton,
+};
+
+const CENTER: Vec2 = Vec2::new(100.0, 200.0);
+
+#[test]
+fn mouse_press_has_to_start_inside() {
+    let mut button = TouchButton::rect(Rect::new(50.0, 150.0, 100.0, 100.0));
+    // pressed outside and moved in
+    let outside = SimulatedInput::new().with_mouse(Vec2::new(10.0, 10.0), true);
+    button.update_with_input(&outside, 0.1);
+    let inside = SimulatedInput::new().with_mouse(CENTER, true);
+    button.update_with_input(&inside, 0.1);
+    assert!(!button.is_pressed());
+
+    let up = SimulatedInput::new().with_mouse(CENTER, false);
+    button.update_with_input(&up, 0.1);
+    button.update_with_input(&inside, 0.1);
+    assert!(button.is_just_pressed());
+    assert!(!button.is_held());
+    // leaving the button keeps it pressed
+    button.update_with_input(&outside, 0.1);
+    assert!(button.is_held());
+    assert!((button.hold_time() - 0.2).abs() < 1e-6);
+
+    button.update_with_input(&up, 0.1);
+    assert!(button.is_just_released());
+    assert!(!button.is_pressed());
+    button.update_with_input(&up, 0.1);
+    assert!(!button.is_just_released());
+}
+
+#[test]
+fn only_the_pressing_touch_releases() {
+    let mut button = TouchButton::circle(CENTER.x, CENTER.y, 20.0);
+    assert!(button.shape().contains(CENTER + Vec2::new(0.0, 19.0)));
+    assert_eq!(button.shape().bounds(), Rect::new(80.0, 180.0, 40.0, 40.0));
+
+    let input = SimulatedInput::new().with_touch(3, TouchPhase::Started, CENTER);
+    button.update_with_input(&input, 0.0);
+    assert!(button.is_just_pressed());
+    let input = SimulatedInput::new()
+        .with_touch(3, TouchPhase::Stationary, CENTER)
+        .with_touch(4, TouchPhase::Started, CENTER)
+        .with_touch(5, TouchPhase::Ended, CENTER);
+    button.update_with_input(&input, 0.0);
+    assert!(button.is_held());
+    let input = SimulatedInput::new().with_touch(3, TouchPhase::Cancelled, CENTER);
+    button.update_with_input(&input, 0.0);
+    assert!(button.is_just_released());
+}
+
+#[test]
+fn disabled_button_ignores_the_input() {
+    let mut button = TouchButton::circle(CENTER.x, CENTER.y, 20.0);
+    let down = SimulatedInput::new().with_mouse(CENTER, true);
+    button.update_with_input(&down, 0.0);
+    assert!(button.is_pressed());
+
+    button.set_enabled(false);
+    assert!(!button.is_pressed());
+    button.update_with_input(&down, 0.0);
+    assert!(!button.is_pressed());
+
+    // the held click doesn't press the enabled button
+    button.set_enabled(true);
+    button.update_with_input(&down, 0.0);
+    assert!(!button.is_pressed());
+    button.update_with_input(&SimulatedInput::new().with_mouse(CENTER, false), 0.0);
+    button.update_with_input(&down, 0.0);
+    assert!(button.is_just_pressed());
+}
+
+#[test]
+fn callbacks_get_the_hold_time() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut button = TouchButton::circle(CENTER.x, CENTER.y, 20.0);
+    let press_log = Rc::clone(&log);
+    button.on_press(Box::new(move |time| {
+        press_log.borrow_mut().push(("press", time))
+    }));
+    let release_log = Rc::clone(&log);
+    button.on_release(Box::new(move |time| {
+        release_log.borrow_mut().push(("release", time))
+    }));
+
+    for phase in [
+        TouchPhase::Started,
+        TouchPhase::Stationary,
+        TouchPhase::Ended,
+    ] {
+        let input = SimulatedInput::new().with_touch(0, phase, CENTER);
+        button.update_with_input(&input, 0.25);
+    }
+    assert_eq!(*log.borrow(), [("press", 0.0), ("release", 0.5)]);
+}
+
+#[test]
+fn manager_assigns_touches_to_joysticks_before_buttons() {
+    let mut manager = JoystickManager::new();
+    manager.add("stick", Joystick::new(CENTER.x, CENTER.y, 50.0));
+    manager.add_button(
+        "jump",
+        TouchButton::new(ButtonShape::Rect(Rect::new(0.0, 0.0, 400.0, 400.0))),
+    );
+
+    let input =
+        SimulatedInput::new().with_touch(0, TouchPhase::Started, CENTER + Vec2::new(10.0, 0.0));
+    manager.update_with_input(&input, 0.0);
+    assert_eq!(
+        manager.event("stick").unwrap().direction,
+        JoystickDirection::Right
+    );
+    assert!(!manager.button("jump").unwrap().is_pressed());
+
+    let input = SimulatedInput::new()
+        .with_touch(0, TouchPhase::Stationary, CENTER + Vec2::new(10.0, 0.0))
+        .with_touch(1, TouchPhase::Started, Vec2::new(300.0, 300.0));
+    manager.update_with_input(&input, 0.0);
+    assert!(manager.button_at(0).unwrap().is_just_pressed());
+}
@@ -0,0 +1,257 @@
+use macroquad::prelude::{Rect, Vec2, BLUE, RED};
+use macroquad_virtual_joystick::{
+    Joystick, JoystickAxis, JoystickBuilder, JoystickConfig, JoystickConfigError, JoystickStyle,
+    UiScale,
+};
+
+fn builder() -> JoystickBuilder {
+    JoystickBuilder::new(100.0, 200.0, 50.0)
+}
+
+#[test]
+fn builder_reports_each_conflict() {
+    assert!(builder().build().is_ok());
+    let cases = [
+        (
+            JoystickBuilder::new(100.0, 200.0, 0.0),
+            JoystickConfigError::InvalidSize(0.0),
+        ),
+        (
+            builder().knob_size(60.0),
+            JoystickConfigError::KnobLargerThanBackground {
+                knob_size: 60.0,
+                size: 50.0,
+            },
+        ),
+        (
+            builder()
+                .renderers(|_, _, _, _| {}, |_, _, _, _| {})
+                .knob_size(60.0),
+            JoystickConfigError::KnobLargerThanBackground {
+                knob_size: 60.0,
+                size: 50.0,
+            },
+        ),
+        (
+            builder().knob_size(-10.0),
+            JoystickConfigError::InvalidKnobSize(-10.0),
+        ),
+        (
+            builder().knob_size(f32::INFINITY),
+            JoystickConfigError::InvalidKnobSize(f32::INFINITY),
+        ),
+        (
+            builder().bands(&[0.7, 0.3]),
+            JoystickConfigError::BandsOutOfOrder(vec![0.7, 0.3]),
+        ),
+        (
+            builder().axis_scale(Vec2::new(1.0, 0.0)),
+            JoystickConfigError::InvalidAxisScale(Vec2::new(1.0, 0.0)),
+        ),
+        (
+            builder().grab_forgiveness(-1.0),
+            JoystickConfigError::InvalidGrabForgiveness(-1.0),
+        ),
+        (
+            builder().commit_distance(60.0),
+            JoystickConfigError::CommitDistanceTooLarge {
+                commit_distance: 60.0,
+                size: 50.0,
+            },
+        ),
+        (
+            builder().time_scale(-1.0),
+            JoystickConfigError::InvalidTimeScale(-1.0),
+        ),
+        (
+            builder().dead_zone(0.5).saturation(0.5),
+            JoystickConfigError::InvalidRadialZones {
+                dead_zone: 0.5,
+                saturation: 0.5,
+            },
+        ),
+        (
+            builder().hit_rect(Rect::new(0.0, 0.0, 100.0, 0.0)),
+            JoystickConfigError::EmptyHitRect(Rect::new(0.0, 0.0, 100.0, 0.0)),
+        ),
+        (
+            builder().travel_radius(0.0),
+            JoystickConfigError::InvalidTravelRadius(0.0),
+        ),
+    ];
+    for (builder, expected) in cases {
+        assert_eq!(builder.build().unwrap_err(), expected);
+    }
+    assert!(matches!(
+        JoystickBuilder::new(0.0, 0.0, f32::NAN).build(),
+        Err(JoystickConfigError::InvalidSize(size)) if size.is_nan()
+    ));
+    assert!(matches!(
+        builder().knob_size(f32::NAN).build(),
+        Err(JoystickConfigError::InvalidKnobSize(knob_size)) if knob_size.is_nan()
+    ));
+}
+
+#[test]
+fn validate_after_runtime_setters() {
+    let mut joystick = builder().build().unwrap();
+    joystick.set_grab_forgiveness(0.0);
+    assert_eq!(
+        joystick.validate(),
+        Err(JoystickConfigError::InvalidGrabForgiveness(0.0))
+    );
+    joystick.set_grab_forgiveness(1.5);
+    assert_eq!(joystick.validate(), Ok(()));
+}
+
+#[test]
+fn constructor_clamps_silently() {
+    let joystick = Joystick::new(100.0, 200.0, -5.0);
+    assert_eq!(joystick.validate(), Ok(()));
+}
+
+#[test]
+fn setters_clamp_silently() {
+    let mut joystick = builder().build().unwrap();
+    joystick.set_hit_radius(-1.0);
+    assert_eq!(joystick.hit_radius(), 0.5);
+    joystick.set_travel_radius(f32::NAN);
+    assert_eq!(joystick.travel_radius(), 0.5);
+    joystick.set_time_scale(-1.0);
+    joystick.set_smoothing(Some(0.0));
+    joystick.set_smoothing(Some(2.0));
+    assert_eq!(joystick.validate(), Ok(()));
+}
+
+#[test]
+fn removing_the_style_restores_the_knob() {
+    let style = JoystickStyle {
+        knob_scale: 0.8,
+        hover_color: Some(RED),
+        ..JoystickStyle::default()
+    };
+    let mut joystick = Joystick::with_style(100.0, 200.0, 50.0, style);
+    assert_eq!(joystick.knob_size(), 40.0);
+    joystick.set_hover_color(Some(BLUE));
+    assert_eq!(joystick.style().unwrap().hover_color, Some(BLUE));
+
+    joystick.set_style(None);
+    assert_eq!(joystick.knob_size(), 25.0);
+}
+
+#[test]
+fn style_clamps_the_knob() {
+    let style = JoystickStyle {
+        knob_scale: 2.0,
+        ..JoystickStyle::default()
+    };
+    let mut joystick = Joystick::with_style(100.0, 200.0, 50.0, style);
+    assert_eq!(joystick.style().unwrap().knob_scale, 2.0);
+    assert_eq!(joystick.validate(), Ok(()));
+    joystick.set_style(None);
+    assert!(joystick.style().is_none());
+}
+
+#[test]
+fn config_round_trips_through_json() {
+    let config: JoystickConfig = serde_json::from_str(
+        r#"{
+            "x": 100.0,
+            "y": 200.0,
+            "size": 50.0,
+            "knob_size": 20.0,
+            "mode": { "Floating": {
+                "activation": { "x": 0.0, "y": 100.0, "w": 200.0, "h": 200.0 },
+                "return_on_release": true
+            } },
+            "axis": "Vertical",
+            "style": {
+                "background_color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 0.5 },
+                "knob_color": { "r": 1.0, "g": 1.0, "b": 1.0, "a": 1.0 },
+                "knob_texture": "knob.png",
+                "idle_alpha": 0.5,
+                "active_alpha": 1.0,
+                "border_width": 3.0
+            }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(config.saturation, 1.0);
+    let mut joystick = Joystick::from_config(&config).unwrap();
+    assert_eq!(joystick.axis(), JoystickAxis::Vertical);
+    assert!((joystick.style().unwrap().knob_scale - 0.4).abs() < 1e-6);
+    assert_eq!(joystick.style().unwrap().border_width, 3.0);
+
+    // the player moves the joystick
+    joystick.set_position(150.0, 250.0);
+    let saved = joystick.to_config();
+    assert_eq!((saved.x, saved.y), (150.0, 250.0));
+    assert_eq!(saved.mode, config.mode);
+    assert_eq!(saved.style, config.style);
+    let json = serde_json::to_string(&saved).unwrap();
+    let loaded: JoystickConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, saved);
+
+    let invalid = JoystickConfig {
+        dead_zone: 1.5,
+        ..config
+    };
+    assert!(matches!(
+        Joystick::from_config(&invalid),
+        Err(JoystickConfigError::InvalidRadialZones { .. })
+    ));
+}
+
+#[test]
+fn ui_scale_scales_the_lengths() {
+    let joystick = builder()
+        .knob_size(20.0)
+        .dead_zone(0.1)
+        .ui_scale(UiScale(1.5))
+        .build()
+        .unwrap();
+    let config = joystick.to_config();
+    assert_eq!((config.x, config.y), (150.0, 300.0));
+    assert_eq!(config.size, 75.0);
+    assert_eq!(config.knob_size, Some(30.0));
+    assert_eq!(config.dead_zone, 0.1);
+    let joystick = builder()
+        .travel_radius(40.0)
+        .ui_scale(UiScale(1.5))
+        .build()
+        .unwrap();
+    assert_eq!(joystick.travel_radius(), 60.0);
+
+    let portrait = UiScale::fit_shorter_side(1.0, Vec2::new(600.0, 1000.0));
+    let joystick = JoystickBuilder::new(0.2, 0.8, 0.25)
+        .ui_scale(portrait)
+        .build()
+        .unwrap();
+    assert_eq!(joystick.size(), 150.0);
+    assert_eq!(joystick.center(), Vec2::new(120.0, 480.0));
+    assert_eq!(UiScale::fit_shorter_side(0.0, Vec2::ONE), UiScale::ONE);
+
+    assert_eq!(
+        builder().ui_scale(UiScale(0.0)).build().unwrap_err(),
+        JoystickConfigError::InvalidSize(0.0)
+    );
+}
+
+#[test]
+fn builder_applies_the_knob_size_after_the_style() {
+    let style = JoystickStyle {
+        knob_scale: 0.8,
+        ..JoystickStyle::default()
+    };
+    let joystick = builder()
+        .style(style)
+        .axis(JoystickAxis::Vertical)
+        .build()
+        .unwrap();
+    assert_eq!(joystick.to_config().knob_size, Some(40.0));
+    assert_eq!(joystick.axis(), JoystickAxis::Vertical);
+
+    let joystick = builder().style(style).knob_size(20.0).build().unwrap();
+    assert_eq!(joystick.to_config().knob_size, Some(20.0));
+    assert!(joystick.style().is_some());
+}
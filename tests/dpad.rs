@@ -0,0 +1,85 @@
+use macroquad::prelude::{TouchPhase, Vec2};
+use macroquad_virtual_joystick::{DPad, DPadMode, JoystickDirection, SimulatedInput};
+
+const CENTER: Vec2 = Vec2::new(100.0, 200.0);
+const SIZE: f32 = 80.0;
+
+#[test]
+fn diagonal_presses_both_directions() {
+    let mut dpad = DPad::new(CENTER.x, CENTER.y, SIZE, DPadMode::EightWay);
+    let position = CENTER + Vec2::new(20.0, 20.0);
+    let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, position);
+    assert_eq!(dpad.update_with_input(&input), JoystickDirection::DownRight);
+    assert!(dpad.is_pressed(JoystickDirection::DownRight));
+    assert!(dpad.is_pressed(JoystickDirection::Down));
+    assert!(dpad.is_pressed(JoystickDirection::Right));
+    assert!(!dpad.is_pressed(JoystickDirection::Up));
+    assert!(!dpad.is_pressed(JoystickDirection::DownLeft));
+    assert!(!dpad.is_pressed(JoystickDirection::Idle));
+
+    // the same position is snapped to an axis in four-way mode
+    let mut dpad = DPad::new(CENTER.x, CENTER.y, SIZE, DPadMode::FourWay);
+    let position = CENTER + Vec2::new(20.0, 21.0);
+    let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, position);
+    assert_eq!(dpad.update_with_input(&input), JoystickDirection::Down);
+    assert!(!dpad.is_pressed(JoystickDirection::Right));
+}
+
+#[test]
+fn drag_slides_between_zones() {
+    let mut dpad = DPad::new(CENTER.x, CENTER.y, SIZE, DPadMode::FourWay);
+    let drag = |dpad: &mut DPad, offset: Vec2, down: bool| {
+        let input = SimulatedInput::new().with_mouse(CENTER + offset, down);
+        dpad.update_with_input(&input)
+    };
+    // the dead zone
+    assert_eq!(
+        drag(&mut dpad, Vec2::new(5.0, 0.0), true),
+        JoystickDirection::Idle
+    );
+    assert!(dpad.is_pressed(JoystickDirection::Idle));
+    assert_eq!(
+        drag(&mut dpad, Vec2::new(-30.0, 0.0), true),
+        JoystickDirection::Left
+    );
+    // leaving the pad keeps the direction of the zone
+    assert_eq!(
+        drag(&mut dpad, Vec2::new(0.0, -100.0), true),
+        JoystickDirection::Up
+    );
+    assert_eq!(
+        drag(&mut dpad, Vec2::new(0.0, -100.0), false),
+        JoystickDirection::Idle
+    );
+    // a press outside of the pad is ignored
+    assert_eq!(
+        drag(&mut dpad, Vec2::new(0.0, -100.0), true),
+        JoystickDirection::Idle
+    );
+}
+
+#[test]
+fn sliding_to_a_diagonal_only_presses_the_new_direction() {
+    let mut dpad = DPad::new(CENTER.x, CENTER.y, SIZE, DPadMode::EightWay);
+    let input =
+        SimulatedInput::new().with_touch(0, TouchPhase::Started, CENTER + Vec2::new(0.0, -30.0));
+    dpad.update_with_input(&input);
+    assert!(dpad.is_just_pressed(JoystickDirection::Up));
+    assert!(dpad.is_just_released(JoystickDirection::Idle));
+
+    let input =
+        SimulatedInput::new().with_touch(0, TouchPhase::Moved, CENTER + Vec2::new(20.0, -20.0));
+    assert_eq!(dpad.update_with_input(&input), JoystickDirection::UpRight);
+    assert!(!dpad.is_just_pressed(JoystickDirection::Up));
+    assert!(dpad.is_just_pressed(JoystickDirection::Right));
+    assert!(dpad.is_just_pressed(JoystickDirection::UpRight));
+
+    let input =
+        SimulatedInput::new().with_touch(0, TouchPhase::Ended, CENTER + Vec2::new(20.0, -20.0));
+    dpad.update_with_input(&input);
+    assert!(dpad.is_just_released(JoystickDirection::Up));
+    assert!(dpad.is_just_released(JoystickDirection::Right));
+    assert!(dpad.is_just_pressed(JoystickDirection::Idle));
+    dpad.update_with_input(&SimulatedInput::new());
+    assert!(!dpad.is_just_released(JoystickDirection::Up));
+}
@@ -0,0 +1,843 @@
+use std::cell::RefCell;
+use std::f32::consts::TAU;
+use std::rc::Rc;
+
+use macroquad::prelude::{Rect, TouchPhase, Vec2};
+use macroquad_virtual_joystick::{
+    DirectionResolver, Easing, GamepadStickAdapter, Joystick, JoystickAxis, JoystickDirection,
+    JoystickEvent, JoystickFeedback, JoystickHarness, JoystickMode, ResponseCurve, RimPersistence,
+    SimulatedInput, Spring, StickLike, Turbo,
+};
+
+const CENTER: Vec2 = Vec2::new(100.0, 200.0);
+const SIZE: f32 = 50.0;
+
+fn harness() -> JoystickHarness {
+    JoystickHarness::new(Joystick::new(CENTER.x, CENTER.y, SIZE))
+}
+
+/// a long drag through the center, around the rim and beyond the background
+fn scripted_drag(harness: &mut JoystickHarness) -> Vec<JoystickEvent> {
+    let mut events = harness.drag_path(CENTER, CENTER + Vec2::new(40.0, 0.0), 20);
+    events.extend(harness.drag_path(CENTER + Vec2::new(40.0, 0.0), CENTER, 20));
+    for step in 0..=72 {
+        let angle = step as f32 / 72.0 * TAU;
+        let radius = (step % 30) as f32;
+        events.push(harness.drag_to(CENTER + Vec2::new(angle.cos(), angle.sin()) * radius));
+    }
+    events.extend(harness.drag_path(CENTER, CENTER + Vec2::new(-3.0, 90.0), 30));
+    events.push(harness.release());
+    events
+}
+
+fn assert_consistent(events: &[JoystickEvent]) {
+    for event in events {
+        let idle = event.direction == JoystickDirection::Idle;
+        assert_eq!(idle, event.intensity == 0.0, "{:?}", event);
+        assert_eq!(idle, event.polar().is_none(), "{:?}", event);
+        if idle {
+            assert_eq!(event.angle, 0.0, "{:?}", event);
+        } else {
+            assert_eq!(
+                JoystickDirection::from_radians(event.angle),
+                event.direction,
+                "{:?}",
+                event
+            );
+        }
+        assert!(!event.intensity.is_nan() && !event.angle.is_nan());
+    }
+}
+
+#[test]
+fn direction_angle_and_intensity_are_consistent() {
+    let mut harness = harness();
+    let events = scripted_drag(&mut harness);
+    assert!(events.iter().any(|event| event.intensity == 0.0));
+    assert_consistent(&events);
+}
+
+#[test]
+fn direction_angle_and_intensity_are_consistent_with_turbo() {
+    let mut harness = harness();
+    harness.joystick_mut().set_turbo(Some(Turbo {
+        threshold: 0.8,
+        rate_per_sec: 2.0,
+        max: 1.5,
+    }));
+    let events = scripted_drag(&mut harness);
+    assert_consistent(&events);
+}
+
+#[test]
+fn band_hysteresis_prevents_flapping() {
+    let mut harness = harness();
+    harness.joystick_mut().set_bands(&[0.5]);
+    harness.joystick_mut().set_band_hysteresis(0.05);
+
+    // slowly sweep up and down across the threshold with some jitter
+    let mut intensities: Vec<f32> = (0..=100).map(|i| 0.3 + 0.004 * i as f32).collect();
+    intensities.extend((0..=100).map(|i| 0.7 - 0.004 * i as f32));
+    let mut events = Vec::new();
+    harness.press(CENTER + Vec2::new(SIZE / 2.0 * 0.3, 0.0));
+    for (i, intensity) in intensities.iter().enumerate() {
+        let jitter = if i % 2 == 0 { 0.02 } else { -0.02 };
+        let x = SIZE / 2.0 * (intensity + jitter);
+        events.push(harness.drag_to(CENTER + Vec2::new(x, 0.0)));
+    }
+
+    let transitions = events
+        .windows(2)
+        .filter(|pair| pair[0].band != pair[1].band)
+        .count();
+    assert_eq!(transitions, 2);
+    assert_eq!(events.first().unwrap().band, 1);
+    assert!(events.iter().any(|event| event.band == 2));
+    assert_eq!(events.last().unwrap().band, 1);
+}
+
+fn event_at(offset: Vec2) -> JoystickEvent {
+    harness().press(CENTER + offset)
+}
+
+#[test]
+fn approx_eq_wraps_angles() {
+    // slightly below and above the seam at ±π
+    let a = event_at(Vec2::new(-20.0, 0.1));
+    let b = event_at(Vec2::new(-20.0, -0.1));
+    assert!(a.angle > 3.1 && b.angle < -3.1);
+    assert!(a.approx_eq(&b, 0.01, 0.02));
+    assert!(b.approx_eq(&a, 0.01, 0.02));
+    assert!(!a.approx_eq(&b, 0.01, 0.001));
+}
+
+#[test]
+fn approx_eq_idle_boundary() {
+    let idle = JoystickEvent::default();
+    let tiny = event_at(Vec2::new(0.001, 0.0));
+    assert!(idle.is_idle());
+    assert!(!tiny.is_idle());
+    assert!(idle.approx_eq(&JoystickEvent::default(), 0.0, 0.0));
+    // idle is never close to a non-idle event, however small it is
+    assert!(!idle.approx_eq(&tiny, 1.0, 10.0));
+    assert!(!tiny.approx_eq(&idle, 1.0, 10.0));
+}
+
+fn rim_harness() -> JoystickHarness {
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .set_rim_persistence(Some(RimPersistence {
+            min_intensity: 0.8,
+            max_frames: 2,
+            max_duration: 0.1,
+        }));
+    harness
+}
+
+#[test]
+fn rim_persistence_filters_short_dips() {
+    let mut harness = rim_harness();
+    harness.press(CENTER + Vec2::new(24.0, 0.0));
+    let event = harness.drag_to(CENTER + Vec2::new(0.0, 24.0));
+    assert_eq!(event.direction, JoystickDirection::Down);
+
+    // the pointer slips into the center for one frame
+    let event = harness.drag_to(CENTER);
+    assert_eq!(event.direction, JoystickDirection::Down);
+    assert_eq!(event.intensity, 0.96);
+    let event = harness.drag_to(CENTER + Vec2::new(-24.0, 0.0));
+    assert_eq!(event.direction, JoystickDirection::Left);
+
+    // a release resets immediately
+    let event = harness.release();
+    assert!(event.is_idle());
+}
+
+#[test]
+fn rim_persistence_passes_long_dips() {
+    let mut harness = rim_harness();
+    harness.press(CENTER + Vec2::new(24.0, 0.0));
+    let events: Vec<_> = (0..4).map(|_| harness.drag_to(CENTER)).collect();
+    assert!(!events[0].is_idle());
+    assert!(!events[1].is_idle());
+    assert!(events[2].is_idle());
+    assert!(events[3].is_idle());
+}
+
+#[test]
+fn rim_persistence_ignores_weak_events() {
+    let mut harness = rim_harness();
+    harness.press(CENTER + Vec2::new(10.0, 0.0));
+    let event = harness.drag_to(CENTER);
+    assert!(event.is_idle());
+}
+
+#[test]
+fn axes_of_circular_gate() {
+    let d = std::f32::consts::FRAC_1_SQRT_2;
+    let corners = [
+        (Vec2::new(100.0, 100.0), Vec2::new(d, d)),
+        (Vec2::new(-100.0, 100.0), Vec2::new(-d, d)),
+        (Vec2::new(-100.0, -100.0), Vec2::new(-d, -d)),
+        (Vec2::new(100.0, -100.0), Vec2::new(d, -d)),
+        (Vec2::new(100.0, 0.0), Vec2::new(1.0, 0.0)),
+        (Vec2::new(0.0, -100.0), Vec2::new(0.0, -1.0)),
+    ];
+    for (offset, axes) in corners {
+        let mut harness = harness();
+        harness.press(CENTER);
+        let event = harness.drag_to(CENTER + offset);
+        assert!(event.axes().abs_diff_eq(axes, 1e-6), "{:?}", event.axes());
+    }
+    assert_eq!(JoystickEvent::default().axes(), Vec2::ZERO);
+}
+
+/// the axes after dragging the configured joystick by the offset
+fn axes_after(configure: impl FnOnce(&mut Joystick), offset: Vec2) -> Vec2 {
+    let mut harness = harness();
+    configure(harness.joystick_mut());
+    harness.press(CENTER);
+    harness.drag_to(CENTER + offset).axes()
+}
+
+#[test]
+fn axes_follow_the_shaped_event() {
+    // the knob is constrained to the axis, so the corner is the end of the axis
+    let axes = axes_after(
+        |joystick| joystick.set_axis(JoystickAxis::Horizontal),
+        Vec2::new(100.0, 100.0),
+    );
+    assert!(axes.abs_diff_eq(Vec2::new(1.0, 0.0), 1e-6), "{:?}", axes);
+
+    // the vertical axis reaches half of the intensity at the rim
+    let axes = axes_after(
+        |joystick| joystick.set_axis_scale(Vec2::new(1.0, 0.5)),
+        Vec2::new(0.0, 25.0),
+    );
+    assert!(axes.abs_diff_eq(Vec2::new(0.0, 0.5), 1e-6), "{:?}", axes);
+
+    // the dead zone is removed from the intensity
+    let axes = axes_after(
+        |joystick| joystick.set_dead_zone(0.2),
+        Vec2::new(0.0, -15.0),
+    );
+    assert!(axes.abs_diff_eq(Vec2::new(0.0, -0.5), 1e-6), "{:?}", axes);
+
+    // the snapped knob reports the center of the sector with the full intensity
+    let axes = axes_after(
+        |joystick| joystick.set_snap_knob(true),
+        Vec2::new(20.0, 5.0),
+    );
+    let intensity = 425.0f32.sqrt() / 25.0;
+    assert!(
+        axes.abs_diff_eq(Vec2::new(intensity, 0.0), 1e-6),
+        "{:?}",
+        axes
+    );
+}
+
+#[test]
+fn degenerate_sizes_never_produce_nan() {
+    for size in [0.0, -10.0, f32::NAN, f32::NEG_INFINITY] {
+        let mut harness = JoystickHarness::new(Joystick::new(CENTER.x, CENTER.y, size));
+        let mut events = vec![harness.press(CENTER)];
+        events.extend(harness.drag_path(CENTER, CENTER + Vec2::new(30.0, 5.0), 5));
+        events.push(harness.release());
+        for event in &events {
+            assert!(!event.intensity.is_nan(), "size {}: {:?}", size, event);
+            assert!(!event.angle.is_nan(), "size {}: {:?}", size, event);
+            assert!(!event.axes().is_nan(), "size {}: {:?}", size, event);
+        }
+        assert_consistent(&events);
+    }
+}
+
+fn scaled_harness(axis_scale: Vec2) -> JoystickHarness {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_axis_scale(axis_scale);
+    JoystickHarness::new(joystick)
+}
+
+#[test]
+fn axis_scale_reduces_vertical_intensity() {
+    let mut scaled = scaled_harness(Vec2::new(1.0, 0.6));
+    scaled.press(CENTER);
+    let up = scaled.drag_to(CENTER + Vec2::new(0.0, -SIZE / 2.0));
+    assert_eq!(up.direction, JoystickDirection::Up);
+    assert!((up.intensity - 0.6).abs() < 1e-5, "{:?}", up);
+    let right = scaled.drag_to(CENTER + Vec2::new(SIZE / 2.0, 0.0));
+    assert!((right.intensity - 1.0).abs() < 1e-5, "{:?}", right);
+
+    // a drag just inside of the diagonal sector is pulled towards the horizontal axis
+    assert_eq!(
+        harness().press(CENTER + Vec2::new(10.0, -6.0)).direction,
+        JoystickDirection::UpRight
+    );
+    let diagonal = scaled.drag_to(CENTER + Vec2::new(10.0, -6.0));
+    assert_eq!(diagonal.direction, JoystickDirection::Right);
+    assert_consistent(&scripted_drag(&mut scaled));
+}
+
+#[test]
+fn unit_axis_scale_is_a_no_op() {
+    let expected = scripted_drag(&mut harness());
+    let actual = scripted_drag(&mut scaled_harness(Vec2::ONE));
+    assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
+}
+
+#[test]
+fn knob_spring_doesnt_change_events() {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_knob_spring(Some(Spring {
+        stiffness: 400.0,
+        damping: 10.0,
+    }));
+    let mut sprung = JoystickHarness::new(joystick);
+    sprung.set_frame_time(1.0 / 60.0);
+    let mut plain = harness();
+    plain.set_frame_time(1.0 / 60.0);
+
+    let mut expected = scripted_drag(&mut plain);
+    let mut actual = scripted_drag(&mut sprung);
+    // reverse from full left to full right in one frame
+    expected.push(plain.press(CENTER - Vec2::new(20.0, 0.0)));
+    actual.push(sprung.press(CENTER - Vec2::new(20.0, 0.0)));
+    expected.push(plain.drag_to(CENTER + Vec2::new(40.0, 0.0)));
+    actual.push(sprung.drag_to(CENTER + Vec2::new(40.0, 0.0)));
+    assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
+}
+
+#[test]
+fn knob_spring_survives_a_huge_frame_time() {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_knob_spring(Some(Spring {
+        stiffness: 400.0,
+        damping: 10.0,
+    }));
+    let mut harness = JoystickHarness::new(joystick);
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    for dt in [1e9, f32::MAX, f32::INFINITY, f32::NAN] {
+        harness.set_frame_time(dt);
+        harness.drag_to(CENTER + Vec2::new(0.0, 20.0));
+        assert!(harness.joystick().rendered_knob().is_finite());
+    }
+    harness.set_frame_time(1.0);
+    harness.drag_to(CENTER + Vec2::new(0.0, 20.0));
+    assert_eq!(
+        harness.joystick().rendered_knob(),
+        CENTER + Vec2::new(0.0, 20.0)
+    );
+}
+
+/// a joystick with several stateful features
+fn stateful_joystick() -> Joystick {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_track_rotation(true);
+    joystick.set_repeat(0.2, 0.1);
+    joystick.set_turbo(Some(Turbo {
+        threshold: 0.5,
+        rate_per_sec: 1.0,
+        max: 2.0,
+    }));
+    joystick.set_bands(&[0.5]);
+    joystick.set_direction_lock(true);
+    joystick.set_rim_persistence(Some(RimPersistence {
+        min_intensity: 0.5,
+        max_frames: 10,
+        max_duration: 1.0,
+    }));
+    joystick.set_knob_spring(Some(Spring {
+        stiffness: 400.0,
+        damping: 10.0,
+    }));
+    joystick
+}
+
+#[test]
+fn clear_restores_a_fresh_joystick() {
+    let mut used = JoystickHarness::new(stateful_joystick());
+    used.set_frame_time(0.1);
+    let mut fresh = JoystickHarness::new(stateful_joystick());
+    fresh.set_frame_time(0.1);
+
+    // drag around and latch the direction
+    scripted_drag(&mut used);
+    used.press(CENTER);
+    used.drag_path(CENTER, CENTER + Vec2::new(30.0, 0.0), 5);
+    used.release();
+    assert!(used.joystick().is_locked());
+
+    used.joystick_mut().clear();
+    assert!(!used.joystick().is_locked());
+    assert_eq!(used.joystick_mut().take_repeats().count(), 0);
+    assert_eq!(used.joystick().seconds_since_last_activity(), 0.0);
+
+    let expected = vec![
+        fresh.advance(0.1),
+        fresh.press(CENTER + Vec2::new(0.0, 20.0)),
+        fresh.advance(0.5),
+        fresh.release(),
+    ];
+    let actual = vec![
+        used.advance(0.1),
+        used.press(CENTER + Vec2::new(0.0, 20.0)),
+        used.advance(0.5),
+        used.release(),
+    ];
+    assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
+    assert_eq!(
+        used.joystick_mut().take_repeats().collect::<Vec<_>>(),
+        fresh.joystick_mut().take_repeats().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn clear_moves_the_joystick_home() {
+    let mut floating = harness();
+    floating.joystick_mut().set_mode(JoystickMode::Floating {
+        activation: Rect::new(0.0, 0.0, 400.0, 400.0),
+        return_on_release: false,
+    });
+    floating.press(Vec2::new(300.0, 300.0));
+    assert_eq!(floating.joystick().center(), Vec2::new(300.0, 300.0));
+    floating.joystick_mut().clear();
+    assert_eq!(floating.joystick().center(), CENTER);
+    assert_eq!(floating.joystick().rendered_knob(), CENTER);
+
+    let mut following = harness();
+    following.joystick_mut().set_follow(true);
+    following.press(CENTER);
+    following.drag_to(CENTER + Vec2::new(100.0, 0.0));
+    assert_ne!(following.joystick().center(), CENTER);
+    following.joystick_mut().clear();
+    assert_eq!(following.joystick().center(), CENTER);
+    assert_eq!(following.joystick().rendered_knob(), CENTER);
+}
+
+#[test]
+fn axis_response_shapes_diagonal_drag() {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_axis_response(ResponseCurve::Linear, ResponseCurve::Power(2.0));
+    let mut curved = JoystickHarness::new(joystick);
+    curved.press(CENTER);
+    let event = curved.drag_to(CENTER + Vec2::new(40.0, 40.0));
+    let d = std::f32::consts::FRAC_1_SQRT_2;
+    assert!(
+        event.axes().abs_diff_eq(Vec2::new(d, 0.5), 1e-5),
+        "{:?}",
+        event.axes()
+    );
+    assert_eq!(event.direction, JoystickDirection::DownRight);
+    assert_consistent(&scripted_drag(&mut curved));
+}
+
+#[test]
+fn linear_axis_response_is_a_no_op() {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_axis_response(ResponseCurve::Linear, ResponseCurve::Linear);
+    let expected = scripted_drag(&mut harness());
+    let actual = scripted_drag(&mut JoystickHarness::new(joystick));
+    assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
+}
+
+#[test]
+fn gamepad_adapter_matches_axes() {
+    let mut harness = harness();
+    for offset in [
+        Vec2::new(10.0, -5.0),
+        Vec2::new(-20.0, 3.0),
+        Vec2::new(0.0, 20.0),
+    ] {
+        let event = harness.press(CENTER + offset);
+        let joystick = harness.joystick();
+        let stick = GamepadStickAdapter::new(joystick);
+        assert_eq!(stick.left_stick(), event.axes());
+        assert!(stick.is_pressed());
+
+        let inverted = stick.invert_x(true).invert_y(true);
+        assert_eq!(inverted.left_stick(), -event.axes());
+        harness.release();
+    }
+    let stick = GamepadStickAdapter::new(harness.joystick());
+    assert_eq!(stick.left_stick(), Vec2::ZERO);
+    assert!(!stick.is_pressed());
+}
+
+#[test]
+fn render_state_follows_the_output() {
+    let joystick = Joystick::from_custom_renderers(
+        CENTER.x,
+        CENTER.y,
+        SIZE,
+        SIZE / 2.0,
+        |_, _, _, _| {},
+        |_, _, _, _| {},
+    );
+    let mut harness = JoystickHarness::new(joystick);
+    let state = harness.joystick().render_state();
+    assert!(!state.active);
+    assert_eq!(state.direction, JoystickDirection::Idle);
+
+    let event = harness.press(CENTER + Vec2::new(0.0, -15.0));
+    let state = harness.joystick().render_state();
+    assert!(state.active);
+    assert_eq!(state.direction, JoystickDirection::Up);
+    assert_eq!(state.intensity, event.intensity);
+    assert_eq!(state.angle, event.angle);
+
+    harness.release();
+    assert!(!harness.joystick().render_state().active);
+}
+
+#[test]
+fn radial_zones_remap_intensity_continuously() {
+    let mut harness = harness();
+    harness.joystick_mut().set_dead_zone(0.2);
+    harness.joystick_mut().set_saturation(0.8);
+    harness.press(CENTER);
+    let mut last = 0.0;
+    for step in 0..=50 {
+        let event = harness.drag_to(CENTER + Vec2::new(0.0, step as f32 * 0.5));
+        let normalized = step as f32 * 0.5 / (SIZE / 2.0);
+        if normalized <= 0.2 {
+            assert!(event.is_idle(), "{:?}", event);
+        } else if normalized >= 0.8 {
+            assert_eq!(event.intensity, 1.0);
+        }
+        assert!(event.intensity >= last);
+        assert!(event.intensity - last < 0.05, "jump at {}", step);
+        last = event.intensity;
+    }
+}
+
+#[test]
+fn vertical_axis_drops_horizontal_drags() {
+    let mut harness = harness();
+    harness.joystick_mut().set_axis(JoystickAxis::Vertical);
+    harness.press(CENTER);
+    let event = harness.drag_to(CENTER + Vec2::new(20.0, 0.0));
+    assert!(event.is_idle(), "{:?}", event);
+    for offset in [Vec2::new(15.0, 15.0), Vec2::new(-20.0, 5.0)] {
+        let event = harness.drag_to(CENTER + offset);
+        assert_eq!(event.direction, JoystickDirection::Down);
+        assert!(event.axes().x.abs() < 1e-6);
+        assert_eq!(event.intensity, offset.y / (SIZE / 2.0));
+    }
+    let event = harness.drag_to(CENTER + Vec2::new(5.0, -40.0));
+    assert_eq!(event.direction, JoystickDirection::Up);
+    assert_eq!(event.intensity, 1.0);
+}
+
+#[test]
+fn direction_vector_and_velocity_match_axes() {
+    let mut harness = harness();
+    harness.press(CENTER);
+    for offset in [
+        Vec2::new(3.0, -7.0),
+        Vec2::new(-12.0, 0.5),
+        Vec2::new(40.0, 40.0),
+    ] {
+        let event = harness.drag_to(CENTER + offset);
+        let direction = event.direction_vector();
+        assert!((direction.length() - 1.0).abs() < 1e-6);
+        assert!(direction.abs_diff_eq(offset.normalize(), 1e-5));
+        assert!(event.velocity(1.0).abs_diff_eq(event.axes(), 1e-6));
+    }
+    let event = harness.release();
+    assert_eq!(event.direction_vector(), Vec2::ZERO);
+    assert_eq!(event.velocity(10.0), Vec2::ZERO);
+}
+
+#[test]
+fn eight_way_resolver_agrees_with_from_radians() {
+    let resolver = DirectionResolver::default();
+    for step in 0..360 {
+        // avoid the exact boundaries between the sectors
+        let angle = (step as f32 + 0.3).to_radians() - std::f32::consts::PI;
+        assert_eq!(
+            resolver.direction(angle),
+            JoystickDirection::from_radians(angle),
+            "angle {}",
+            angle
+        );
+    }
+}
+
+#[test]
+fn sixteen_way_sectors() {
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .set_direction_resolver(DirectionResolver::sixteen_way());
+    // about 22.5 degrees clockwise of the positive x-axis
+    let event = harness.press(CENTER + Vec2::new(20.0, 8.3));
+    assert_eq!(event.sector, 1);
+    let event = harness.drag_to(CENTER + Vec2::new(20.0, -8.3));
+    assert_eq!(event.sector, 15);
+    assert_eq!(event.direction, JoystickDirection::UpRight);
+    assert_eq!(harness.release().sector, 0);
+
+    let resolver = DirectionResolver::four_way().with_offset(TAU / 8.0);
+    assert_eq!(resolver.sector(0.1), 0);
+    assert_eq!(resolver.sector(-0.1), 3);
+    assert_eq!(resolver.direction(-0.1), JoystickDirection::UpRight);
+}
+
+#[test]
+fn smoothing_converges_while_the_touch_is_held() {
+    let mut harness = harness();
+    harness.joystick_mut().set_smoothing(Some(0.5));
+    harness.press(CENTER);
+    let first = harness.drag_to(CENTER + Vec2::new(0.0, 25.0));
+    assert!((first.intensity - 0.5).abs() < 1e-5);
+    // the touch is stationary from now on
+    let mut last = first;
+    for _ in 0..20 {
+        let event = harness.advance(1.0 / 60.0);
+        assert!(event.intensity >= last.intensity);
+        last = event;
+    }
+    assert_eq!(last.direction, JoystickDirection::Down);
+    assert!(last.intensity > 0.999);
+    assert!(harness.release().is_idle());
+}
+
+#[test]
+fn return_animation_is_stopped_by_a_new_drag() {
+    let mut harness = harness();
+    harness.joystick_mut().set_return_duration(0.5);
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    harness.release();
+    harness.advance(0.1);
+    let returning = harness.joystick().rendered_knob();
+    assert!(returning.x > CENTER.x && returning.x < CENTER.x + 20.0);
+    assert!(!harness.joystick().is_quiescent());
+
+    harness.press(CENTER + Vec2::new(0.0, -10.0));
+    assert_eq!(
+        harness.joystick().rendered_knob(),
+        CENTER + Vec2::new(0.0, -10.0)
+    );
+    harness.release();
+    harness.advance(1.0);
+    assert_eq!(harness.joystick().rendered_knob(), CENTER);
+    assert!(harness.joystick().is_quiescent());
+}
+
+#[test]
+fn return_easing_can_overshoot_the_center() {
+    let mut harness = harness();
+    harness.joystick_mut().set_return_duration(0.5);
+    harness.joystick_mut().set_return_easing(Easing::BackOut);
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    harness.release();
+    harness.advance(0.4);
+    assert!(harness.joystick().rendered_knob().x < CENTER.x);
+    harness.advance(0.1);
+    assert_eq!(harness.joystick().rendered_knob(), CENTER);
+}
+
+/// records the calls of the feedback hooks
+struct Recorder(Rc<RefCell<Vec<String>>>);
+
+impl JoystickFeedback for Recorder {
+    fn on_start(&mut self, _event: &JoystickEvent) {
+        self.0.borrow_mut().push("start".to_string());
+    }
+
+    fn on_direction_change(&mut self, from: JoystickDirection, event: &JoystickEvent) {
+        let change = format!("{:?} -> {:?}", from, event.direction);
+        self.0.borrow_mut().push(change);
+    }
+
+    fn on_edge_reach(&mut self, _event: &JoystickEvent) {
+        self.0.borrow_mut().push("edge".to_string());
+    }
+
+    fn on_release(&mut self, _event: &JoystickEvent) {
+        self.0.borrow_mut().push("release".to_string());
+    }
+}
+
+#[test]
+fn feedback_hooks_are_called_once_per_moment() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .set_feedback(Some(Box::new(Recorder(Rc::clone(&log)))));
+
+    harness.press(CENTER + Vec2::new(10.0, 0.0));
+    harness.drag_to(CENTER + Vec2::new(30.0, 0.0));
+    harness.drag_to(CENTER + Vec2::new(40.0, 0.0));
+    harness.drag_to(CENTER + Vec2::new(0.0, 30.0));
+    harness.drag_to(CENTER + Vec2::new(0.0, 10.0));
+    harness.drag_to(CENTER + Vec2::new(0.0, 30.0));
+    harness.release();
+    assert_eq!(
+        *log.borrow(),
+        [
+            "start",
+            "Idle -> Right",
+            "edge",
+            "Right -> Down",
+            "edge",
+            // the release is idle
+            "Down -> Idle",
+            "release",
+        ]
+    );
+
+    // a tap in a single update
+    log.borrow_mut().clear();
+    let position = CENTER + Vec2::new(-10.0, 0.0);
+    let tap = SimulatedInput::new()
+        .with_touch(1, TouchPhase::Started, position)
+        .with_touch(1, TouchPhase::Ended, position);
+    harness.joystick_mut().update_with_input(&tap, 0.1);
+    assert_eq!(*log.borrow(), ["start", "release"]);
+}
+
+#[test]
+fn snapped_knob_moves_along_the_sectors() {
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .set_direction_resolver(DirectionResolver::four_way());
+    harness.joystick_mut().set_snap_knob(true);
+
+    // almost diagonal, but closer to down
+    let event = harness.press(CENTER + Vec2::new(9.0, 10.0));
+    assert_eq!(event.direction, JoystickDirection::Down);
+    assert!((event.axes().x).abs() < 1e-6);
+    let knob = harness.joystick().rendered_knob() - CENTER;
+    assert!(knob.x.abs() < 1e-4);
+    assert!((knob.y - Vec2::new(9.0, 10.0).length()).abs() < 1e-4);
+    assert!((event.intensity - Vec2::new(9.0, 10.0).length() / 25.0).abs() < 1e-6);
+
+    harness.joystick_mut().set_snap_knob(false);
+    let event = harness.drag_to(CENTER + Vec2::new(9.0, 10.0));
+    assert!(event.axes().x > 0.0);
+}
+
+#[test]
+fn response_curve_is_applied_after_the_dead_zone() {
+    let mut harness = harness();
+    harness.joystick_mut().set_dead_zone(0.2);
+    harness
+        .joystick_mut()
+        .set_response_curve(ResponseCurve::Exponential(2.0));
+    let event = harness.press(CENTER + Vec2::new(-15.0, 0.0));
+    let expected = (2.0f32 * 0.5).exp_m1() / 2.0f32.exp_m1();
+    assert!((event.intensity - expected).abs() < 1e-5);
+    assert_eq!(event.direction, JoystickDirection::Left);
+    assert!(harness.drag_to(CENTER + Vec2::new(-4.0, 0.0)).is_idle());
+    assert_eq!(
+        harness.drag_to(CENTER + Vec2::new(-30.0, 0.0)).intensity,
+        1.0
+    );
+}
+
+#[test]
+fn event_callbacks_follow_the_phases() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    let press_log = Rc::clone(&log);
+    joystick.on_press(Box::new(move |_| press_log.borrow_mut().push("press")));
+    let drag_log = Rc::clone(&log);
+    joystick.on_drag(Box::new(move |_| drag_log.borrow_mut().push("drag")));
+    let release_log = Rc::clone(&log);
+    joystick.on_release(Box::new(move |_| release_log.borrow_mut().push("release")));
+
+    let right = CENTER + Vec2::new(10.0, 0.0);
+    for phase in [TouchPhase::Started, TouchPhase::Moved, TouchPhase::Ended] {
+        let input = SimulatedInput::new().with_touch(0, phase, right);
+        joystick.update_with_input(&input, 0.1);
+    }
+    assert_eq!(*log.borrow(), ["press", "drag", "release"]);
+
+    // a tap in one update
+    log.borrow_mut().clear();
+    let input = SimulatedInput::new()
+        .with_touch(1, TouchPhase::Started, right)
+        .with_touch(1, TouchPhase::Ended, right);
+    joystick.update_with_input(&input, 0.1);
+    assert_eq!(*log.borrow(), ["press", "release"]);
+
+    joystick.clear_event_callbacks();
+    log.borrow_mut().clear();
+    let input = SimulatedInput::new().with_touch(2, TouchPhase::Started, right);
+    joystick.update_with_input(&input, 0.1);
+    assert!(log.borrow().is_empty());
+}
+
+#[test]
+fn transition_flags_are_set_for_one_update() {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    let right = CENTER + Vec2::new(20.0, 0.0);
+    let up = CENTER + Vec2::new(0.0, -20.0);
+    let flags = |event: JoystickEvent| {
+        (
+            event.just_pressed,
+            event.just_released,
+            event.direction_changed,
+        )
+    };
+
+    let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, right);
+    assert_eq!(
+        flags(joystick.update_with_input(&input, 0.1)),
+        (true, false, true)
+    );
+    let input = SimulatedInput::new().with_touch(0, TouchPhase::Moved, right);
+    assert_eq!(
+        flags(joystick.update_with_input(&input, 0.1)),
+        (false, false, false)
+    );
+    let input = SimulatedInput::new().with_touch(0, TouchPhase::Moved, up);
+    assert_eq!(
+        flags(joystick.update_with_input(&input, 0.1)),
+        (false, false, true)
+    );
+    let input = SimulatedInput::new().with_touch(0, TouchPhase::Ended, up);
+    assert_eq!(
+        flags(joystick.update_with_input(&input, 0.1)),
+        (false, true, true)
+    );
+    let input = SimulatedInput::new();
+    assert_eq!(
+        flags(joystick.update_with_input(&input, 0.1)),
+        (false, false, false)
+    );
+
+    // a tap in one update
+    let input = SimulatedInput::new()
+        .with_touch(1, TouchPhase::Started, right)
+        .with_touch(1, TouchPhase::Ended, right);
+    assert_eq!(
+        flags(joystick.update_with_input(&input, 0.1)),
+        (true, true, false)
+    );
+}
+
+#[test]
+fn direction_hysteresis_starts_over_after_the_release() {
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .set_direction_hysteresis(5f32.to_radians());
+    let at = |degrees: f32| {
+        let radians = degrees.to_radians();
+        CENTER + Vec2::new(radians.cos(), radians.sin()) * 20.0
+    };
+    harness.press(at(20.0));
+    let event = harness.drag_to(at(25.0));
+    assert_eq!(event.direction, JoystickDirection::Right);
+    assert_eq!(event.sector, 0);
+    assert!((event.angle - 25f32.to_radians()).abs() < 1e-5);
+    harness.release();
+
+    let event = harness.press(at(25.0));
+    assert_eq!(event.direction, JoystickDirection::DownRight);
+}
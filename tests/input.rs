@@ -0,0 +1,817 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use macroquad::prelude::{KeyCode, Rect, Touch, TouchPhase, Vec2};
+use macroquad_virtual_joystick::{
+    Flick, FrameStamp, InputSource, Joystick, JoystickDirection, JoystickHarness, JoystickInput,
+    JoystickPhase, SimulatedInput, SwipeInterpolation,
+};
+
+const CENTER: Vec2 = Vec2::new(100.0, 200.0);
+const SIZE: f32 = 50.0;
+
+fn harness() -> JoystickHarness {
+    JoystickHarness::new(Joystick::new(CENTER.x, CENTER.y, SIZE))
+}
+
+#[test]
+fn press_inside_radius_grabs() {
+    let mut harness = harness();
+    let event = harness.press(CENTER + Vec2::new(0.0, 20.0));
+    assert_eq!(event.direction, JoystickDirection::Down);
+    assert_eq!(event.intensity, 0.8);
+}
+
+#[test]
+fn press_outside_radius_is_ignored() {
+    let mut harness = harness();
+    let event = harness.press(CENTER + Vec2::new(0.0, 30.0));
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    // moving into the joystick doesn't grab it either
+    let event = harness.drag_to(CENTER + Vec2::new(0.0, 10.0));
+    assert_eq!(event.direction, JoystickDirection::Idle);
+}
+
+#[test]
+fn drag_is_clamped_at_rim() {
+    let mut harness = harness();
+    harness.press(CENTER);
+    let events = harness.drag_path(CENTER, CENTER + Vec2::new(-200.0, 0.0), 10);
+    for event in &events[1..] {
+        assert_eq!(event.direction, JoystickDirection::Left);
+        assert!(event.intensity <= 1.0);
+    }
+    assert_eq!(events.last().unwrap().intensity, 1.0);
+}
+
+#[test]
+fn held_touch_keeps_event() {
+    let mut harness = harness();
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    let event = harness.advance(1.0);
+    assert_eq!(event.direction, JoystickDirection::Right);
+    assert_eq!(event.intensity, 0.8);
+}
+
+#[test]
+fn other_touches_are_ignored() {
+    let mut harness = harness();
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+
+    // a second touch outside of the joystick moves and ends
+    harness.touch_start(1, Vec2::new(500.0, 500.0));
+    let event = harness.advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Right);
+    harness.touch_move(1, Vec2::new(0.0, 0.0));
+    let event = harness.advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Right);
+    harness.touch_end(1);
+    let event = harness.advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Right);
+
+    // the first touch still controls the joystick
+    let event = harness.drag_to(CENTER + Vec2::new(-20.0, 0.0));
+    assert_eq!(event.direction, JoystickDirection::Left);
+}
+
+#[test]
+fn release_resets() {
+    let mut harness = harness();
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    let event = harness.release();
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert_eq!(event.intensity, 0.0);
+    assert_eq!(event.angle, 0.0);
+}
+
+#[test]
+fn cancel_resets() {
+    let mut harness = harness();
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    let event = harness.cancel();
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert_eq!(event.intensity, 0.0);
+}
+
+#[test]
+fn mouse_drag() {
+    let mut harness = harness();
+    harness.mouse(CENTER + Vec2::new(0.0, -20.0), true);
+    let event = harness.advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Up);
+    harness.mouse(CENTER + Vec2::new(0.0, -20.0), false);
+    let event = harness.advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+}
+
+#[test]
+fn grab_forgiveness_expands_only_the_grab() {
+    let press = CENTER + Vec2::new(SIZE / 2.0 * 1.2, 0.0);
+
+    let mut strict = harness();
+    let event = strict.press(press);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+
+    let mut forgiving = harness();
+    forgiving.joystick_mut().set_grab_forgiveness(1.3);
+    let event = forgiving.press(press);
+    assert_eq!(event.direction, JoystickDirection::Right);
+    // the intensity is still relative to the real radius
+    assert_eq!(event.intensity, 1.0);
+    let event = forgiving.drag_to(CENTER + Vec2::new(SIZE / 4.0, 0.0));
+    assert_eq!(event.intensity, 0.5);
+}
+
+#[test]
+fn direction_lock_latches_strong_release() {
+    let mut harness = harness();
+    harness.joystick_mut().set_direction_lock(true);
+    let released = Rc::new(Cell::new(0));
+    let callback_released = Rc::clone(&released);
+    harness.joystick_mut().on_release(Box::new(move |_| {
+        callback_released.set(callback_released.get() + 1)
+    }));
+    harness.press(CENTER + Vec2::new(0.0, -24.0));
+    let event = harness.release();
+    assert_eq!(event.direction, JoystickDirection::Up);
+    assert_eq!(event.phase, JoystickPhase::Idle);
+    assert!(!event.just_released);
+    assert_eq!(event.flick, None);
+    assert_eq!(released.get(), 0);
+    let event = harness.advance(1.0);
+    assert_eq!(event.direction, JoystickDirection::Up);
+    assert_eq!(event.intensity, 0.96);
+    assert!(harness.joystick().is_locked());
+
+    // a new press clears the latch and starts a normal drag
+    let event = harness.press(CENTER + Vec2::new(10.0, 0.0));
+    assert!(!harness.joystick().is_locked());
+    assert_eq!(event.direction, JoystickDirection::Right);
+    let event = harness.release();
+    assert_eq!(event.direction, JoystickDirection::Idle);
+}
+
+#[test]
+fn direction_lock_ignores_weak_release() {
+    let mut harness = harness();
+    harness.joystick_mut().set_direction_lock(true);
+    harness.press(CENTER + Vec2::new(0.0, -10.0));
+    let event = harness.release();
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert!(!harness.joystick().is_locked());
+}
+
+#[test]
+fn secondary_touch_down_first_up() {
+    let mut harness = harness();
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    // a second finger rests on the joystick
+    harness.touch_start(1, CENTER + Vec2::new(-10.0, 0.0));
+    let event = harness.advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Right);
+    assert_eq!(event.intensity, 0.8);
+
+    let event = harness.release();
+    assert!(event.is_idle());
+    // the resting finger doesn't own the joystick
+    let event = harness.advance(0.0);
+    assert!(event.is_idle());
+    harness.touch_move(1, CENTER + Vec2::new(0.0, 10.0));
+    let event = harness.advance(0.0);
+    assert!(event.is_idle());
+}
+
+#[test]
+fn secondary_touch_both_up_same_frame() {
+    let mut harness = harness();
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    harness.touch_start(1, CENTER + Vec2::new(-10.0, 0.0));
+    harness.advance(0.0);
+
+    harness.touch_end(0).touch_end(1);
+    let event = harness.advance(0.0);
+    assert!(event.is_idle());
+    let event = harness.advance(0.0);
+    assert!(event.is_idle());
+}
+
+#[test]
+fn secondary_touch_down_and_move_first_up() {
+    let mut harness = harness();
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    harness.touch_start(1, CENTER + Vec2::new(-10.0, 0.0));
+    harness.advance(0.0);
+
+    // moving the second finger doesn't affect the joystick
+    harness.touch_move(1, CENTER + Vec2::new(0.0, -20.0));
+    let event = harness.advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Right);
+
+    let event = harness.release();
+    assert!(event.is_idle());
+    harness.touch_move(1, CENTER + Vec2::new(0.0, 20.0));
+    let event = harness.advance(0.0);
+    assert!(event.is_idle());
+}
+
+#[test]
+fn touch_handoff_transfers_ownership() {
+    let mut harness = harness();
+    harness.joystick_mut().set_touch_handoff(true);
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    harness.touch_start(1, CENTER + Vec2::new(-10.0, 0.0));
+    harness.advance(0.0);
+
+    let event = harness.release();
+    assert_eq!(event.direction, JoystickDirection::Left);
+    assert_eq!(event.intensity, 0.4);
+    harness.touch_move(1, CENTER + Vec2::new(0.0, 20.0));
+    let event = harness.advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Down);
+}
+
+#[test]
+fn idle_timeout_fires_once_and_rearms() {
+    let mut harness = harness();
+    let fired = Rc::new(Cell::new(0));
+    let callback_fired = Rc::clone(&fired);
+    harness.joystick_mut().on_idle_timeout(
+        5.0,
+        Box::new(move || callback_fired.set(callback_fired.get() + 1)),
+    );
+    assert_eq!(harness.joystick().seconds_since_last_activity(), 0.0);
+
+    for _ in 0..8 {
+        harness.advance(1.0);
+    }
+    assert_eq!(harness.joystick().seconds_since_last_activity(), 8.0);
+    assert_eq!(fired.get(), 1);
+
+    harness.set_frame_time(1.0);
+    harness.press(CENTER);
+    harness.release();
+    assert_eq!(harness.joystick().seconds_since_last_activity(), 0.0);
+    for _ in 0..4 {
+        harness.advance(1.0);
+    }
+    assert_eq!(fired.get(), 1);
+    harness.advance(1.0);
+    assert_eq!(fired.get(), 2);
+
+    harness.advance(f32::MAX);
+    harness.advance(f32::MAX);
+    assert_eq!(harness.joystick().seconds_since_last_activity(), f32::MAX);
+}
+
+#[test]
+fn idle_timeout_is_rearmed_by_a_tap_in_one_update() {
+    let mut harness = harness();
+    let fired = Rc::new(Cell::new(0));
+    let callback_fired = Rc::clone(&fired);
+    harness.joystick_mut().on_idle_timeout(
+        5.0,
+        Box::new(move || callback_fired.set(callback_fired.get() + 1)),
+    );
+    for _ in 0..5 {
+        harness.advance(1.0);
+    }
+    assert_eq!(fired.get(), 1);
+
+    // a tap which starts and ends in the same update
+    let tap = SimulatedInput::new()
+        .with_touch(1, TouchPhase::Started, CENTER)
+        .with_touch(1, TouchPhase::Ended, CENTER);
+    let event = harness.joystick_mut().update_with_input(&tap, 1.0);
+    assert_eq!(event.phase, JoystickPhase::Release);
+    assert_eq!(harness.joystick().seconds_since_last_activity(), 0.0);
+    for _ in 0..5 {
+        harness.advance(1.0);
+    }
+    assert_eq!(fired.get(), 2);
+}
+
+#[test]
+fn commit_distance_ignores_short_press() {
+    let mut harness = harness();
+    harness.joystick_mut().set_commit_distance(5.0);
+    let press = CENTER + Vec2::new(15.0, 0.0);
+    let event = harness.press(press);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    let event = harness.drag_to(press + Vec2::new(3.0, 3.0));
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    let event = harness.release();
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert_eq!(event.source, InputSource::Touch);
+}
+
+#[test]
+fn commit_distance_commits_at_current_position() {
+    let mut harness = harness();
+    harness.joystick_mut().set_commit_distance(5.0);
+    let press = CENTER + Vec2::new(15.0, 0.0);
+    harness.press(press);
+    // the event uses the position relative to the center, not to the press
+    let event = harness.drag_to(press + Vec2::new(0.0, 5.0));
+    assert_eq!(event.direction, JoystickDirection::Right);
+    assert_eq!(
+        event.intensity,
+        Vec2::new(15.0, 5.0).length() / (SIZE / 2.0)
+    );
+    // after the commit, moving back to the press point keeps reporting
+    let event = harness.drag_to(press);
+    assert_eq!(event.direction, JoystickDirection::Right);
+    assert_eq!(event.intensity, 0.6);
+}
+
+/// input which counts the queries
+#[derive(Default)]
+struct CountingInput {
+    touches: Cell<u32>,
+    mouse_position: Cell<u32>,
+    mouse_down: Cell<u32>,
+}
+
+impl JoystickInput for CountingInput {
+    fn touches(&self) -> Vec<Touch> {
+        self.touches.set(self.touches.get() + 1);
+        Vec::new()
+    }
+
+    fn mouse_position(&self) -> Vec2 {
+        self.mouse_position.set(self.mouse_position.get() + 1);
+        Vec2::ZERO
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        self.mouse_down.set(self.mouse_down.get() + 1);
+        false
+    }
+}
+
+#[test]
+fn quiescent_update_changes_nothing() {
+    let mut harness = harness();
+    harness.press(CENTER + Vec2::new(10.0, 0.0));
+    harness.release();
+    // the release is reported in the frame after the drag
+    assert!(!harness.joystick().is_quiescent());
+    harness.advance(0.0);
+    assert!(harness.joystick().is_quiescent());
+
+    let joystick = harness.joystick_mut();
+    let state = format!("{:?}", joystick.save_state());
+    let input = CountingInput::default();
+    let event = joystick.update_with_input(&input, 0.0);
+    assert!(event.is_idle());
+    assert_eq!(format!("{:?}", joystick.save_state()), state);
+    assert_eq!(input.touches.get(), 1);
+    assert_eq!(input.mouse_down.get(), 1);
+    assert_eq!(input.mouse_position.get(), 1);
+}
+
+#[test]
+fn update_in_frame_processes_each_frame_once() {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_repeat(0.0, 0.01);
+    let presses = Rc::new(Cell::new(0));
+    let tracer_presses = Rc::clone(&presses);
+    joystick.set_tracer(Some(Box::new(move |_: &str, event| {
+        if !event.is_idle() {
+            tracer_presses.set(tracer_presses.get() + 1);
+        }
+    })));
+
+    let press =
+        SimulatedInput::new().with_touch(0, TouchPhase::Started, CENTER + Vec2::new(10.0, 0.0));
+    let first = joystick.update_in_frame(&press, FrameStamp::new(0.1, 1.0));
+    let second = joystick.update_in_frame(&press, FrameStamp::new(0.1, 1.05));
+    assert_eq!(format!("{:?}", first), format!("{:?}", second));
+    assert_eq!(presses.get(), 1);
+    assert_eq!(joystick.take_repeats().count(), 1);
+
+    // the next frame is processed again
+    let held =
+        SimulatedInput::new().with_touch(0, TouchPhase::Stationary, CENTER + Vec2::new(10.0, 0.0));
+    joystick.update_in_frame(&held, FrameStamp::new(0.105, 1.1));
+    assert_eq!(joystick.take_repeats().count(), 11);
+    joystick.update_in_frame(&held, FrameStamp::new(0.105, 1.15));
+    assert_eq!(joystick.take_repeats().count(), 0);
+    let release =
+        SimulatedInput::new().with_touch(0, TouchPhase::Ended, CENTER + Vec2::new(10.0, 0.0));
+    let event = joystick.update_in_frame(&release, FrameStamp::new(0.12, 1.2));
+    assert!(event.is_idle());
+}
+
+#[test]
+fn predictive_start_uses_moved_position() {
+    let start = CENTER + Vec2::new(5.0, 0.0);
+    let moved = CENTER + Vec2::new(0.0, 15.0);
+    // the movement is listed before the start
+    let input = SimulatedInput::new()
+        .with_touch(0, TouchPhase::Moved, moved)
+        .with_touch(0, TouchPhase::Started, start);
+
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_predictive_start(true);
+    let event = joystick.update_with_input(&input, 0.0);
+    assert_eq!(event.direction, JoystickDirection::Down);
+    assert_eq!(event.intensity, 0.6);
+
+    // the predicted position is the press, so the drag doesn't commit
+    joystick.clear();
+    joystick.set_commit_distance(5.0);
+    let event = joystick.update_with_input(&input, 0.0);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+
+    // without the prediction, the stale start is the press
+    joystick.clear();
+    joystick.set_predictive_start(false);
+    let event = joystick.update_with_input(&input, 0.0);
+    assert_eq!(event.direction, JoystickDirection::Down);
+}
+
+#[test]
+fn stats_count_grabs_and_taps() {
+    let mut harness = harness();
+    assert_eq!(harness.joystick().stats(), None);
+    harness.joystick_mut().set_stats(true);
+    harness.set_frame_time(0.1);
+
+    // a tap
+    harness.press(CENTER + Vec2::new(0.0, -10.0));
+    harness.release();
+    // a long drag
+    harness.drag_path(CENTER, CENTER + Vec2::new(-30.0, 0.0), 5);
+    harness.release();
+
+    let stats = harness.joystick().stats().unwrap();
+    assert_eq!(stats.grabs, 2);
+    assert_eq!(stats.taps, 1);
+    assert!((stats.drag_time - 0.7).abs() < 1e-5, "{}", stats.drag_time);
+    assert!((stats.edge_time - 0.1).abs() < 1e-5, "{}", stats.edge_time);
+    let held: u32 = stats.histogram.iter().flatten().sum();
+    assert_eq!(held, 7);
+    // held at -24 and -30 pixels
+    let left: u32 = stats.histogram.iter().map(|row| row[0]).sum();
+    assert_eq!(left, 2);
+
+    harness.joystick_mut().reset_stats();
+    assert_eq!(harness.joystick().stats().unwrap().grabs, 0);
+}
+
+#[test]
+fn time_scale_scales_hold_time() {
+    let hold_time = |time_scale: f32| {
+        let mut harness = harness();
+        harness.joystick_mut().set_time_scale(time_scale);
+        harness.set_frame_time(0.1);
+        let events = harness.drag_path(CENTER, CENTER + Vec2::new(20.0, 0.0), 9);
+        assert_eq!(events.last().unwrap().intensity, 0.8);
+        harness.joystick().hold_time()
+    };
+    let real = hold_time(1.0);
+    assert!((real - 1.0).abs() < 1e-5, "{}", real);
+    assert!((hold_time(0.5) / real - 0.5).abs() < 1e-5);
+    // frozen timers still process the input
+    assert_eq!(hold_time(0.0), 0.0);
+}
+
+#[test]
+fn swipe_interpolation_reports_skipped_directions() {
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .set_swipe_interpolation(Some(SwipeInterpolation {
+            max_spacing: 5.0,
+            max_samples: 16,
+        }));
+    harness.press(CENTER + Vec2::new(-20.0, 0.0));
+    let event = harness.drag_to(CENTER + Vec2::new(20.0, 0.0));
+    assert_eq!(event.direction, JoystickDirection::Right);
+
+    let events: Vec<_> = harness.joystick_mut().take_events().collect();
+    // the press, 7 samples and the final position
+    assert_eq!(events.len(), 9);
+    let directions: Vec<_> = events.iter().map(|event| event.direction).collect();
+    use JoystickDirection::{Idle, Left, Right};
+    assert_eq!(
+        directions,
+        [Left, Left, Left, Left, Idle, Right, Right, Right, Right]
+    );
+    assert_eq!(format!("{:?}", events[8]), format!("{:?}", event));
+    assert_eq!(harness.joystick_mut().take_events().count(), 0);
+
+    // the samples are limited
+    harness
+        .joystick_mut()
+        .set_swipe_interpolation(Some(SwipeInterpolation {
+            max_spacing: 5.0,
+            max_samples: 2,
+        }));
+    harness.drag_to(CENTER + Vec2::new(-20.0, 0.0));
+    assert_eq!(harness.joystick_mut().take_events().count(), 3);
+    // nothing is queued without the interpolation
+    harness.joystick_mut().set_swipe_interpolation(None);
+    harness.drag_to(CENTER + Vec2::new(20.0, 0.0));
+    assert_eq!(harness.joystick_mut().take_events().count(), 0);
+}
+
+#[test]
+fn queues_are_capped_until_they_are_taken() {
+    let mut harness = harness();
+    harness.joystick_mut().set_repeat(0.0, 0.1);
+    harness
+        .joystick_mut()
+        .set_swipe_interpolation(Some(SwipeInterpolation {
+            max_spacing: 0.5,
+            max_samples: 1000,
+        }));
+    harness.press(CENTER + Vec2::new(-20.0, 0.0));
+    harness.drag_to(CENTER + Vec2::new(20.0, 0.0));
+    for dt in [1e9, f32::INFINITY] {
+        harness.advance(dt);
+    }
+    assert_eq!(harness.joystick_mut().take_repeats().count(), 64);
+    assert_eq!(harness.joystick_mut().take_events().count(), 64);
+
+    // the repeat continues after the long frames
+    harness.advance(0.1);
+    assert_eq!(harness.joystick_mut().take_repeats().count(), 1);
+}
+
+#[test]
+fn hit_and_travel_radius_are_independent_of_size() {
+    let mut harness = harness();
+    harness.joystick_mut().set_hit_radius(15.0);
+    harness.joystick_mut().set_travel_radius(40.0);
+    assert_eq!(harness.joystick().size(), SIZE);
+    assert_eq!(harness.joystick().hit_radius(), 15.0);
+    assert_eq!(harness.joystick().travel_radius(), 40.0);
+
+    // inside of the background but outside of the hit radius
+    let event = harness.press(CENTER + Vec2::new(20.0, 0.0));
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    harness.release();
+
+    let event = harness.press(CENTER + Vec2::new(10.0, 0.0));
+    assert_eq!(event.intensity, 0.25);
+    // the knob travels beyond the background
+    let event = harness.drag_to(CENTER + Vec2::new(30.0, 0.0));
+    assert_eq!(event.intensity, 0.75);
+    let event = harness.drag_to(CENTER + Vec2::new(60.0, 0.0));
+    assert_eq!(event.intensity, 1.0);
+}
+
+#[test]
+fn hit_and_travel_radius_default_to_half_the_size() {
+    let joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    assert_eq!(joystick.hit_radius(), SIZE / 2.0);
+    assert_eq!(joystick.travel_radius(), SIZE / 2.0);
+}
+
+/// the input of a touch at `position` which is mirrored by the mouse
+fn mirrored_touch(phase: TouchPhase, position: Vec2) -> SimulatedInput {
+    SimulatedInput::new()
+        .with_touch(0, phase, position)
+        .with_mouse(position, true)
+}
+
+#[test]
+fn late_synthesized_mouse_release_doesnt_grab() {
+    let release_frames = |touch_synthesizes_mouse: bool| {
+        let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+        joystick.set_touch_synthesizes_mouse(touch_synthesizes_mouse);
+        let position = CENTER + Vec2::new(10.0, 0.0);
+        joystick.update_with_input(&mirrored_touch(TouchPhase::Started, CENTER), 0.0);
+        joystick.update_with_input(&mirrored_touch(TouchPhase::Moved, position), 0.0);
+        let event = joystick.update_with_input(&mirrored_touch(TouchPhase::Ended, position), 0.0);
+        assert_eq!(event.direction, JoystickDirection::Idle);
+        // the release of the mouse arrives a frame later
+        let late = SimulatedInput::new().with_mouse(position, true);
+        let event = joystick.update_with_input(&late, 0.0);
+        let up = SimulatedInput::new().with_mouse(position, false);
+        joystick.update_with_input(&up, 0.0);
+        (event, joystick)
+    };
+
+    // the held button isn't a new press, so it doesn't grab either
+    let (event, _) = release_frames(false);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+
+    let (event, mut joystick) = release_frames(true);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert!(!joystick.is_dragging());
+    // a real press of the mouse still grabs
+    let press = SimulatedInput::new().with_mouse(CENTER + Vec2::new(0.0, 10.0), true);
+    let event = joystick.update_with_input(&press, 0.0);
+    assert_eq!(event.direction, JoystickDirection::Down);
+    assert_eq!(event.source, InputSource::Mouse);
+}
+
+#[test]
+fn synthesized_mouse_drag_is_handed_to_the_touch() {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_touch_synthesizes_mouse(true);
+    // the synthesized mouse arrives before the touch
+    let early = SimulatedInput::new().with_mouse(CENTER, true);
+    joystick.update_with_input(&early, 0.0);
+    assert!(joystick.is_dragging());
+
+    joystick.update_with_input(&mirrored_touch(TouchPhase::Started, CENTER), 0.0);
+    let position = CENTER + Vec2::new(-10.0, 0.0);
+    let event = joystick.update_with_input(&mirrored_touch(TouchPhase::Moved, position), 0.0);
+    assert_eq!(event.direction, JoystickDirection::Left);
+    assert_eq!(event.source, InputSource::Touch);
+    let event = joystick.update_with_input(&mirrored_touch(TouchPhase::Ended, position), 0.0);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert!(!joystick.is_dragging());
+}
+
+#[test]
+fn phases_of_taps_and_drags() {
+    let mut harness = harness();
+    assert_eq!(harness.press(CENTER).phase, JoystickPhase::Start);
+    let events = harness.drag_path(CENTER, CENTER + Vec2::new(10.0, 0.0), 3);
+    assert!(events
+        .iter()
+        .all(|event| event.phase == JoystickPhase::Drag));
+    assert_eq!(harness.release().phase, JoystickPhase::Release);
+    assert_eq!(harness.advance(0.1).phase, JoystickPhase::Idle);
+
+    // a tap which starts and ends in the same update is still released
+    let input = SimulatedInput::new()
+        .with_touch(1, TouchPhase::Started, CENTER)
+        .with_touch(1, TouchPhase::Ended, CENTER);
+    let event = harness.joystick_mut().update_with_input(&input, 0.1);
+    assert_eq!(event.phase, JoystickPhase::Release);
+    assert_eq!(harness.advance(0.1).phase, JoystickPhase::Idle);
+    // a press outside is never started
+    assert_eq!(
+        harness.press(CENTER + Vec2::new(40.0, 0.0)).phase,
+        JoystickPhase::Idle
+    );
+}
+
+#[test]
+fn flick_is_only_reported_for_fast_releases() {
+    let flick = |path: &[f32]| {
+        let mut harness = harness();
+        harness.joystick_mut().set_flick(Some(Flick {
+            min_speed: 4.0,
+            window: 0.1,
+        }));
+        harness.set_frame_time(0.05);
+        harness.press(CENTER);
+        for &x in path {
+            let event = harness.drag_to(CENTER + Vec2::new(x, 0.0));
+            assert!(event.flick.is_none());
+        }
+        harness.release().flick
+    };
+    let fast = flick(&[-10.0, -20.0, -25.0]).unwrap();
+    assert_eq!(fast.direction, JoystickDirection::Left);
+    assert!(fast.speed >= 4.0, "{:?}", fast);
+    // too slow
+    assert!(flick(&[-2.0, -4.0, -6.0]).is_none());
+    // fast, but held still before the release
+    assert!(flick(&[-25.0, -25.0, -25.0, -25.0]).is_none());
+}
+
+#[test]
+fn bound_keys_drive_the_joystick() {
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .bind_keys(KeyCode::W, KeyCode::A, KeyCode::S, KeyCode::D);
+
+    let event = harness.key(KeyCode::D, true).advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Right);
+    assert_eq!(event.intensity, 1.0);
+    assert_eq!(event.phase, JoystickPhase::Start);
+    assert_eq!(event.source, InputSource::Keyboard);
+
+    let event = harness.key(KeyCode::S, true).advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::DownRight);
+    assert_eq!(event.phase, JoystickPhase::Drag);
+
+    // a touch takes precedence over the keys
+    let event = harness.press(CENTER + Vec2::new(0.0, -20.0));
+    assert_eq!(event.direction, JoystickDirection::Up);
+    assert_eq!(event.source, InputSource::Touch);
+    assert_eq!(harness.release().phase, JoystickPhase::Release);
+
+    let event = harness.advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::DownRight);
+    assert_eq!(event.phase, JoystickPhase::Start);
+
+    let event = harness
+        .key(KeyCode::D, false)
+        .key(KeyCode::S, false)
+        .advance(0.0);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert_eq!(event.phase, JoystickPhase::Release);
+    assert_eq!(harness.advance(0.0).phase, JoystickPhase::Idle);
+}
+
+#[test]
+fn events_report_the_held_duration() {
+    let mut harness = harness();
+    harness.set_frame_time(0.1);
+    assert_eq!(harness.advance(0.1).duration, 0.0);
+    assert!((harness.press(CENTER + Vec2::new(10.0, 0.0)).duration - 0.1).abs() < 1e-6);
+    assert!((harness.advance(0.25).duration - 0.35).abs() < 1e-6);
+    assert!((harness.release().duration - 0.35).abs() < 1e-6);
+    assert_eq!(harness.advance(0.1).duration, 0.0);
+
+    harness
+        .joystick_mut()
+        .bind_keys(KeyCode::W, KeyCode::A, KeyCode::S, KeyCode::D);
+    harness.key(KeyCode::W, true);
+    assert!((harness.advance(0.2).duration - 0.2).abs() < 1e-6);
+    assert!((harness.advance(0.2).duration - 0.4).abs() < 1e-6);
+}
+
+#[test]
+fn key_presets_bind_wasd_and_the_arrow_keys() {
+    let mut harness = harness();
+    harness.joystick_mut().bind_arrow_keys();
+    harness.key(KeyCode::Up, true).key(KeyCode::Right, true);
+    assert_eq!(harness.advance(0.1).direction, JoystickDirection::UpRight);
+    harness.key(KeyCode::Up, false).key(KeyCode::Right, false);
+    assert!(harness.advance(0.1).is_idle());
+
+    harness.joystick_mut().bind_wasd();
+    harness.key(KeyCode::Up, true);
+    assert!(harness.advance(0.1).is_idle());
+    harness.key(KeyCode::S, true);
+    assert_eq!(harness.advance(0.1).direction, JoystickDirection::Down);
+}
+
+#[test]
+fn hit_rect_grabs_outside_of_the_hit_radius() {
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .set_hit_rect(Some(Rect::new(0.0, 150.0, 200.0, 100.0)));
+    let event = harness.press(Vec2::new(10.0, 240.0));
+    assert_eq!(event.direction, JoystickDirection::DownLeft);
+    assert_eq!(harness.joystick().center(), CENTER);
+    harness.release();
+
+    // outside of both
+    assert!(harness.press(Vec2::new(210.0, 200.0)).is_idle());
+    harness.release();
+    harness.joystick_mut().set_hit_rect(None);
+    assert!(harness.press(Vec2::new(10.0, 240.0)).is_idle());
+}
+
+#[test]
+fn disabled_joystick_ignores_the_input() {
+    let mut harness = harness();
+    harness.press(CENTER + Vec2::new(20.0, 0.0));
+    harness.joystick_mut().set_enabled(false);
+    assert!(!harness.joystick().is_dragging());
+    assert!(harness.drag_to(CENTER + Vec2::new(0.0, 20.0)).is_idle());
+    assert!(harness.release().is_idle());
+    assert!(harness.press(CENTER + Vec2::new(20.0, 0.0)).is_idle());
+    harness.release();
+
+    harness.joystick_mut().set_enabled(true);
+    let event = harness.press(CENTER + Vec2::new(20.0, 0.0));
+    assert_eq!(event.phase, JoystickPhase::Start);
+    assert_eq!(event.direction, JoystickDirection::Right);
+}
+
+#[test]
+fn gamepad_stick_drives_the_joystick() {
+    let mut harness = harness();
+    harness.joystick_mut().set_dead_zone(0.1);
+
+    // drift inside of the dead zone is ignored
+    harness.gamepad_stick(Vec2::new(0.05, 0.0));
+    let event = harness.advance(0.1);
+    assert!(event.is_idle());
+    assert_eq!(event.phase, JoystickPhase::Idle);
+
+    harness.gamepad_stick(Vec2::new(0.0, 0.55));
+    let event = harness.advance(0.1);
+    assert_eq!(event.phase, JoystickPhase::Start);
+    assert_eq!(event.source, InputSource::Gamepad);
+    assert_eq!(event.direction, JoystickDirection::Down);
+    assert!((event.intensity - 0.5).abs() < 1e-6);
+    assert!(
+        (harness.joystick().rendered_knob() - (CENTER + Vec2::new(0.0, 13.75))).length() < 1e-4
+    );
+
+    // a pointer takes precedence
+    let event = harness.press(CENTER + Vec2::new(-20.0, 0.0));
+    assert_eq!(event.source, InputSource::Touch);
+    assert_eq!(event.direction, JoystickDirection::Left);
+    harness.release();
+    assert_eq!(harness.advance(0.1).source, InputSource::Gamepad);
+
+    harness.gamepad_stick(Vec2::ZERO);
+    let event = harness.advance(0.1);
+    assert_eq!(event.phase, JoystickPhase::Release);
+    assert!(harness.advance(0.1).is_idle());
+}
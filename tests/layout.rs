@@ -0,0 +1,223 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use macroquad::prelude::{Mat3, Rect, Vec2};
+use macroquad_virtual_joystick::{
+    Joystick, JoystickAxis, JoystickDirection, JoystickHarness, JoystickMode, JoystickStyle,
+};
+
+const CENTER: Vec2 = Vec2::new(100.0, 200.0);
+const SIZE: f32 = 50.0;
+
+fn harness() -> JoystickHarness {
+    JoystickHarness::new(Joystick::new(CENTER.x, CENTER.y, SIZE))
+}
+
+#[test]
+fn attached_joystick_follows_provider() {
+    let mut harness = harness();
+    let player = Rc::new(Cell::new(Vec2::new(300.0, 300.0)));
+    let attached_player = Rc::clone(&player);
+    harness
+        .joystick_mut()
+        .attach_to(Box::new(move || attached_player.get()));
+
+    // the old center doesn't grab anymore
+    let event = harness.press(CENTER);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    harness.release();
+
+    let event = harness.press(Vec2::new(300.0, 310.0));
+    assert_eq!(event.direction, JoystickDirection::Down);
+    assert_eq!(event.intensity, 0.4);
+
+    // a held touch keeps its offset while the center moves
+    player.set(Vec2::new(350.0, 300.0));
+    let event = harness.advance(0.1);
+    assert_eq!(harness.joystick().center(), Vec2::new(350.0, 300.0));
+    assert_eq!(event.direction, JoystickDirection::Down);
+    assert_eq!(event.intensity, 0.4);
+
+    // a moved touch is relative to the new center
+    let event = harness.drag_to(Vec2::new(340.0, 300.0));
+    assert_eq!(event.direction, JoystickDirection::Left);
+
+    harness.joystick_mut().detach();
+    assert_eq!(harness.joystick().center(), CENTER);
+}
+
+#[test]
+fn attached_joystick_composes_with_transform() {
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .set_transform(Mat3::from_scale(Vec2::new(2.0, 2.0)));
+    harness
+        .joystick_mut()
+        .attach_to(Box::new(|| Vec2::new(400.0, 400.0)));
+
+    harness.advance(0.0);
+    // the provider returns screen coordinates
+    assert_eq!(harness.joystick().center(), Vec2::new(200.0, 200.0));
+    let event = harness.press(Vec2::new(420.0, 400.0));
+    assert_eq!(event.direction, JoystickDirection::Right);
+    assert_eq!(event.intensity, 0.4);
+}
+
+#[test]
+fn mirrored_joystick_moves_across_the_axis() {
+    let mut harness = harness();
+    harness.joystick_mut().set_mirror_axis(Some(400.0));
+    harness.joystick_mut().set_mirrored(true);
+
+    let event = harness.press(CENTER);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    harness.release();
+
+    let mirrored = Vec2::new(700.0, CENTER.y);
+    let event = harness.press(mirrored + Vec2::new(10.0, 0.0));
+    assert_eq!(event.direction, JoystickDirection::Right);
+    harness.joystick_mut().set_mirror_output(true);
+    let event = harness.drag_to(mirrored + Vec2::new(10.0, -10.0));
+    assert_eq!(event.direction, JoystickDirection::UpLeft);
+
+    // toggling the mirroring cancels the drag
+    harness.joystick_mut().set_mirrored(false);
+    let event = harness.drag_to(CENTER + Vec2::new(10.0, 0.0));
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert!(!harness.joystick().is_mirrored());
+}
+
+#[test]
+fn mirroring_composes_with_transform() {
+    let mut harness = harness();
+    harness
+        .joystick_mut()
+        .set_transform(Mat3::from_scale(Vec2::new(2.0, 2.0)));
+    harness.joystick_mut().set_mirror_axis(Some(400.0));
+    harness.joystick_mut().set_mirrored(true);
+
+    // the center is drawn at (200, 400) and mirrored to (600, 400)
+    let event = harness.press(Vec2::new(620.0, 400.0));
+    assert_eq!(event.direction, JoystickDirection::Right);
+    assert_eq!(event.intensity, 0.4);
+}
+
+#[test]
+fn floating_joystick_spawns_under_the_touch() {
+    let mut harness = harness();
+    harness.joystick_mut().set_mode(JoystickMode::Floating {
+        activation: Rect::new(200.0, 0.0, 200.0, 400.0),
+        return_on_release: false,
+    });
+
+    let start = Vec2::new(300.0, 100.0);
+    let event = harness.press(start);
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert_eq!(harness.joystick().center(), start);
+    let event = harness.drag_to(start + Vec2::new(-20.0, 0.0));
+    assert_eq!(event.direction, JoystickDirection::Left);
+    assert_eq!(event.intensity, 0.8);
+    // the joystick stays where it was released
+    harness.release();
+    assert_eq!(harness.joystick().center(), start);
+
+    // a press inside the joystick but outside of the activation area grabs it in place
+    harness.joystick_mut().set_mode(JoystickMode::Floating {
+        activation: Rect::new(0.0, 0.0, 50.0, 50.0),
+        return_on_release: false,
+    });
+    let event = harness.press(start + Vec2::new(0.0, 10.0));
+    assert_eq!(event.direction, JoystickDirection::Down);
+    assert_eq!(harness.joystick().center(), start);
+    harness.release();
+
+    // outside of both nothing happens
+    let event = harness.press(Vec2::new(10.0, 300.0));
+    assert_eq!(event.direction, JoystickDirection::Idle);
+    assert!(!harness.joystick().is_dragging());
+}
+
+#[test]
+fn relayout_keeps_the_drag() {
+    let mut harness = harness();
+    harness.press(CENTER);
+    let event = harness.drag_to(CENTER + Vec2::new(10.0, 0.0));
+    assert_eq!(event.intensity, 0.4);
+
+    // the offset of the knob is scaled with the size
+    harness.joystick_mut().set_size(SIZE * 2.0);
+    let event = harness.advance(0.0);
+    assert_eq!(event.intensity, 0.4);
+    let event = harness.drag_to(CENTER + Vec2::new(20.0, 0.0));
+    assert_eq!(event.intensity, 0.4);
+
+    let moved = CENTER + Vec2::new(100.0, 0.0);
+    harness.joystick_mut().set_position(moved.x, moved.y);
+    assert_eq!(harness.joystick().center(), moved);
+    let event = harness.drag_to(moved + Vec2::new(0.0, -50.0));
+    assert_eq!(event.direction, JoystickDirection::Up);
+    assert_eq!(event.intensity, 1.0);
+    harness.release();
+
+    // the moved joystick is grabbed at its new position
+    let event = harness.press(moved + Vec2::new(-40.0, 0.0));
+    assert_eq!(event.direction, JoystickDirection::Left);
+    assert_eq!(event.intensity, 0.8);
+}
+
+#[test]
+fn follow_slides_the_joystick() {
+    let mut harness = harness();
+    harness.joystick_mut().set_follow(true);
+    harness.joystick_mut().set_axis(JoystickAxis::Horizontal);
+    harness.press(CENTER);
+    let event = harness.drag_to(CENTER + Vec2::new(-60.0, 30.0));
+    assert_eq!(event.direction, JoystickDirection::Left);
+    assert_eq!(event.intensity, 1.0);
+    // only along the axis
+    assert_eq!(harness.joystick().center(), CENTER - Vec2::new(35.0, 0.0));
+    // moving back inside doesn't pull the joystick back
+    let event = harness.drag_to(CENTER - Vec2::new(35.0, 0.0));
+    assert!(event.is_idle());
+    assert_eq!(harness.joystick().center(), CENTER - Vec2::new(35.0, 0.0));
+    harness.release();
+    assert_eq!(harness.joystick().center(), CENTER);
+
+    // a floating joystick which doesn't return stays where it was dragged to
+    harness.joystick_mut().set_mode(JoystickMode::Floating {
+        activation: Rect::new(0.0, 0.0, 400.0, 400.0),
+        return_on_release: false,
+    });
+    harness.press(Vec2::new(300.0, 300.0));
+    harness.drag_to(Vec2::new(350.0, 300.0));
+    harness.release();
+    assert_eq!(harness.joystick().center(), Vec2::new(325.0, 300.0));
+}
+
+#[test]
+fn render_cache_is_invalidated_by_the_drawn_size_and_the_style() {
+    let mut harness = harness();
+    harness.joystick_mut().set_render_cache(true);
+    harness.redraw_render_cache();
+    assert!(!harness.joystick().is_render_cache_dirty());
+
+    // the cached textures are drawn at the center
+    harness.joystick_mut().set_position(300.0, 300.0);
+    assert!(!harness.joystick().is_render_cache_dirty());
+
+    harness.joystick_mut().set_size(80.0);
+    assert!(harness.joystick().is_render_cache_dirty());
+    harness.redraw_render_cache();
+    assert!(!harness.joystick().is_render_cache_dirty());
+
+    // the style draws the elements, so the cache stays dirty until it's used again
+    harness
+        .joystick_mut()
+        .set_style(Some(JoystickStyle::default()));
+    harness.redraw_render_cache();
+    assert!(harness.joystick().is_render_cache_dirty());
+    harness.joystick_mut().set_style(None);
+    harness.redraw_render_cache();
+    assert!(!harness.joystick().is_render_cache_dirty());
+}
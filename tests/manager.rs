@@ -0,0 +1,254 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use macroquad::prelude::{Rect, TouchPhase, Vec2};
+use macroquad_virtual_joystick::{
+    ButtonShape, ControlKind, FrameStamp, Joystick, JoystickDirection, JoystickManager,
+    LayoutChange, SimulatedInput, TouchButton,
+};
+
+const CENTER: Vec2 = Vec2::new(100.0, 200.0);
+const SIZE: f32 = 50.0;
+
+/// two joysticks on top of each other
+fn overlapping() -> JoystickManager {
+    let mut manager = JoystickManager::new();
+    manager.add("first", Joystick::new(CENTER.x, CENTER.y, SIZE));
+    manager.add("second", Joystick::new(CENTER.x, CENTER.y, SIZE));
+    manager
+}
+
+#[test]
+fn touch_is_claimed_by_one_joystick() {
+    let mut manager = overlapping();
+    let right = CENTER + Vec2::new(10.0, 0.0);
+    let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, right);
+    manager.update_with_input(&input, 0.0);
+    assert_eq!(
+        manager.event("first").unwrap().direction,
+        JoystickDirection::Right
+    );
+    assert!(manager.event("second").unwrap().is_idle());
+
+    // the second touch goes to the free joystick and the first one is kept
+    let down = CENTER + Vec2::new(0.0, 10.0);
+    let input = SimulatedInput::new()
+        .with_touch(0, TouchPhase::Stationary, right)
+        .with_touch(1, TouchPhase::Started, down);
+    manager.update_with_input(&input, 0.0);
+    assert_eq!(
+        manager.event_at(0).unwrap().direction,
+        JoystickDirection::Right
+    );
+    assert_eq!(
+        manager.event_at(1).unwrap().direction,
+        JoystickDirection::Down
+    );
+
+    let input = SimulatedInput::new()
+        .with_touch(0, TouchPhase::Ended, right)
+        .with_touch(1, TouchPhase::Moved, CENTER + Vec2::new(-10.0, 0.0));
+    manager.update_with_input(&input, 0.0);
+    let directions: Vec<_> = manager
+        .events()
+        .map(|(name, event)| (name, event.direction))
+        .collect();
+    assert_eq!(
+        directions,
+        [
+            ("first", JoystickDirection::Idle),
+            ("second", JoystickDirection::Left)
+        ]
+    );
+}
+
+#[test]
+fn mouse_is_claimed_by_one_joystick() {
+    let mut manager = overlapping();
+    let input = SimulatedInput::new().with_mouse(CENTER + Vec2::new(0.0, -10.0), true);
+    manager.update_with_input(&input, 0.0);
+    assert_eq!(
+        manager.event("first").unwrap().direction,
+        JoystickDirection::Up
+    );
+    assert!(manager.event("second").unwrap().is_idle());
+    assert!(!manager.get("second").unwrap().is_dragging());
+
+    let input = SimulatedInput::new().with_mouse(CENTER, false);
+    manager.update_with_input(&input, 0.0);
+    assert!(manager.events().all(|(_, event)| event.is_idle()));
+    assert_eq!(manager.index_of("second"), Some(1));
+    assert!(manager.get("third").is_none());
+}
+
+#[test]
+fn edit_mode_moves_and_resizes_controls() {
+    let mut manager = JoystickManager::new();
+    manager.add("move", Joystick::new(CENTER.x, CENTER.y, SIZE));
+    manager.add_button(
+        "jump",
+        TouchButton::rect(Rect::new(300.0, 200.0, 40.0, 40.0)),
+    );
+    let changes = Rc::new(RefCell::new(Vec::<LayoutChange>::new()));
+    let recorded = Rc::clone(&changes);
+    manager.on_layout_changed(Box::new(move |change| {
+        recorded.borrow_mut().push(change.clone())
+    }));
+
+    // hold the joystick and enter the edit mode
+    let press = SimulatedInput::new().with_touch(0, TouchPhase::Started, CENTER);
+    manager.update_with_input(&press, 0.0);
+    assert!(manager.get("move").unwrap().is_dragging());
+    manager.set_edit_mode(true);
+    assert!(!manager.get("move").unwrap().is_dragging());
+
+    // resize the joystick at its rim
+    let rim = CENTER + Vec2::new(24.0, 0.0);
+    let drag = |id, phase, position| SimulatedInput::new().with_touch(id, phase, position);
+    manager.update_with_input(&drag(1, TouchPhase::Started, rim), 0.0);
+    manager.update_with_input(&drag(1, TouchPhase::Moved, rim * 2.0 - CENTER), 0.0);
+    assert_eq!(manager.get("move").unwrap().size(), SIZE * 2.0);
+    assert!(changes.borrow().is_empty());
+    manager.update_with_input(&drag(1, TouchPhase::Ended, rim * 2.0 - CENTER), 0.0);
+    assert_eq!(
+        changes.borrow().last(),
+        Some(&LayoutChange {
+            name: "move".to_string(),
+            kind: ControlKind::Joystick,
+            shape: ButtonShape::Circle {
+                center: CENTER,
+                radius: SIZE,
+            },
+        })
+    );
+
+    // move the button with the mouse
+    let mouse = |position, down| SimulatedInput::new().with_mouse(position, down);
+    manager.update_with_input(&mouse(Vec2::new(320.0, 220.0), true), 0.0);
+    manager.update_with_input(&mouse(Vec2::new(220.0, 120.0), true), 0.0);
+    manager.update_with_input(&mouse(Vec2::new(220.0, 120.0), false), 0.0);
+    assert_eq!(
+        manager.button("jump").unwrap().shape(),
+        ButtonShape::Rect(Rect::new(200.0, 100.0, 40.0, 40.0))
+    );
+    assert_eq!(changes.borrow().len(), 2);
+    assert!(!manager.button("jump").unwrap().is_pressed());
+
+    // a tap doesn't change the layout
+    manager.update_with_input(&drag(2, TouchPhase::Started, CENTER), 0.0);
+    manager.update_with_input(&drag(2, TouchPhase::Ended, CENTER), 0.0);
+    assert_eq!(changes.borrow().len(), 2);
+
+    // the resized joystick is used after the edit mode
+    manager.set_edit_mode(false);
+    let press =
+        SimulatedInput::new().with_touch(3, TouchPhase::Started, CENTER + Vec2::new(40.0, 0.0));
+    manager.update_with_input(&press, 0.0);
+    assert_eq!(
+        manager.event("move").unwrap().direction,
+        JoystickDirection::Right
+    );
+}
+
+#[test]
+fn free_touches_exclude_the_touches_of_the_controls() {
+    let mut manager = JoystickManager::new();
+    manager.add("stick", Joystick::new(CENTER.x, CENTER.y, SIZE));
+    manager.add_button("jump", TouchButton::circle(300.0, 200.0, 30.0));
+    let stick = CENTER + Vec2::new(10.0, 0.0);
+    let jump = Vec2::new(300.0, 200.0);
+    let tap = Vec2::new(500.0, 100.0);
+
+    let ids = |manager: &JoystickManager, input: &SimulatedInput| -> Vec<u64> {
+        manager
+            .free_touches(input)
+            .iter()
+            .map(|touch| touch.id)
+            .collect()
+    };
+
+    let input = SimulatedInput::new()
+        .with_touch(0, TouchPhase::Started, stick)
+        .with_touch(1, TouchPhase::Started, jump)
+        .with_touch(2, TouchPhase::Started, tap);
+    manager.update_with_input(&input, 0.0);
+    assert_eq!(manager.get("stick").unwrap().active_touch(), Some(0));
+    assert_eq!(ids(&manager, &input), [2]);
+
+    // the releases don't count as taps
+    let input = SimulatedInput::new()
+        .with_touch(0, TouchPhase::Ended, stick)
+        .with_touch(1, TouchPhase::Ended, jump)
+        .with_touch(2, TouchPhase::Ended, tap);
+    manager.update_with_input(&input, 0.0);
+    assert_eq!(manager.get("stick").unwrap().active_touch(), None);
+    assert_eq!(ids(&manager, &input), [2]);
+
+    let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, tap);
+    manager.update_with_input(&input, 0.0);
+    assert_eq!(ids(&manager, &input), [0]);
+}
+
+#[test]
+fn removed_joystick_frees_its_touch() {
+    let mut manager = overlapping();
+    let right = CENTER + Vec2::new(10.0, 0.0);
+    let input = SimulatedInput::new().with_touch(0, TouchPhase::Started, right);
+    manager.update_with_input(&input, 0.0);
+    assert!(manager.get("first").unwrap().is_dragging());
+
+    let first = manager.remove("first").unwrap();
+    assert!(first.is_dragging());
+    assert!(manager.remove("first").is_none());
+    assert_eq!(manager.index_of("second"), Some(0));
+
+    let input = SimulatedInput::new().with_touch(1, TouchPhase::Started, right);
+    manager.update_with_input(&input, 0.0);
+    assert!(manager.get("second").unwrap().is_dragging());
+}
+
+#[test]
+fn gamepad_stick_reaches_the_managed_joysticks() {
+    let mut manager = overlapping();
+    let input = SimulatedInput::new().with_gamepad_stick(Vec2::new(0.0, -1.0));
+    manager.update_with_input(&input, 0.0);
+    assert_eq!(
+        manager.event("first").unwrap().direction,
+        JoystickDirection::Up
+    );
+    assert_eq!(
+        manager.event("second").unwrap().direction,
+        JoystickDirection::Up
+    );
+
+    manager.update_with_input(&SimulatedInput::new(), 0.0);
+    assert!(manager.event("first").unwrap().is_idle());
+}
+
+#[test]
+fn buttons_are_not_counted_as_joysticks() {
+    let mut manager = JoystickManager::new();
+    manager.add_button("jump", TouchButton::rect(Rect::new(0.0, 0.0, 40.0, 40.0)));
+    assert_eq!(manager.len(), 0);
+    assert!(manager.is_empty());
+    manager.add("move", Joystick::new(CENTER.x, CENTER.y, SIZE));
+    assert!(!manager.is_empty());
+}
+
+#[test]
+fn manager_is_updated_once_per_frame() {
+    let mut manager = overlapping();
+    let right = CENTER + Vec2::new(10.0, 0.0);
+    let press = SimulatedInput::new().with_touch(0, TouchPhase::Started, right);
+    manager.update_in_frame(&press, FrameStamp::new(0.016, 1.0));
+    // the release isn't processed in the same frame
+    let release = SimulatedInput::new().with_touch(0, TouchPhase::Ended, right);
+    manager.update_in_frame(&release, FrameStamp::new(0.016, 1.001));
+    assert_eq!(
+        manager.event("first").unwrap().direction,
+        JoystickDirection::Right
+    );
+
+    manager.update_in_frame(&release, FrameStamp::new(0.016, 1.02));
+    assert!(manager.event("first").unwrap().is_idle());
+}
@@ -0,0 +1,62 @@
+use macroquad::prelude::{Rect, TouchPhase, Vec2};
+use macroquad_virtual_joystick::{SimulatedInput, SliderOrientation, VirtualSlider};
+
+const TRACK: Rect = Rect {
+    x: 100.0,
+    y: 200.0,
+    w: 200.0,
+    h: 40.0,
+};
+
+fn drag(slider: &mut VirtualSlider, x: f32, down: bool) -> f32 {
+    let input = SimulatedInput::new().with_mouse(Vec2::new(x, 220.0), down);
+    slider.update_with_input(&input)
+}
+
+#[test]
+fn slider_springs_back_unless_sticky() {
+    let mut slider = VirtualSlider::new(TRACK, SliderOrientation::Horizontal);
+    slider.set_sticky(false);
+    slider.set_rest_value(0.5);
+    assert_eq!(slider.value(), 0.5);
+
+    assert_eq!(drag(&mut slider, 150.0, true), 0.25);
+    // leaving the track clamps the value
+    assert_eq!(drag(&mut slider, 400.0, true), 1.0);
+    assert_eq!(slider.knob_position(), Vec2::new(300.0, 220.0));
+    assert_eq!(drag(&mut slider, 400.0, false), 0.5);
+
+    slider.set_sticky(true);
+    drag(&mut slider, 150.0, true);
+    assert_eq!(drag(&mut slider, 150.0, false), 0.25);
+    // a press outside of the track is ignored
+    assert_eq!(drag(&mut slider, 50.0, true), 0.25);
+    assert!(!slider.is_pressed());
+}
+
+#[test]
+fn only_the_dragging_touch_moves_the_slider() {
+    let mut slider = VirtualSlider::new(TRACK, SliderOrientation::Horizontal);
+    slider.set_detents(vec![0.0, 0.5, 1.0]);
+    let touch = |id, phase, x| SimulatedInput::new().with_touch(id, phase, Vec2::new(x, 220.0));
+
+    assert_eq!(
+        slider.update_with_input(&touch(1, TouchPhase::Started, 260.0)),
+        0.8
+    );
+    let input = SimulatedInput::new()
+        .with_touch(1, TouchPhase::Moved, Vec2::new(195.0, 220.0))
+        .with_touch(2, TouchPhase::Started, Vec2::new(120.0, 220.0));
+    // snapped to the detent
+    assert_eq!(slider.update_with_input(&input), 0.5);
+    assert_eq!(
+        slider.update_with_input(&touch(2, TouchPhase::Ended, 120.0)),
+        0.5
+    );
+    assert!(slider.is_pressed());
+    assert_eq!(
+        slider.update_with_input(&touch(1, TouchPhase::Ended, 195.0)),
+        0.5
+    );
+    assert!(!slider.is_pressed());
+}
@@ -0,0 +1,232 @@
+use macroquad::prelude::{KeyCode, TouchPhase, Vec2};
+use macroquad_virtual_joystick::{
+    Flick, Joystick, JoystickDirection, JoystickRecorder, JoystickRuntimeState, SimulatedInput,
+    SwipeInterpolation, Turbo,
+};
+
+const CENTER: Vec2 = Vec2::new(100.0, 200.0);
+const SIZE: f32 = 50.0;
+
+fn joystick() -> Joystick {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_repeat(0.2, 0.1);
+    joystick.set_turbo(Some(Turbo {
+        threshold: 0.5,
+        rate_per_sec: 1.0,
+        max: 2.0,
+    }));
+    joystick
+}
+
+fn touch(phase: TouchPhase, position: Vec2) -> SimulatedInput {
+    SimulatedInput::new().with_touch(3, phase, position)
+}
+
+#[test]
+fn state_round_trips_through_serde_mid_drag() {
+    let mut original = joystick();
+    original.update_with_input(&touch(TouchPhase::Started, CENTER), 0.1);
+    for x in [5.0, 15.0, 25.0] {
+        let input = touch(TouchPhase::Moved, CENTER + Vec2::new(x, 0.0));
+        original.update_with_input(&input, 0.1);
+    }
+
+    let json = serde_json::to_string(&original.save_state()).unwrap();
+    let state: JoystickRuntimeState = serde_json::from_str(&json).unwrap();
+    let mut reloaded = joystick();
+    reloaded.restore_state(state);
+
+    // the same ongoing touch continues seamlessly
+    let inputs = [
+        touch(TouchPhase::Stationary, CENTER + Vec2::new(25.0, 0.0)),
+        touch(TouchPhase::Moved, CENTER + Vec2::new(0.0, 20.0)),
+        touch(TouchPhase::Ended, CENTER + Vec2::new(0.0, 20.0)),
+    ];
+    for input in &inputs {
+        let expected = original.update_with_input(input, 0.1);
+        let actual = reloaded.update_with_input(input, 0.1);
+        assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
+    }
+    assert_eq!(
+        original.take_repeats().collect::<Vec<_>>(),
+        reloaded.take_repeats().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn state_keeps_the_smoothing_and_the_return_animation() {
+    let configure = |joystick: &mut Joystick| {
+        joystick.set_smoothing(Some(0.5));
+        joystick.set_return_duration(0.5);
+    };
+    let mut original = joystick();
+    configure(&mut original);
+    original.update_with_input(&touch(TouchPhase::Started, CENTER), 0.1);
+    original.update_with_input(
+        &touch(TouchPhase::Moved, CENTER + Vec2::new(20.0, 0.0)),
+        0.1,
+    );
+
+    let mut reloaded = joystick();
+    configure(&mut reloaded);
+    reloaded.restore_state(original.save_state());
+    assert_eq!(reloaded.smoothed_position(), original.smoothed_position());
+
+    let inputs = [
+        touch(TouchPhase::Stationary, CENTER + Vec2::new(20.0, 0.0)),
+        touch(TouchPhase::Ended, CENTER + Vec2::new(20.0, 0.0)),
+    ];
+    for input in &inputs {
+        let expected = original.update_with_input(input, 0.1);
+        let actual = reloaded.update_with_input(input, 0.1);
+        assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
+    }
+
+    // restore in the middle of the return animation
+    let mut returning = joystick();
+    configure(&mut returning);
+    returning.restore_state(original.save_state());
+    assert_eq!(returning.rendered_knob(), original.rendered_knob());
+    assert_ne!(returning.rendered_knob(), CENTER);
+}
+
+#[test]
+fn state_keeps_the_flick_and_the_pending_events_mid_drag() {
+    let configure = |joystick: &mut Joystick| {
+        joystick.set_flick(Some(Flick {
+            min_speed: 2.0,
+            window: 0.2,
+        }));
+        joystick.set_swipe_interpolation(Some(SwipeInterpolation {
+            max_spacing: 5.0,
+            max_samples: 8,
+        }));
+    };
+    let mut original = joystick();
+    configure(&mut original);
+    original.update_with_input(&touch(TouchPhase::Started, CENTER), 0.05);
+    original.update_with_input(
+        &touch(TouchPhase::Moved, CENTER + Vec2::new(25.0, 0.0)),
+        0.05,
+    );
+
+    let json = serde_json::to_string(&original.save_state()).unwrap();
+    let state: JoystickRuntimeState = serde_json::from_str(&json).unwrap();
+    let mut reloaded = joystick();
+    configure(&mut reloaded);
+    reloaded.restore_state(state);
+
+    let expected = original.take_events().collect::<Vec<_>>();
+    assert!(!expected.is_empty());
+    let actual = reloaded.take_events().collect::<Vec<_>>();
+    assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
+
+    let release = touch(TouchPhase::Ended, CENTER + Vec2::new(25.0, 0.0));
+    let expected = original.update_with_input(&release, 0.05);
+    let actual = reloaded.update_with_input(&release, 0.05);
+    assert!(expected.flick.is_some());
+    assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
+}
+
+#[test]
+fn restore_into_resized_joystick_keeps_intensity() {
+    let mut original = joystick();
+    original.update_with_input(&touch(TouchPhase::Started, CENTER), 0.0);
+    let event = original.update_with_input(
+        &touch(TouchPhase::Moved, CENTER + Vec2::new(0.0, -20.0)),
+        0.0,
+    );
+    assert_eq!(event.intensity, 0.8);
+
+    let mut resized = Joystick::new(300.0, 300.0, 20.0);
+    resized.restore_state(original.save_state());
+    let event = resized.update_with_input(&touch(TouchPhase::Stationary, CENTER), 0.0);
+    assert_eq!(event.direction, JoystickDirection::Up);
+    assert_eq!(event.intensity, 0.8);
+
+    // the next movement is relative to the new center
+    let event = resized.update_with_input(&touch(TouchPhase::Moved, Vec2::new(320.0, 300.0)), 0.0);
+    assert_eq!(event.direction, JoystickDirection::Right);
+    assert_eq!(event.intensity, 1.0);
+}
+
+#[test]
+fn replay_reproduces_the_recorded_events() {
+    let mut original = joystick();
+    original.bind_keys(KeyCode::W, KeyCode::A, KeyCode::S, KeyCode::D);
+    let mut recorder = JoystickRecorder::new();
+    recorder.record(&mut original, &touch(TouchPhase::Started, CENTER), 0.1);
+    for x in [5.0, 15.0, 25.0] {
+        let input = touch(TouchPhase::Moved, CENTER + Vec2::new(x, 0.0));
+        recorder.record(&mut original, &input, 0.1);
+    }
+    let input = touch(TouchPhase::Ended, CENTER + Vec2::new(25.0, 0.0));
+    recorder.record(&mut original, &input, 0.1);
+    // unbound keys are not recorded
+    let input = SimulatedInput::new()
+        .with_key(KeyCode::S)
+        .with_key(KeyCode::Q);
+    recorder.record(&mut original, &input, 0.1);
+    assert_eq!(recorder.frames()[5].input.keys, [KeyCode::S]);
+    assert!((recorder.duration() - 0.6).abs() < 1e-6);
+
+    let mut ghost = joystick();
+    ghost.bind_keys(KeyCode::W, KeyCode::A, KeyCode::S, KeyCode::D);
+    let mut replay = recorder.replay();
+    assert_eq!(replay.advance_to(&mut ghost, 0.25).len(), 2);
+    let mut events = Vec::new();
+    while let Some(event) = replay.step(&mut ghost) {
+        events.push(event);
+    }
+    assert_eq!(events.len(), 4);
+    for (event, frame) in events.iter().zip(&recorder.frames()[2..]) {
+        assert_eq!(format!("{:?}", event), format!("{:?}", frame.event));
+    }
+    assert_eq!(events[3].direction, JoystickDirection::Down);
+    assert_eq!(
+        original.take_repeats().collect::<Vec<_>>(),
+        ghost.take_repeats().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn sticky_knob_is_replaced_by_the_next_drag() {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_sticky(true);
+    let right = CENTER + Vec2::new(10.0, 0.0);
+    joystick.update_with_input(&touch(TouchPhase::Started, right), 0.1);
+    joystick.update_with_input(&touch(TouchPhase::Ended, right), 0.1);
+    assert!(joystick.is_locked());
+    assert_eq!(joystick.event().direction, JoystickDirection::Right);
+
+    let up = CENTER + Vec2::new(0.0, -5.0);
+    joystick.update_with_input(&touch(TouchPhase::Started, up), 0.1);
+    let event = joystick.update_with_input(&touch(TouchPhase::Ended, up), 0.1);
+    assert_eq!(event.direction, JoystickDirection::Up);
+    assert_eq!(event.intensity, 0.2);
+
+    joystick.unlock();
+    assert!(joystick
+        .update_with_input(&SimulatedInput::new(), 0.1)
+        .is_idle());
+}
+
+#[test]
+fn disabling_sticky_restores_the_lock_threshold() {
+    let mut joystick = Joystick::new(CENTER.x, CENTER.y, SIZE);
+    joystick.set_direction_lock_threshold(0.5);
+    joystick.set_sticky(true);
+    joystick.set_sticky(true);
+    joystick.set_sticky(false);
+    joystick.set_direction_lock(true);
+
+    let weak = CENTER + Vec2::new(5.0, 0.0);
+    joystick.update_with_input(&touch(TouchPhase::Started, weak), 0.1);
+    joystick.update_with_input(&touch(TouchPhase::Ended, weak), 0.1);
+    assert!(!joystick.is_locked());
+
+    let strong = CENTER + Vec2::new(20.0, 0.0);
+    joystick.update_with_input(&touch(TouchPhase::Started, strong), 0.1);
+    joystick.update_with_input(&touch(TouchPhase::Ended, strong), 0.1);
+    assert!(joystick.is_locked());
+}
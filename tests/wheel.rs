@@ -0,0 +1,69 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use macroquad::prelude::{TouchPhase, Vec2};
+use macroquad_virtual_joystick::{SimulatedInput, Spring, VirtualWheel};
+
+const CENTER: Vec2 = Vec2::new(100.0, 200.0);
+
+fn at(angle: f32) -> Vec2 {
+    CENTER + Vec2::new(angle.cos(), angle.sin()) * 30.0
+}
+
+#[test]
+fn wheel_turns_across_the_negative_x_axis_up_to_the_limit() {
+    let mut wheel = VirtualWheel::new(CENTER.x, CENTER.y, 50.0);
+    wheel.set_limits(-PI, 1.5 * PI);
+    let touch = |phase, angle| SimulatedInput::new().with_touch(0, phase, at(angle));
+
+    // grab at the top and turn clockwise in quarters
+    wheel.update_with_input(&touch(TouchPhase::Started, -FRAC_PI_2), 0.0);
+    for angle in [0.0, FRAC_PI_2, PI, -FRAC_PI_2, 0.0] {
+        wheel.update_with_input(&touch(TouchPhase::Moved, angle), 0.0);
+    }
+    assert!((wheel.rotation() - 1.5 * PI).abs() < 1e-5);
+    assert_eq!(wheel.value(), 1.0);
+
+    // turning back counter-clockwise leaves the limit immediately
+    wheel.update_with_input(&touch(TouchPhase::Moved, -FRAC_PI_2), 0.0);
+    assert!((wheel.rotation() - PI).abs() < 1e-5);
+}
+
+#[test]
+fn return_spring_turns_the_released_wheel_back() {
+    let mut wheel = VirtualWheel::new(CENTER.x, CENTER.y, 50.0);
+    wheel.set_return_spring(Some(Spring {
+        stiffness: 200.0,
+        damping: 30.0,
+    }));
+    let drag = |wheel: &mut VirtualWheel, angle, down| {
+        let input = SimulatedInput::new().with_mouse(at(angle), down);
+        wheel.update_with_input(&input, 0.05)
+    };
+    drag(&mut wheel, -FRAC_PI_2, true);
+    drag(&mut wheel, -PI, true);
+    assert!((wheel.rotation() + FRAC_PI_2).abs() < 1e-5);
+    assert!((wheel.value() + 0.5).abs() < 1e-5);
+
+    let first = drag(&mut wheel, -PI, false);
+    assert!(first < 0.0 && first > -FRAC_PI_2);
+    assert!(!wheel.is_pressed());
+    for _ in 0..40 {
+        drag(&mut wheel, -PI, false);
+    }
+    assert_eq!(wheel.rotation(), 0.0);
+}
+
+#[test]
+fn return_spring_survives_a_huge_frame_time() {
+    let mut wheel = VirtualWheel::new(CENTER.x, CENTER.y, 50.0);
+    wheel.set_return_spring(Some(Spring {
+        stiffness: 200.0,
+        damping: 30.0,
+    }));
+    for dt in [1e9, f32::MAX, f32::INFINITY, f32::NAN] {
+        wheel.update_with_input(&SimulatedInput::new().with_mouse(at(-FRAC_PI_2), true), 0.0);
+        wheel.update_with_input(&SimulatedInput::new().with_mouse(at(-PI), true), 0.0);
+        wheel.update_with_input(&SimulatedInput::new().with_mouse(at(-PI), false), dt);
+        assert!(wheel.rotation().is_finite());
+    }
+}